@@ -0,0 +1,7 @@
+fn main() {
+    // Use a vendored protoc so builds don't depend on a system install
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+    std::env::set_var("PROTOC", protoc);
+
+    tonic_build::compile_protos("proto/qrms.proto").expect("compile qrms.proto");
+}
@@ -18,9 +18,13 @@ use sha2::{Sha256, Digest};
 use hex;
 use chrono::{DateTime, Utc};
 use std::collections::{VecDeque, HashMap};
+use std::fmt;
 
-use crate::apqc::AdaptivePqcLayer;
+use crate::apqc::{AdaptivePqcLayer, SignatureAlgorithm};
+use crate::crypto::{decrypt_transaction, CryptoError};
+use crate::ordering::{self, OrderingCandidate, OrderingContext};
 use crate::qrm::{QuantumResistanceMonitor, RiskAssessment};
+use crate::sequencer::{Sequencer, SubmitError, DEFAULT_MAX_MEMPOOL};
 
 /// Aegis-TEE attestation (TDX/SEV/SGX)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +36,8 @@ pub struct AegisTeeAttestation {
     pub mr_enclave: String,           // Measurement of enclave code
     pub mr_signer: String,            // Measurement of signer
     pub report_data: Vec<u8>,         // Custom report data (batch hash)
+    #[serde(default)]
+    pub block_number: u64,            // Block bound into report_data
     pub timestamp: DateTime<Utc>,
     pub aegis_verification: bool,      // Verified by Aegis-TEE infrastructure
     pub phala_redundancy: Option<PhalaRedundancyAttestation>, // Optional Phala redundancy
@@ -71,6 +77,40 @@ pub enum AssetType {
     CrossChainBridge,
 }
 
+impl AssetType {
+    /// Whether the asset lives on a chain (and so needs a chain id)
+    pub fn is_on_chain(&self) -> bool {
+        matches!(self, Self::OnChainToken | Self::OnChainNFT | Self::OnChainData | Self::CrossChainBridge)
+    }
+}
+
+impl AssetProtection {
+    /// Reject registrations whose location or policy cannot be enforced
+    pub fn validate(&self) -> Result<(), String> {
+        if self.asset_id.trim().is_empty() {
+            return Err("asset_id must not be empty".to_string());
+        }
+        if self.asset_type.is_on_chain() && self.chain_id.is_none() {
+            return Err(format!("{:?} assets require a chain_id", self.asset_type));
+        }
+        if !self.asset_type.is_on_chain() && (self.chain_id.is_some() || self.contract_address.is_some()) {
+            return Err(format!("{:?} assets cannot set chain_id or contract_address", self.asset_type));
+        }
+        if self.access_policy.risk_threshold > 10000 {
+            return Err(format!(
+                "risk_threshold {} exceeds 10000 basis points",
+                self.access_policy.risk_threshold
+            ));
+        }
+        if let Some(op) = self.access_policy.allowed_operations.iter()
+            .find(|op| op.trim().is_empty() || op.contains('('))
+        {
+            return Err(format!("invalid allowed operation '{}'", op));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessPolicy {
     pub allowed_operations: Vec<String>,
@@ -79,7 +119,41 @@ pub struct AccessPolicy {
     pub risk_threshold: u32,           // Minimum risk score to trigger protection
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Outcome of checking a transaction against asset access policies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Allow,
+    /// Not permitted yet; the transaction stays in the mempool
+    Defer(String),
+    /// Never permitted; the transaction is dropped
+    Deny(String),
+}
+
+impl AccessPolicy {
+    /// Check an operation against this policy at the current risk score
+    /// `pqc`/`tee` report whether the payload was PQC-sealed and is handled inside a TEE.
+    /// An empty `allowed_operations` permits every operation.
+    pub fn evaluate(&self, operation: &str, risk_score: u32, pqc: bool, tee: bool) -> PolicyDecision {
+        if !self.allowed_operations.is_empty() && !self.allowed_operations.iter().any(|op| op == operation) {
+            return PolicyDecision::Deny(format!("operation '{}' not allowed", operation));
+        }
+        if self.requires_pqc && !pqc {
+            return PolicyDecision::Defer("requires PQC transport".to_string());
+        }
+        if self.requires_tee && !tee {
+            return PolicyDecision::Defer("requires TEE execution".to_string());
+        }
+        if risk_score < self.risk_threshold {
+            return PolicyDecision::Defer(format!(
+                "risk {} below threshold {}",
+                risk_score, self.risk_threshold
+            ));
+        }
+        PolicyDecision::Allow
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MigrationState {
     Active,                            // Currently active
     Preparing,                         // Preparing for migration
@@ -88,11 +162,60 @@ pub enum MigrationState {
     Rollback,                          // Rolled back to previous state
 }
 
+impl MigrationState {
+    /// Whether the migration graph has an edge from `self` to `next`
+    /// Active→Preparing→Migrating→Migrated→Preparing (next migration);
+    /// Preparing/Migrating→Rollback→Active
+    pub fn can_transition_to(self, next: MigrationState) -> bool {
+        use MigrationState::*;
+        matches!(
+            (self, next),
+            (Active, Preparing)
+                | (Preparing, Migrating)
+                | (Migrating, Migrated)
+                | (Migrated, Preparing)
+                | (Preparing, Rollback)
+                | (Migrating, Rollback)
+                | (Rollback, Active)
+        )
+    }
+
+    /// Move to `next`, rejecting edges outside the migration graph
+    pub fn transition_to(&mut self, next: MigrationState) -> Result<(), MigrationError> {
+        if !self.can_transition_to(next) {
+            return Err(MigrationError::IllegalTransition { from: *self, to: next });
+        }
+        *self = next;
+        Ok(())
+    }
+}
+
+/// Migration state machine violations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    IllegalTransition { from: MigrationState, to: MigrationState },
+    /// Completion was requested with no migration running
+    NotInProgress,
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalTransition { from, to } => {
+                write!(f, "illegal migration transition {:?} -> {:?}", from, to)
+            }
+            Self::NotInProgress => write!(f, "no migration in progress"),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
 /// Encrypted transaction with asset context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedTransaction {
     pub tx_id: String,
-    pub encrypted_data: Vec<u8>,       // Encrypted with TEE key
+    pub encrypted_data: Vec<u8>,       // crypto::encrypt_transaction to the TEE public key
     pub asset_refs: Vec<String>,       // Referenced asset IDs
     pub priority_fee: u64,
     pub timestamp: DateTime<Utc>,
@@ -111,6 +234,17 @@ pub struct MigrationCheckpoint {
     pub pqc_signature: String,         // ML-DSA signature
 }
 
+/// What a migration checkpoint would capture, computed without touching state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationPreview {
+    /// Would-be checkpoint; `pqc_signature` is left empty
+    pub checkpoint: MigrationCheckpoint,
+    /// Registered assets that cannot currently enter `Preparing`
+    pub blocked_assets: Vec<String>,
+    /// Whether the active signature set could sign the checkpoint
+    pub signable: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetSnapshot {
     pub asset_id: String,
@@ -124,7 +258,9 @@ pub struct QuantumResistantBatch {
     pub batch_id: String,
     pub block_number: u64,
     pub transactions: Vec<DecryptedTransaction>,
+    #[serde(with = "hex_signature")]
     pub ml_dsa_sig: String,
+    #[serde(with = "hex_signature")]
     pub slh_dsa_sig: String,
     pub attestation: AegisTeeAttestation,
     pub risk_assessment: RiskAssessment,
@@ -133,6 +269,41 @@ pub struct QuantumResistantBatch {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Hex signatures stay strings in JSON but travel as raw bytes in binary
+/// encodings, halving the dominant share of a batch's size.
+mod hex_signature {
+    use serde::{ser::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(sig: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.serialize_str(sig);
+        }
+        match hex::decode(sig) {
+            Ok(bytes) => serializer.serialize_bytes(&bytes),
+            Err(_) => Err(S::Error::custom("signature is not hex")),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        if deserializer.is_human_readable() {
+            return String::deserialize(deserializer);
+        }
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Ok(hex::encode(bytes))
+    }
+}
+
+impl QuantumResistantBatch {
+    /// Compact binary encoding for storage and relay
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecryptedTransaction {
     pub tx_id: String,
@@ -144,7 +315,8 @@ pub struct DecryptedTransaction {
 }
 
 /// Intelligence-based ordering strategy
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum IntelligenceOrdering {
     RiskAware,                         // Order by risk level (high risk first)
     AssetProtection,                   // Prioritize protected assets
@@ -162,6 +334,7 @@ pub enum IntelligenceOrdering {
 pub struct AegisTeeSequencer {
     // Encrypted mempool (only decrypted inside TEE)
     encrypted_mempool: VecDeque<EncryptedTransaction>,
+    max_mempool: usize,
     
     // Asset registry
     asset_registry: HashMap<String, AssetProtection>,
@@ -211,6 +384,7 @@ impl AegisTeeSequencer {
 
         Self {
             encrypted_mempool: VecDeque::with_capacity(10000),
+            max_mempool: DEFAULT_MAX_MEMPOOL,
             asset_registry: HashMap::new(),
             migration_state: None,
             migration_in_progress: false,
@@ -234,12 +408,34 @@ impl AegisTeeSequencer {
     }
 
     /// Submit encrypted transaction (from outside TEE)
-    pub fn submit_encrypted(&mut self, encrypted_tx: EncryptedTransaction) {
+    /// Refused once the mempool holds `max_mempool` entries
+    pub fn submit_encrypted(&mut self, encrypted_tx: EncryptedTransaction) -> Result<(), SubmitError> {
+        if self.encrypted_mempool.len() >= self.max_mempool {
+            return Err(SubmitError::MempoolFull { capacity: self.max_mempool });
+        }
         self.encrypted_mempool.push_back(encrypted_tx);
+        Ok(())
+    }
+
+    /// Set mempool capacity (minimum 1)
+    pub fn set_max_mempool(&mut self, max_mempool: usize) {
+        self.max_mempool = max_mempool.max(1);
+    }
+
+    /// Pending encrypted transactions
+    pub fn mempool_size(&self) -> usize {
+        self.encrypted_mempool.len()
+    }
+
+    /// Mempool capacity
+    pub fn mempool_capacity(&self) -> usize {
+        self.max_mempool
     }
 
     /// Decrypt and order transactions (inside TEE only)
-    /// This function simulates TEE operation - in production, runs inside Aegis-TEE enclave
+    /// `tee_key` is the enclave's ML-KEM secret key; payloads that fail to
+    /// decrypt or authenticate under it are dropped. Transactions touching a
+    /// registered asset are held in the mempool until its access policy allows them.
     pub fn decrypt_and_order_intelligent(
         &mut self,
         tee_key: &[u8],  // TEE-protected decryption key
@@ -248,16 +444,35 @@ impl AegisTeeSequencer {
             return vec![];
         }
 
-        // Decrypt transactions (simulated - real implementation uses TEE key)
-        let mut decrypted: Vec<(DecryptedTransaction, u32, Vec<String>)> = Vec::new();
+        let mut decrypted: Vec<OrderingCandidate> = Vec::new();
+        let risk_score = self.qrm.calculate_risk_filtered(&[]).score;
+        let pending = std::mem::take(&mut self.encrypted_mempool);
         
-        for enc_tx in self.encrypted_mempool.iter() {
-            // In real TEE: decrypt with tee_key
-            // For now, simulate decryption
+        for enc_tx in pending {
+            let Some(plaintext) = decrypt_transaction(&enc_tx.encrypted_data, tee_key) else {
+                tracing::warn!("Dropping {}: payload failed to decrypt or authenticate", enc_tx.tx_id);
+                continue;
+            };
+            let data = String::from_utf8_lossy(&plaintext).to_string();
+
+            // Enforce access policies of referenced assets before releasing the payload
+            match self.check_access(&enc_tx.asset_refs, &data, risk_score) {
+                PolicyDecision::Allow => {}
+                PolicyDecision::Defer(reason) => {
+                    tracing::info!("Deferring {}: {}", enc_tx.tx_id, reason);
+                    self.encrypted_mempool.push_back(enc_tx);
+                    continue;
+                }
+                PolicyDecision::Deny(reason) => {
+                    tracing::warn!("Dropping {}: {}", enc_tx.tx_id, reason);
+                    continue;
+                }
+            }
+
             let decrypted_tx = DecryptedTransaction {
                 tx_id: enc_tx.tx_id.clone(),
-                sender: "0x".to_string() + &hex::encode(&enc_tx.encrypted_data[..8]),
-                data: String::from_utf8_lossy(&enc_tx.encrypted_data).to_string(),
+                sender: "0x".to_string() + &hex::encode(&plaintext[..plaintext.len().min(8)]),
+                data,
                 asset_refs: enc_tx.asset_refs.clone(),
                 priority_fee: enc_tx.priority_fee,
                 timestamp: enc_tx.timestamp,
@@ -266,128 +481,35 @@ impl AegisTeeSequencer {
             decrypted.push((
                 decrypted_tx,
                 enc_tx.risk_level,
-                enc_tx.asset_refs.clone(),
+                enc_tx.asset_refs,
             ));
         }
 
-        // Clear processed transactions
-        self.encrypted_mempool.clear();
-
         // Intelligence-based ordering
-        let ordered = match self.intelligence_mode {
-            IntelligenceOrdering::RiskAware => {
-                self.order_by_risk(decrypted)
-            }
-            IntelligenceOrdering::AssetProtection => {
-                self.order_by_asset_protection(decrypted)
-            }
-            IntelligenceOrdering::MigrationAware => {
-                self.order_by_migration(decrypted)
-            }
-            IntelligenceOrdering::Hybrid => {
-                self.order_hybrid(decrypted)
-            }
+        let ctx = OrderingContext {
+            asset_registry: &self.asset_registry,
+            migration_in_progress: self.migration_in_progress,
         };
+        let ordered = ordering::order(self.intelligence_mode, decrypted, ctx);
 
         ordered.into_iter().take(self.batch_size).collect()
     }
 
-    /// Order by risk level (high risk first for faster protection)
-    fn order_by_risk(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        txs.sort_by(|a, b| b.1.cmp(&a.1)); // Descending risk
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Order by asset protection priority
-    fn order_by_asset_protection(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        txs.sort_by(|a, b| {
-            let a_protected = a.2.iter()
-                .any(|asset_id| self.asset_registry.contains_key(asset_id));
-            let b_protected = b.2.iter()
-                .any(|asset_id| self.asset_registry.contains_key(asset_id));
-            
-            match (a_protected, b_protected) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.1.cmp(&b.1), // Fallback to risk
-            }
-        });
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Order by migration requirements
-    fn order_by_migration(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        if self.migration_in_progress {
-            // Group migration-related transactions
-            txs.sort_by(|a, b| {
-                let a_migration = a.0.tx_id.contains("migration");
-                let b_migration = b.0.tx_id.contains("migration");
-                
-                match (a_migration, b_migration) {
-                    (true, false) => std::cmp::Ordering::Less,
-                    (false, true) => std::cmp::Ordering::Greater,
-                    _ => a.1.cmp(&b.1),
-                }
-            });
-        } else {
-            txs.sort_by(|a, b| b.1.cmp(&a.1));
-        }
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Hybrid ordering: combines risk, asset protection, and migration
-    fn order_hybrid(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        // Score each transaction
-        txs.sort_by(|a, b| {
-            let a_score = self.calculate_priority_score(&a.0, a.1, &a.2);
-            let b_score = self.calculate_priority_score(&b.0, b.1, &b.2);
-            b_score.cmp(&a_score)
-        });
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Calculate priority score for hybrid ordering
-    fn calculate_priority_score(
-        &self,
-        tx: &DecryptedTransaction,
-        risk: u32,
-        asset_refs: &[String],
-    ) -> u64 {
-        let mut score = risk as u64 * 100;
-        
-        // Asset protection bonus
-        for asset_id in asset_refs {
-            if let Some(asset) = self.asset_registry.get(asset_id) {
-                if asset.access_policy.requires_pqc {
-                    score += 1000;
-                }
-                if asset.access_policy.requires_tee {
-                    score += 2000;
-                }
+    /// Check a decrypted payload against the policies of every asset it references
+    /// Any denial wins; otherwise the first deferral holds the transaction back.
+    fn check_access(&self, asset_refs: &[String], data: &str, risk_score: u32) -> PolicyDecision {
+        // Calls are encoded as `operation(args)`
+        let operation = data.split('(').next().unwrap_or_default().trim();
+        let mut decision = PolicyDecision::Allow;
+        for asset in asset_refs.iter().filter_map(|id| self.asset_registry.get(id)) {
+            // Payloads are ML-KEM sealed and only decrypted inside the enclave
+            match asset.access_policy.evaluate(operation, risk_score, true, true) {
+                deny @ PolicyDecision::Deny(_) => return deny,
+                defer @ PolicyDecision::Defer(_) if decision == PolicyDecision::Allow => decision = defer,
+                _ => {}
             }
         }
-        
-        // Migration bonus
-        if self.migration_in_progress && tx.tx_id.contains("migration") {
-            score += 5000;
-        }
-        
-        // Priority fee bonus
-        score += tx.priority_fee as u64;
-        
-        score
+        decision
     }
 
     /// Create quantum-resistant batch with intelligence
@@ -395,7 +517,7 @@ impl AegisTeeSequencer {
         &mut self,
         apqc: &mut AdaptivePqcLayer,
         tee_key: &[u8],
-    ) -> Option<QuantumResistantBatch> {
+    ) -> Result<Option<QuantumResistantBatch>, CryptoError> {
         // Get current risk assessment
         let risk = self.qrm.calculate_risk();
         
@@ -403,7 +525,7 @@ impl AegisTeeSequencer {
         let ordered_txs = self.decrypt_and_order_intelligent(tee_key);
         
         if ordered_txs.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         // Collect asset protections for this batch
@@ -427,14 +549,14 @@ impl AegisTeeSequencer {
         let batch_id = hex::encode(&hasher.finalize());
 
         // Sign with dual PQC
-        let signatures = apqc.sign_dual(&batch_data).await;
+        let signatures = apqc.sign_dual(&batch_data).await?;
 
         // Generate Aegis-TEE attestation (with optional Phala redundancy)
         let attestation = self.generate_aegis_attestation(&batch_id);
 
         // Create migration checkpoint if needed
         let checkpoint = if self.migration_in_progress {
-            Some(self.create_migration_checkpoint(&ordered_txs, apqc).await)
+            Some(self.create_migration_checkpoint(&ordered_txs, apqc).await?)
         } else {
             None
         };
@@ -455,21 +577,13 @@ impl AegisTeeSequencer {
         self.batches.push(batch.clone());
         self.current_block += 1;
 
-        Some(batch)
+        Ok(Some(batch))
     }
 
     /// Generate Aegis-TEE attestation (with optional Phala redundancy)
     fn generate_aegis_attestation(&self, batch_id: &str) -> AegisTeeAttestation {
-        let mut hasher = Sha256::new();
-        hasher.update(batch_id.as_bytes());
-        hasher.update(&self.current_block.to_be_bytes());
-        hasher.update(self.enclave_id.as_bytes());
-        let report_data = hasher.finalize().to_vec();
-
-        let mut mrenclave_hasher = Sha256::new();
-        mrenclave_hasher.update(b"QuantumAegis-AegisTEE-Enclave");
-        mrenclave_hasher.update(self.enclave_id.as_bytes());
-        let mr_enclave = hex::encode(&mrenclave_hasher.finalize()[..16]);
+        let report_data = attestation_report_data(batch_id, self.current_block, &self.enclave_id);
+        let mr_enclave = expected_mr_enclave(&self.enclave_id);
 
         let mut mrsigner_hasher = Sha256::new();
         mrsigner_hasher.update(b"QuantumAegis-AegisTEE-Signer");
@@ -493,6 +607,7 @@ impl AegisTeeSequencer {
             mr_enclave,
             mr_signer,
             report_data,
+            block_number: self.current_block,
             timestamp: Utc::now(),
             aegis_verification: true,
             phala_redundancy,
@@ -501,10 +616,7 @@ impl AegisTeeSequencer {
 
     /// Generate Phala Network redundancy attestation (for fallback/redundancy)
     fn generate_phala_redundancy_attestation(&self, batch_id: &str) -> PhalaRedundancyAttestation {
-        let mut hasher = Sha256::new();
-        hasher.update(batch_id.as_bytes());
-        hasher.update(self.phala_enclave_id.as_ref().unwrap().as_bytes());
-        let quote = hasher.finalize().to_vec();
+        let quote = phala_redundancy_quote(batch_id, self.phala_enclave_id.as_ref().unwrap());
 
         PhalaRedundancyAttestation {
             worker_id: self.phala_worker_id.as_ref().unwrap().clone(),
@@ -521,7 +633,38 @@ impl AegisTeeSequencer {
         &self,
         txs: &[DecryptedTransaction],
         apqc: &mut AdaptivePqcLayer,
-    ) -> MigrationCheckpoint {
+    ) -> Result<MigrationCheckpoint, CryptoError> {
+        let mut checkpoint = self.unsigned_checkpoint(txs);
+
+        // Sign checkpoint with PQC
+        let checkpoint_data = serde_json::to_vec(&checkpoint.asset_snapshots).unwrap_or_default();
+        let sig = apqc.sign_dual(&checkpoint_data).await?;
+        checkpoint.pqc_signature = sig.ml_dsa.signature;
+        Ok(checkpoint)
+    }
+
+    /// Preview the checkpoint a migration would produce for `txs`
+    /// Leaves `migration_in_progress`, asset states and stored checkpoints untouched
+    pub fn dry_run_migration(&self, txs: &[DecryptedTransaction], apqc: &AdaptivePqcLayer) -> MigrationPreview {
+        let mut blocked_assets: Vec<String> = self.asset_registry.values()
+            .filter(|asset| !asset.migration_state.can_transition_to(MigrationState::Preparing))
+            .map(|asset| asset.asset_id.clone())
+            .collect();
+        blocked_assets.sort();
+
+        let signable = [SignatureAlgorithm::MlDsa87, SignatureAlgorithm::SlhDsa256s]
+            .iter()
+            .all(|required| apqc.active_signatures.contains(required));
+
+        MigrationPreview {
+            checkpoint: self.unsigned_checkpoint(txs),
+            blocked_assets,
+            signable,
+        }
+    }
+
+    /// Snapshot the assets referenced by `txs` and hash them at the current block
+    fn unsigned_checkpoint(&self, txs: &[DecryptedTransaction]) -> MigrationCheckpoint {
         // Snapshot asset states
         let mut snapshots = Vec::new();
         for tx in txs {
@@ -546,28 +689,47 @@ impl AegisTeeSequencer {
         hasher.update(&self.current_block.to_be_bytes());
         let state_hash = hex::encode(&hasher.finalize());
 
-        // Sign checkpoint with PQC
-        let sig = apqc.sign_dual(&checkpoint_data).await;
-
         MigrationCheckpoint {
             checkpoint_id: format!("checkpoint_{}", self.current_block),
             block_number: self.current_block,
             state_hash,
             asset_snapshots: snapshots,
             timestamp: Utc::now(),
-            pqc_signature: sig.ml_dsa.signature,
+            pqc_signature: String::new(),
         }
     }
 
     /// Start migration process
-    pub fn start_migration(&mut self) {
+    /// Moves every registered asset →Preparing→Migrating; on error no asset changes
+    pub fn start_migration(&mut self) -> Result<(), MigrationError> {
+        self.transition_assets(None, &[MigrationState::Preparing, MigrationState::Migrating])?;
         self.migration_in_progress = true;
+        Ok(())
     }
 
     /// Complete migration
-    pub fn complete_migration(&mut self, checkpoint: MigrationCheckpoint) {
+    /// Moves the migrating assets to Migrated; assets registered after the
+    /// migration started are left as they are
+    pub fn complete_migration(&mut self, checkpoint: MigrationCheckpoint) -> Result<(), MigrationError> {
+        if !self.migration_in_progress {
+            return Err(MigrationError::NotInProgress);
+        }
+        self.transition_assets(Some(MigrationState::Migrating), &[MigrationState::Migrated])?;
         self.migration_state = Some(checkpoint);
         self.migration_in_progress = false;
+        Ok(())
+    }
+
+    /// Apply a sequence of transitions atomically to all assets, or only those in state `from`
+    fn transition_assets(&mut self, from: Option<MigrationState>, steps: &[MigrationState]) -> Result<(), MigrationError> {
+        let mut staged = self.asset_registry.clone();
+        for asset in staged.values_mut().filter(|a| from.is_none_or(|state| a.migration_state == state)) {
+            for &step in steps {
+                asset.migration_state.transition_to(step)?;
+            }
+        }
+        self.asset_registry = staged;
+        Ok(())
     }
 
     /// Get asset protection status
@@ -575,6 +737,13 @@ impl AegisTeeSequencer {
         self.asset_registry.get(asset_id)
     }
 
+    /// All registered assets, ordered by id
+    pub fn registered_assets(&self) -> Vec<&AssetProtection> {
+        let mut assets: Vec<_> = self.asset_registry.values().collect();
+        assets.sort_by(|a, b| a.asset_id.cmp(&b.asset_id));
+        assets
+    }
+
     /// Update QRM with new threat indicator
     pub fn update_threat(&mut self, indicator: crate::qrm::ThreatIndicator) {
         self.qrm.add_indicator(indicator);
@@ -591,6 +760,16 @@ impl AegisTeeSequencer {
         self.phala_worker_id = worker_id;
         self.phala_enclave_id = enclave_id;
     }
+
+    /// Get active intelligence ordering mode
+    pub fn intelligence_mode(&self) -> IntelligenceOrdering {
+        self.intelligence_mode
+    }
+
+    /// Switch intelligence ordering mode (applies to the next ordering pass)
+    pub fn set_intelligence_mode(&mut self, mode: IntelligenceOrdering) {
+        self.intelligence_mode = mode;
+    }
 }
 
 impl Default for AegisTeeSequencer {
@@ -604,5 +783,440 @@ impl Default for AegisTeeSequencer {
     }
 }
 
+#[async_trait::async_trait]
+impl Sequencer for AegisTeeSequencer {
+    type Tx = EncryptedTransaction;
+    type Ordered = DecryptedTransaction;
+    type Batch = QuantumResistantBatch;
+
+    fn submit(&mut self, tx: EncryptedTransaction) -> Result<(), SubmitError> {
+        self.submit_encrypted(tx)
+    }
+
+    fn order(&mut self, tee_key: &[u8]) -> Vec<DecryptedTransaction> {
+        self.decrypt_and_order_intelligent(tee_key)
+    }
+
+    async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer, tee_key: &[u8]) -> Result<Option<QuantumResistantBatch>, CryptoError> {
+        self.create_quantum_batch(apqc, tee_key).await
+    }
+
+    fn recent_batches(&self, count: usize) -> Vec<QuantumResistantBatch> {
+        self.get_recent_batches(count)
+    }
+}
+
+/// Outcome of checking an Aegis-TEE attestation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationVerdict {
+    pub valid: bool,
+    /// enclave_id equals the expected enclave
+    pub enclave_id_valid: bool,
+    /// report_data matches H(batch_id || block || expected enclave_id)
+    pub report_data_valid: bool,
+    /// Quote commits to the same report_data
+    pub quote_valid: bool,
+    /// mr_enclave equals the expected measurement
+    pub mr_enclave_valid: bool,
+    /// Phala redundancy quote is consistent (None when absent)
+    pub phala_redundancy_valid: Option<bool>,
+}
+
+/// Expected enclave measurement for an Aegis-TEE enclave id
+pub fn expected_mr_enclave(enclave_id: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"QuantumAegis-AegisTEE-Enclave");
+    hasher.update(enclave_id.as_bytes());
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// Report data binding a batch to a block and enclave
+fn attestation_report_data(batch_id: &str, block: u64, enclave_id: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(batch_id.as_bytes());
+    hasher.update(block.to_be_bytes());
+    hasher.update(enclave_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Quote produced by the Phala redundancy enclave for a batch
+fn phala_redundancy_quote(batch_id: &str, phala_enclave_id: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(batch_id.as_bytes());
+    hasher.update(phala_enclave_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Verify an attestation against the expected enclave, its measurement and the batch
+pub fn verify_attestation(
+    att: &AegisTeeAttestation,
+    expected_enclave_id: &str,
+    expected_mr_enclave: &str,
+    expected_batch_id: &str,
+) -> AttestationVerdict {
+    let enclave_id_valid = att.enclave_id == expected_enclave_id;
+    let expected_report = attestation_report_data(expected_batch_id, att.block_number, expected_enclave_id);
+    let report_data_valid = att.report_data == expected_report;
+    let quote_valid = att.quote == att.report_data;
+    let mr_enclave_valid = att.mr_enclave == expected_mr_enclave;
+
+    let phala_redundancy_valid = att.phala_redundancy.as_ref().map(|phala| {
+        phala.quote == phala_redundancy_quote(expected_batch_id, &phala.enclave_id)
+            && phala.quote_type == att.quote_type
+    });
+
+    AttestationVerdict {
+        valid: enclave_id_valid
+            && report_data_valid
+            && quote_valid
+            && mr_enclave_valid
+            && phala_redundancy_valid.unwrap_or(true),
+        enclave_id_valid,
+        report_data_valid,
+        quote_valid,
+        mr_enclave_valid,
+        phala_redundancy_valid,
+    }
+}
+
 // Re-export for backward compatibility and Phala integration
 pub use crate::phala_deploy::PhalaDeploymentConfig;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{encrypt_transaction, ThresholdCipher};
+
+    fn encrypted_tx(cipher: &ThresholdCipher, tx_id: &str, risk_level: u32, priority_fee: u64) -> EncryptedTransaction {
+        let payload = format!("payload-{}", tx_id);
+        EncryptedTransaction {
+            tx_id: tx_id.to_string(),
+            encrypted_data: encrypt_transaction(payload.as_bytes(), &cipher.public_key_bytes()).unwrap(),
+            asset_refs: vec![],
+            priority_fee,
+            timestamp: Utc::now(),
+            risk_level,
+            requires_migration: false,
+        }
+    }
+
+    fn order_with(mode: IntelligenceOrdering) -> Vec<String> {
+        let cipher = ThresholdCipher::generate();
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.set_intelligence_mode(mode);
+        // High fee outweighs moderate risk under hybrid scoring
+        sequencer.submit_encrypted(encrypted_tx(&cipher, "low_risk_high_fee", 10, 5000)).unwrap();
+        sequencer.submit_encrypted(encrypted_tx(&cipher, "high_risk", 50, 0)).unwrap();
+        sequencer.submit_encrypted(encrypted_tx(&cipher, "mid_risk", 30, 0)).unwrap();
+        sequencer.decrypt_and_order_intelligent(&cipher.secret_key_bytes())
+            .into_iter()
+            .map(|tx| tx.tx_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_mempool_payloads_require_tee_key() {
+        let cipher = ThresholdCipher::generate();
+        let tx = encrypted_tx(&cipher, "secret", 10, 0);
+        assert!(!String::from_utf8_lossy(&tx.encrypted_data).contains("payload-secret"));
+
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.submit_encrypted(tx.clone()).unwrap();
+        let wrong_key = ThresholdCipher::generate().secret_key_bytes();
+        assert!(sequencer.decrypt_and_order_intelligent(&wrong_key).is_empty());
+
+        sequencer.submit_encrypted(tx).unwrap();
+        let decrypted = sequencer.decrypt_and_order_intelligent(&cipher.secret_key_bytes());
+        assert_eq!(decrypted.len(), 1);
+        assert_eq!(decrypted[0].data, "payload-secret");
+    }
+
+    fn protected_asset(asset_id: &str) -> AssetProtection {
+        AssetProtection {
+            asset_id: asset_id.to_string(),
+            asset_type: AssetType::OnChainToken,
+            chain_id: Some(1),
+            contract_address: None,
+            encryption_key: vec![],
+            access_policy: AccessPolicy {
+                allowed_operations: vec![],
+                requires_pqc: true,
+                requires_tee: true,
+                risk_threshold: 50,
+            },
+            migration_state: MigrationState::Active,
+        }
+    }
+
+    fn checkpoint() -> MigrationCheckpoint {
+        MigrationCheckpoint {
+            checkpoint_id: "checkpoint_0".to_string(),
+            block_number: 0,
+            state_hash: String::new(),
+            asset_snapshots: vec![],
+            timestamp: Utc::now(),
+            pqc_signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_full_migration_sequence() {
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.register_asset(protected_asset("token"));
+
+        sequencer.start_migration().unwrap();
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Migrating);
+
+        sequencer.complete_migration(checkpoint()).unwrap();
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Migrated);
+
+        let mut state = MigrationState::Preparing;
+        state.transition_to(MigrationState::Rollback).unwrap();
+        state.transition_to(MigrationState::Active).unwrap();
+    }
+
+    #[test]
+    fn test_asset_registered_mid_migration_does_not_block_completion() {
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.register_asset(protected_asset("token"));
+        sequencer.start_migration().unwrap();
+        sequencer.register_asset(protected_asset("late"));
+
+        sequencer.complete_migration(checkpoint()).unwrap();
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Migrated);
+        assert_eq!(sequencer.get_asset_protection("late").unwrap().migration_state, MigrationState::Active);
+    }
+
+    #[test]
+    fn test_second_migration_starts_from_migrated() {
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.register_asset(protected_asset("token"));
+        sequencer.start_migration().unwrap();
+        sequencer.complete_migration(checkpoint()).unwrap();
+
+        sequencer.start_migration().unwrap();
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Migrating);
+        sequencer.complete_migration(checkpoint()).unwrap();
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Migrated);
+    }
+
+    #[tokio::test]
+    async fn test_migration_dry_run_matches_checkpoint_without_starting() {
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.register_asset(protected_asset("token"));
+        sequencer.register_asset(protected_asset("vault"));
+        sequencer.register_asset(protected_asset("idle"));
+
+        let txs: Vec<DecryptedTransaction> = ["token", "vault"].iter().map(|asset| DecryptedTransaction {
+            tx_id: format!("tx_{}", asset),
+            sender: String::new(),
+            data: String::new(),
+            asset_refs: vec![asset.to_string()],
+            priority_fee: 0,
+            timestamp: Utc::now(),
+        }).collect();
+
+        let mut apqc = AdaptivePqcLayer::new();
+        let preview = sequencer.dry_run_migration(&txs, &apqc);
+        assert!(preview.signable);
+        assert!(preview.blocked_assets.is_empty());
+        assert!(preview.checkpoint.pqc_signature.is_empty());
+        assert!(!sequencer.migration_in_progress);
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Active);
+
+        let real = sequencer.create_migration_checkpoint(&txs, &mut apqc).await.unwrap();
+        let ids = |c: &MigrationCheckpoint| c.asset_snapshots.iter().map(|s| s.asset_id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&preview.checkpoint), ids(&real));
+        assert_eq!(ids(&real), vec!["token", "vault"]);
+        assert_eq!(preview.checkpoint.state_hash, real.state_hash);
+        assert!(!real.pqc_signature.is_empty());
+    }
+
+    #[test]
+    fn test_illegal_migration_jump_rejected() {
+        let mut state = MigrationState::Active;
+        assert_eq!(
+            state.transition_to(MigrationState::Migrated),
+            Err(MigrationError::IllegalTransition { from: MigrationState::Active, to: MigrationState::Migrated })
+        );
+        assert_eq!(state, MigrationState::Active);
+
+        // Completing without starting leaves assets untouched
+        let mut sequencer = AegisTeeSequencer::default();
+        sequencer.register_asset(protected_asset("token"));
+        assert!(sequencer.complete_migration(checkpoint()).is_err());
+        assert_eq!(sequencer.get_asset_protection("token").unwrap().migration_state, MigrationState::Active);
+    }
+
+    fn severe_indicator(category: crate::qrm::ThreatCategory) -> crate::qrm::ThreatIndicator {
+        crate::qrm::ThreatIndicator {
+            category,
+            sub_category: "test".to_string(),
+            severity: 1.0,
+            confidence: 1.0,
+            source: "test".to_string(),
+            timestamp: Utc::now(),
+            description: "severe".to_string(),
+            era_relevance: crate::qrm::QuantumEra::Nisq,
+            references: vec![],
+            tags: vec![],
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_access_policy_defers_until_risk_threshold() {
+        let cipher = ThresholdCipher::generate();
+        let mut sequencer = AegisTeeSequencer::default();
+        let mut vault = protected_asset("vault");
+        vault.access_policy.risk_threshold = 5000;
+        sequencer.register_asset(vault);
+
+        let mut tx = encrypted_tx(&cipher, "guarded", 10, 0);
+        tx.asset_refs = vec!["vault".to_string()];
+        sequencer.submit_encrypted(tx).unwrap();
+        sequencer.submit_encrypted(encrypted_tx(&cipher, "free", 10, 0)).unwrap();
+
+        // Low risk: only the unguarded transaction is released
+        let released = sequencer.decrypt_and_order_intelligent(&cipher.secret_key_bytes());
+        assert_eq!(released.iter().map(|tx| tx.tx_id.as_str()).collect::<Vec<_>>(), vec!["free"]);
+        assert_eq!(sequencer.encrypted_mempool.len(), 1);
+
+        for category in crate::qrm::ThreatCategory::all() {
+            sequencer.update_threat(severe_indicator(*category));
+        }
+        let released = sequencer.decrypt_and_order_intelligent(&cipher.secret_key_bytes());
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].tx_id, "guarded");
+        assert!(sequencer.encrypted_mempool.is_empty());
+    }
+
+    #[test]
+    fn test_access_policy_drops_disallowed_operation() {
+        let mut policy = protected_asset("vault").access_policy;
+        policy.allowed_operations = vec!["transfer".to_string()];
+
+        assert_eq!(policy.evaluate("transfer", 6000, true, true), PolicyDecision::Allow);
+        assert!(matches!(policy.evaluate("approve", 6000, true, true), PolicyDecision::Deny(_)));
+        assert!(matches!(policy.evaluate("transfer", 6000, true, false), PolicyDecision::Defer(_)));
+    }
+
+    #[test]
+    fn test_intelligence_mode_defaults_to_hybrid() {
+        let sequencer = AegisTeeSequencer::default();
+        assert_eq!(sequencer.intelligence_mode(), IntelligenceOrdering::Hybrid);
+    }
+
+    #[test]
+    fn test_risk_aware_mode_changes_ordering() {
+        let hybrid = order_with(IntelligenceOrdering::Hybrid);
+        let risk_aware = order_with(IntelligenceOrdering::RiskAware);
+
+        assert_eq!(hybrid, vec!["low_risk_high_fee", "high_risk", "mid_risk"]);
+        assert_eq!(risk_aware, vec!["high_risk", "mid_risk", "low_risk_high_fee"]);
+    }
+
+    fn sequencer_with_phala() -> AegisTeeSequencer {
+        let mut sequencer = AegisTeeSequencer::new(
+            "aegis_worker_0".to_string(),
+            "aegis_enclave_0".to_string(),
+            "TDX".to_string(),
+            Some(("phala_worker_0".to_string(), "phala_enclave_0".to_string())),
+        );
+        sequencer.current_block = 42;
+        sequencer
+    }
+
+    #[test]
+    fn test_verify_attestation_accepts_valid() {
+        let att = sequencer_with_phala().generate_aegis_attestation("batch_1");
+
+        let verdict = verify_attestation(&att, "aegis_enclave_0", &expected_mr_enclave("aegis_enclave_0"), "batch_1");
+
+        assert!(verdict.valid, "{:?}", verdict);
+        assert_eq!(verdict.phala_redundancy_valid, Some(true));
+    }
+
+    #[test]
+    fn test_verify_attestation_rejects_tampered_report_data() {
+        let mut att = sequencer_with_phala().generate_aegis_attestation("batch_1");
+        att.report_data[0] ^= 0xff;
+
+        let verdict = verify_attestation(&att, "aegis_enclave_0", &expected_mr_enclave("aegis_enclave_0"), "batch_1");
+
+        assert!(!verdict.valid);
+        assert!(!verdict.report_data_valid);
+        assert!(verdict.mr_enclave_valid);
+    }
+
+    #[test]
+    fn test_verify_attestation_rejects_wrong_mr_enclave() {
+        let att = sequencer_with_phala().generate_aegis_attestation("batch_1");
+
+        let verdict = verify_attestation(&att, "aegis_enclave_0", &expected_mr_enclave("rogue_enclave"), "batch_1");
+
+        assert!(!verdict.valid);
+        assert!(!verdict.mr_enclave_valid);
+        assert!(verdict.report_data_valid);
+    }
+
+    #[test]
+    fn test_verify_attestation_rejects_unexpected_enclave() {
+        // A rogue enclave attesting consistently under its own id and measurement
+        let mut rogue = AegisTeeSequencer::new(
+            "rogue_worker".to_string(),
+            "rogue_enclave".to_string(),
+            "TDX".to_string(),
+            None,
+        );
+        rogue.current_block = 42;
+        let att = rogue.generate_aegis_attestation("batch_1");
+
+        let verdict = verify_attestation(&att, "aegis_enclave_0", &att.mr_enclave, "batch_1");
+
+        assert!(!verdict.valid);
+        assert!(!verdict.enclave_id_valid);
+        assert!(!verdict.report_data_valid);
+    }
+
+    #[tokio::test]
+    async fn test_batch_bincode_round_trip() {
+        let cipher = ThresholdCipher::generate();
+        let mut sequencer = sequencer_with_phala();
+        sequencer.update_threat(crate::qrm::ThreatIndicator {
+            category: crate::qrm::ThreatCategory::DigitalSignatures,
+            sub_category: "ecdsa".to_string(),
+            severity: 0.7,
+            confidence: 0.9,
+            source: "test".to_string(),
+            timestamp: Utc::now(),
+            description: "Shor against secp256k1".to_string(),
+            era_relevance: crate::qrm::QuantumEra::Nisq,
+            references: vec!["arXiv:quant-ph/9508027".to_string()],
+            tags: vec![],
+            expires_at: None,
+        });
+        for i in 0..4 {
+            sequencer.submit_encrypted(encrypted_tx(&cipher, &format!("tx_{}", i), 10, i)).unwrap();
+        }
+        let mut apqc = AdaptivePqcLayer::new();
+        let batch = sequencer
+            .create_quantum_batch(&mut apqc, &cipher.secret_key_bytes())
+            .await
+            .unwrap()
+            .expect("batch");
+
+        let binary = batch.to_bincode().unwrap();
+        let decoded = QuantumResistantBatch::from_bincode(&binary).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&batch).unwrap(),
+        );
+        assert_eq!(decoded.attestation.report_data, batch.attestation.report_data);
+        assert_eq!(decoded.risk_assessment.indicators.len(), 1);
+
+        // Byte fields cost one JSON number each; the binary form must stay well below
+        let json = serde_json::to_vec(&batch).unwrap();
+        assert!(binary.len() * 4 < json.len() * 3, "bincode {} vs json {}", binary.len(), json.len());
+    }
+}
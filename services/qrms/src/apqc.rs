@@ -2,16 +2,20 @@
 //! Manages concurrent redundant post-quantum cryptographic operations
 
 use serde::{Deserialize, Serialize};
-use sha2::{Sha256, Digest};
+use chrono::{DateTime, Utc};
+use sha2::Sha256;
+use hkdf::Hkdf;
 use hex;
 use rand::Rng;
+use std::fmt;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::crypto::{
-    MldsaKeyPair, SlhDsaKeyPair, MlKemKeyPair, HqcKeyPair, EcdsaKeyPair,
-    HybridSignature,
+    MldsaKeyPair, SlhDsaKeyPair, MlKemKeyPair, HqcKeyPair, EcdsaKeyPair, FalconKeyPair, FalconVariant,
+    HybridSignature, CryptoError, MldsaPublicKey, SlhDsaPublicKey, decode_signature_hex,
 };
+use crate::qrm::{QuantumEra, RiskRecommendation, ThreatCategory, ThreatIndicator};
 
 /// Signature algorithms
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,6 +24,10 @@ pub enum SignatureAlgorithm {
     MlDsa87,
     #[serde(rename = "SLH-DSA-256s")]
     SlhDsa256s,
+    #[serde(rename = "Falcon-512")]
+    Falcon512,
+    #[serde(rename = "Falcon-1024")]
+    Falcon1024,
 }
 
 impl SignatureAlgorithm {
@@ -27,13 +35,18 @@ impl SignatureAlgorithm {
         match self {
             Self::MlDsa87 => "ML-DSA-87",
             Self::SlhDsa256s => "SLH-DSA-256s",
+            Self::Falcon512 => "Falcon-512",
+            Self::Falcon1024 => "Falcon-1024",
         }
     }
 
+    /// Signature size in bytes (the maximum for variable-length Falcon)
     pub fn signature_size(&self) -> usize {
         match self {
             Self::MlDsa87 => 4595,
             Self::SlhDsa256s => 29792,
+            Self::Falcon512 => FalconKeyPair::signature_size(FalconVariant::Falcon512),
+            Self::Falcon1024 => FalconKeyPair::signature_size(FalconVariant::Falcon1024),
         }
     }
 
@@ -41,6 +54,17 @@ impl SignatureAlgorithm {
         match self {
             Self::MlDsa87 => 2592,
             Self::SlhDsa256s => 64,
+            Self::Falcon512 => FalconKeyPair::public_key_size(FalconVariant::Falcon512),
+            Self::Falcon1024 => FalconKeyPair::public_key_size(FalconVariant::Falcon1024),
+        }
+    }
+
+    /// Falcon parameter set, for the Falcon variants
+    pub fn falcon_variant(&self) -> Option<FalconVariant> {
+        match self {
+            Self::Falcon512 => Some(FalconVariant::Falcon512),
+            Self::Falcon1024 => Some(FalconVariant::Falcon1024),
+            _ => None,
         }
     }
 }
@@ -65,7 +89,7 @@ impl KemAlgorithm {
     pub fn ciphertext_size(&self) -> usize {
         match self {
             Self::MlKem1024 => 1568,
-            Self::Hqc256 => 6730,
+            Self::Hqc256 => 14421,
         }
     }
 }
@@ -79,11 +103,13 @@ pub struct SingleSignature {
     pub sign_time_ms: f64,
 }
 
-/// Dual signature combining ML-DSA and SLH-DSA
+/// Dual signature combining ML-DSA and SLH-DSA, plus Falcon when active
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DualSignature {
     pub ml_dsa: SingleSignature,
     pub slh_dsa: SingleSignature,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub falcon: Option<SingleSignature>,
     pub combined_size_bytes: usize,
 }
 
@@ -94,7 +120,12 @@ pub struct VerificationResult {
     pub mode: String,
     pub ml_dsa_valid: bool,
     pub slh_dsa_valid: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub falcon_valid: Option<bool>,
     pub verify_time_ms: f64,
+    /// Why a component could not be verified (malformed hex, wrong length, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Hybrid KEM encapsulation result
@@ -109,18 +140,108 @@ pub struct HybridKemResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KemPartResult {
     pub algorithm: String,
+    pub ciphertext: String,
     pub ciphertext_size: usize,
     pub encaps_time_ms: f64,
 }
 
+/// Per-algorithm KEM benchmark figures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KemAlgorithmBenchmark {
+    pub algorithm: String,
+    pub ciphertext_size: usize,
+    pub shared_secret_size: usize,
+    pub mean_encaps_time_ms: f64,
+    pub mean_decaps_time_ms: f64,
+}
+
+/// Wire and secret sizes of the hybrid KEM under a combiner mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KemCombinerBenchmark {
+    pub mode: CombinerMode,
+    pub combined_ct_size: usize,
+    pub combined_secret_size: usize,
+}
+
+/// KEM size and timing benchmark across both KEM families
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KemBenchmark {
+    pub iterations: usize,
+    pub ml_kem: KemAlgorithmBenchmark,
+    pub hqc: KemAlgorithmBenchmark,
+    pub combiners: Vec<KemCombinerBenchmark>,
+}
+
+/// Per-algorithm sign/verify latency figures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureAlgorithmBenchmark {
+    pub algorithm: String,
+    pub signature_size: usize,
+    pub mean_sign_time_ms: f64,
+    pub p95_sign_time_ms: f64,
+    pub mean_verify_time_ms: f64,
+    pub p95_verify_time_ms: f64,
+    /// Sign+verify cycles per second on one core, from the mean latencies
+    pub signatures_per_second: f64,
+}
+
+/// Signing cost model across the active signature algorithms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningBenchmark {
+    pub iterations: usize,
+    pub message_size: usize,
+    pub algorithms: Vec<SignatureAlgorithmBenchmark>,
+    /// Dual signatures per second when every active algorithm signs in turn
+    pub dual_signatures_per_second: f64,
+}
+
 /// Combiner mode for signature verification
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum CombinerMode {
     And,  // Both must be valid (security)
     Or,   // Either valid (availability)
 }
 
+/// Combiner policy that reacts to compromise disclosures and risk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdaptiveCombiner {
+    /// Algorithms under a compromise disclosure; their components never count
+    pub untrusted: Vec<SignatureAlgorithm>,
+}
+
+impl AdaptiveCombiner {
+    pub fn is_trusted(&self, algorithm: SignatureAlgorithm) -> bool {
+        !self.untrusted.contains(&algorithm)
+    }
+
+    /// Mode to verify with under the current risk recommendation
+    /// While an algorithm is distrusted, AND relaxes to OR over the remaining
+    /// algorithms so verification stays available; emergency risk keeps `base`.
+    pub fn select_mode(&self, base: CombinerMode, recommendation: RiskRecommendation) -> CombinerMode {
+        if self.untrusted.is_empty() || recommendation == RiskRecommendation::EmergencyRotation {
+            base
+        } else {
+            CombinerMode::Or
+        }
+    }
+}
+
+/// Disclosure status of a signature algorithm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlgorithmStatus {
+    pub algorithm: SignatureAlgorithm,
+    pub compromised: bool,
+    pub disclosed_at: Option<DateTime<Utc>>,
+}
+
+/// Default blocks between executed rotations
+pub const DEFAULT_ROTATION_COOLDOWN_BLOCKS: u64 = 50;
+
+/// Signature algorithms `sign_dual` always signs with
+pub const DUAL_SIGNATURE_ALGORITHMS: [SignatureAlgorithm; 2] =
+    [SignatureAlgorithm::MlDsa87, SignatureAlgorithm::SlhDsa256s];
+
 /// Adaptive PQC Layer
 pub struct AdaptivePqcLayer {
     pub active_signatures: Vec<SignatureAlgorithm>,
@@ -128,6 +249,16 @@ pub struct AdaptivePqcLayer {
     pub rotation_pending: bool,
     pub rotation_block: Option<u64>,
     pub key_generation_count: u64,
+    /// Rotations executed since startup
+    pub rotation_count: u64,
+    /// Block of the last rotation executed via `execute_rotation_at`
+    pub last_rotation_block: Option<u64>,
+    /// Blocks after a rotation during which no new rotation is started
+    pub rotation_cooldown_blocks: u64,
+    pub combiner_mode: CombinerMode,
+    pub adaptive_combiner: AdaptiveCombiner,
+    /// Algorithms with a recorded compromise disclosure
+    algorithm_status: Vec<AlgorithmStatus>,
     
     // Real PQC key pairs
     mldsa_keys: Arc<Mutex<MldsaKeyPair>>,
@@ -135,47 +266,169 @@ pub struct AdaptivePqcLayer {
     mlkem_keys: Arc<Mutex<MlKemKeyPair>>,
     hqc_keys: Arc<Mutex<HqcKeyPair>>,
     ecdsa_keys: Arc<Mutex<EcdsaKeyPair>>,
+    /// Present once `enable_falcon` has been called
+    falcon_keys: Arc<Mutex<Option<FalconKeyPair>>>,
     
     // Pending keys for rotation
     pending_mldsa_keys: Arc<Mutex<Option<MldsaKeyPair>>>,
     pending_slhdsa_keys: Arc<Mutex<Option<SlhDsaKeyPair>>>,
+
+    // Keys replaced by the last rotation, kept for rollback
+    previous_mldsa_keys: Arc<Mutex<Option<MldsaKeyPair>>>,
+    previous_slhdsa_keys: Arc<Mutex<Option<SlhDsaKeyPair>>>,
 }
 
 impl AdaptivePqcLayer {
     pub fn new() -> Self {
         Self {
-            active_signatures: vec![SignatureAlgorithm::MlDsa87, SignatureAlgorithm::SlhDsa256s],
+            active_signatures: DUAL_SIGNATURE_ALGORITHMS.to_vec(),
             active_kems: vec![KemAlgorithm::MlKem1024, KemAlgorithm::Hqc256],
             rotation_pending: false,
             rotation_block: None,
             key_generation_count: 0,
+            rotation_count: 0,
+            last_rotation_block: None,
+            rotation_cooldown_blocks: DEFAULT_ROTATION_COOLDOWN_BLOCKS,
+            combiner_mode: CombinerMode::And,
+            adaptive_combiner: AdaptiveCombiner::default(),
+            algorithm_status: Vec::new(),
             mldsa_keys: Arc::new(Mutex::new(MldsaKeyPair::generate())),
             slhdsa_keys: Arc::new(Mutex::new(SlhDsaKeyPair::generate())),
             mlkem_keys: Arc::new(Mutex::new(MlKemKeyPair::generate())),
             hqc_keys: Arc::new(Mutex::new(HqcKeyPair::generate())),
             ecdsa_keys: Arc::new(Mutex::new(EcdsaKeyPair::generate())),
+            falcon_keys: Arc::new(Mutex::new(None)),
+            pending_mldsa_keys: Arc::new(Mutex::new(None)),
+            pending_slhdsa_keys: Arc::new(Mutex::new(None)),
+            previous_mldsa_keys: Arc::new(Mutex::new(None)),
+            previous_slhdsa_keys: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Verification-only layer from public keys exported by `get_public_keys`
+    /// KEM key pairs are freshly generated since they play no part in verification.
+    pub fn from_public_keys(mldsa_pk: &[u8], slhdsa_pk: &[u8], ecdsa_pk: &[u8]) -> Result<Self, CryptoError> {
+        Ok(Self {
+            active_signatures: DUAL_SIGNATURE_ALGORITHMS.to_vec(),
+            active_kems: vec![KemAlgorithm::MlKem1024, KemAlgorithm::Hqc256],
+            rotation_pending: false,
+            rotation_block: None,
+            key_generation_count: 0,
+            rotation_count: 0,
+            last_rotation_block: None,
+            rotation_cooldown_blocks: DEFAULT_ROTATION_COOLDOWN_BLOCKS,
+            combiner_mode: CombinerMode::And,
+            adaptive_combiner: AdaptiveCombiner::default(),
+            algorithm_status: Vec::new(),
+            mldsa_keys: Arc::new(Mutex::new(MldsaKeyPair::from_public_key(mldsa_pk)?)),
+            slhdsa_keys: Arc::new(Mutex::new(SlhDsaKeyPair::from_public_key(slhdsa_pk)?)),
+            mlkem_keys: Arc::new(Mutex::new(MlKemKeyPair::generate())),
+            hqc_keys: Arc::new(Mutex::new(HqcKeyPair::generate())),
+            ecdsa_keys: Arc::new(Mutex::new(EcdsaKeyPair::from_public_key(ecdsa_pk)?)),
+            falcon_keys: Arc::new(Mutex::new(None)),
             pending_mldsa_keys: Arc::new(Mutex::new(None)),
             pending_slhdsa_keys: Arc::new(Mutex::new(None)),
+            previous_mldsa_keys: Arc::new(Mutex::new(None)),
+            previous_slhdsa_keys: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Add Falcon to the active signature set, replacing any other Falcon
+    /// parameter set, so `sign_dual` also produces a Falcon signature
+    pub async fn enable_falcon(&mut self, variant: FalconVariant) {
+        *self.falcon_keys.lock().await = Some(FalconKeyPair::generate(variant));
+        self.active_signatures.retain(|a| a.falcon_variant().is_none());
+        self.active_signatures.push(match variant {
+            FalconVariant::Falcon512 => SignatureAlgorithm::Falcon512,
+            FalconVariant::Falcon1024 => SignatureAlgorithm::Falcon1024,
+        });
+    }
+
+    /// Check a candidate signature set without applying it
+    /// Must be non-empty and duplicate-free, keep ML-DSA and SLH-DSA for
+    /// `sign_dual`, and only name a Falcon variant generated by `enable_falcon`.
+    pub async fn validate_signatures(&self, algorithms: &[SignatureAlgorithm]) -> Result<(), AlgorithmSetError> {
+        check_algorithm_set("signature", algorithms, SignatureAlgorithm::name)?;
+        let falcon = self.falcon_keys.lock().await.as_ref().map(|keys| keys.variant);
+        if let Some(missing) = algorithms.iter()
+            .find(|a| a.falcon_variant().is_some() && a.falcon_variant() != falcon)
+        {
+            return Err(AlgorithmSetError::MissingKeys(missing.name()));
         }
+        for required in DUAL_SIGNATURE_ALGORITHMS {
+            if !algorithms.contains(&required) {
+                return Err(AlgorithmSetError::Required(required.name()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the active signature set once `validate_signatures` accepts it
+    pub async fn set_active_signatures(&mut self, algorithms: Vec<SignatureAlgorithm>) -> Result<(), AlgorithmSetError> {
+        self.validate_signatures(&algorithms).await?;
+        self.active_signatures = algorithms;
+        Ok(())
+    }
+
+    /// Check a candidate KEM set without applying it; must be non-empty and duplicate-free
+    pub fn validate_kems(&self, kems: &[KemAlgorithm]) -> Result<(), AlgorithmSetError> {
+        check_algorithm_set("KEM", kems, KemAlgorithm::name)
+    }
+
+    /// Replace the active KEM set once `validate_kems` accepts it
+    pub fn set_active_kems(&mut self, kems: Vec<KemAlgorithm>) -> Result<(), AlgorithmSetError> {
+        self.validate_kems(&kems)?;
+        self.active_kems = kems;
+        Ok(())
+    }
+
+    /// Falcon keys, if a Falcon variant is in the active set
+    async fn active_falcon_keys(&self) -> Option<FalconKeyPair> {
+        let keys = self.falcon_keys.lock().await.clone()?;
+        self.active_signatures.iter()
+            .any(|a| a.falcon_variant() == Some(keys.variant))
+            .then_some(keys)
     }
 
     /// Sign message with dual PQC signatures (real implementation)
-    pub async fn sign_dual(&mut self, message: &[u8]) -> DualSignature {
-        self.key_generation_count += 1;
+    /// Fails on a verification-only layer built with `from_public_keys`, and
+    /// when ML-DSA or SLH-DSA has been dropped from the active set.
+    pub async fn sign_dual(&mut self, message: &[u8]) -> Result<DualSignature, CryptoError> {
+        for required in DUAL_SIGNATURE_ALGORITHMS {
+            if !self.active_signatures.contains(&required) {
+                return Err(CryptoError::AlgorithmInactive(required.name()));
+            }
+        }
 
         // Real ML-DSA signature
         let mldsa_keys = self.mldsa_keys.lock().await;
-        let (ml_sig_bytes, ml_time) = mldsa_keys.sign(message);
+        let (ml_sig_bytes, ml_time) = mldsa_keys.sign(message)?;
         let ml_sig = hex::encode(&ml_sig_bytes);
         drop(mldsa_keys);
 
         // Real SLH-DSA signature
         let slhdsa_keys = self.slhdsa_keys.lock().await;
-        let (slh_sig_bytes, slh_time) = slhdsa_keys.sign(message);
+        let (slh_sig_bytes, slh_time) = slhdsa_keys.sign(message)?;
         let slh_sig = hex::encode(&slh_sig_bytes);
         drop(slhdsa_keys);
 
-        DualSignature {
+        let falcon = match self.active_falcon_keys().await {
+            Some(keys) => {
+                let (sig_bytes, time) = keys.sign(message)?;
+                Some(SingleSignature {
+                    algorithm: keys.variant.name().to_string(),
+                    signature: hex::encode(&sig_bytes),
+                    size_bytes: sig_bytes.len(),
+                    sign_time_ms: time,
+                })
+            }
+            None => None,
+        };
+        let falcon_size = falcon.as_ref().map_or(0, |f| f.size_bytes);
+
+        self.key_generation_count += 1;
+
+        Ok(DualSignature {
             ml_dsa: SingleSignature {
                 algorithm: SignatureAlgorithm::MlDsa87.name().to_string(),
                 signature: ml_sig,
@@ -188,68 +441,139 @@ impl AdaptivePqcLayer {
                 size_bytes: slh_sig_bytes.len(),
                 sign_time_ms: slh_time,
             },
-            combined_size_bytes: ml_sig_bytes.len() + slh_sig_bytes.len(),
-        }
+            falcon,
+            combined_size_bytes: ml_sig_bytes.len() + slh_sig_bytes.len() + falcon_size,
+        })
     }
 
     /// Sign with hybrid scheme (ECDSA + PQC dual)
-    pub async fn sign_hybrid(&mut self, message: &[u8]) -> HybridSignature {
+    pub async fn sign_hybrid(&mut self, message: &[u8]) -> Result<HybridSignature, CryptoError> {
         // ECDSA signature
         let ecdsa_keys = self.ecdsa_keys.lock().await;
-        let (ecdsa_sig, _) = ecdsa_keys.sign(message);
+        let (ecdsa_sig, _) = ecdsa_keys.sign(message)?;
         drop(ecdsa_keys);
 
         // PQC dual signatures
-        let dual = self.sign_dual(message).await;
+        let dual = self.sign_dual(message).await?;
 
-        HybridSignature::new(
+        Ok(HybridSignature::new(
             ecdsa_sig,
             hex::decode(&dual.ml_dsa.signature).unwrap_or_default(),
             hex::decode(&dual.slh_dsa.signature).unwrap_or_default(),
-        )
+        ))
     }
 
     /// Verify dual signature (real implementation)
     pub async fn verify_dual(&self, message: &[u8], signature: &DualSignature, mode: CombinerMode) -> VerificationResult {
-        // Verify ML-DSA
-        let mldsa_keys = self.mldsa_keys.lock().await;
-        let ml_sig_bytes = hex::decode(&signature.ml_dsa.signature).unwrap_or_default();
-        let (ml_dsa_valid, ml_time) = if !ml_sig_bytes.is_empty() {
-            MldsaKeyPair::verify(message, &ml_sig_bytes, &mldsa_keys.public_key)
-        } else {
-            (false, 0.0)
-        };
-        drop(mldsa_keys);
+        let ml_pk = self.mldsa_keys.lock().await.public_key;
+        let slh_pk = self.slhdsa_keys.lock().await.public_key;
+        let falcon = self.falcon_keys.lock().await.clone();
+        let untrusted = &self.adaptive_combiner.untrusted;
+        verify_dual_with_keys(message, signature, &ml_pk, &slh_pk, falcon.as_ref(), mode, untrusted)
+    }
 
-        // Verify SLH-DSA
-        let slhdsa_keys = self.slhdsa_keys.lock().await;
-        let slh_sig_bytes = hex::decode(&signature.slh_dsa.signature).unwrap_or_default();
-        let (slh_dsa_valid, slh_time) = if !slh_sig_bytes.is_empty() {
-            SlhDsaKeyPair::verify(message, &slh_sig_bytes, &slhdsa_keys.public_key)
-        } else {
-            (false, 0.0)
-        };
-        drop(slhdsa_keys);
+    /// Verify with the combiner mode the adaptive policy picks for the current risk
+    pub async fn verify_dual_adaptive(
+        &self,
+        message: &[u8],
+        signature: &DualSignature,
+        recommendation: RiskRecommendation,
+    ) -> VerificationResult {
+        let mode = self.adaptive_combiner.select_mode(self.combiner_mode, recommendation);
+        self.verify_dual(message, signature, mode).await
+    }
 
-        let valid = match mode {
-            CombinerMode::And => ml_dsa_valid && slh_dsa_valid,
-            CombinerMode::Or => ml_dsa_valid || slh_dsa_valid,
-        };
+    /// Flag an algorithm as compromised (or clear the flag)
+    pub fn set_trusted(&mut self, algorithm: SignatureAlgorithm, trusted: bool) {
+        self.adaptive_combiner.untrusted.retain(|a| *a != algorithm);
+        if !trusted {
+            self.adaptive_combiner.untrusted.push(algorithm);
+        }
+    }
+
+    /// Record a real-world break of `algorithm` and stop trusting it
+    /// Returns the indicator to feed the QRM so the risk engine reacts.
+    pub fn mark_compromised(&mut self, algorithm: SignatureAlgorithm, description: &str) -> ThreatIndicator {
+        let now = Utc::now();
+        self.algorithm_status.retain(|s| s.algorithm != algorithm);
+        self.algorithm_status.push(AlgorithmStatus { algorithm, compromised: true, disclosed_at: Some(now) });
+        self.set_trusted(algorithm, false);
+
+        ThreatIndicator {
+            category: ThreatCategory::DigitalSignatures,
+            sub_category: format!("{} Compromise", algorithm.name()),
+            severity: 1.0,
+            confidence: 1.0,
+            source: "Compromise Disclosure".to_string(),
+            timestamp: now,
+            description: description.to_string(),
+            era_relevance: QuantumEra::PreQuantum,
+            references: vec![],
+            tags: vec!["compromise".to_string(), algorithm.name().to_string()],
+            expires_at: None,
+        }
+    }
+
+    /// Disclosure status of every algorithm marked compromised
+    pub fn algorithm_status(&self) -> &[AlgorithmStatus] {
+        &self.algorithm_status
+    }
+
+    pub fn is_compromised(&self, algorithm: SignatureAlgorithm) -> bool {
+        self.algorithm_status.iter().any(|s| s.algorithm == algorithm && s.compromised)
+    }
 
-        VerificationResult {
-            valid,
-            mode: format!("{:?}", mode).to_lowercase(),
-            ml_dsa_valid,
-            slh_dsa_valid,
-            verify_time_ms: ml_time + slh_time,
+    /// Raise `recommendation` to at least a scheduled rotation while an active
+    /// signature algorithm is compromised
+    pub fn compromise_recommendation(&self, recommendation: RiskRecommendation) -> RiskRecommendation {
+        let active_compromised = self.active_signatures.iter().any(|a| self.is_compromised(*a));
+        match recommendation {
+            RiskRecommendation::Continue | RiskRecommendation::MonitorClosely if active_compromised => {
+                RiskRecommendation::ScheduleRotation
+            }
+            other => other,
         }
     }
 
+    /// Verify many dual signatures in parallel, preserving input order
+    /// Keys are read once up front, so the mutexes are not held while verifying.
+    pub async fn verify_dual_batch(&self, items: &[(Vec<u8>, DualSignature)], mode: CombinerMode) -> Vec<VerificationResult> {
+        let ml_pk = self.mldsa_keys.lock().await.public_key;
+        let slh_pk = self.slhdsa_keys.lock().await.public_key;
+        let falcon = self.falcon_keys.lock().await.clone();
+
+        let handles: Vec<_> = items.iter()
+            .cloned()
+            .map(|(message, signature)| {
+                let falcon = falcon.clone();
+                let untrusted = self.adaptive_combiner.untrusted.clone();
+                tokio::task::spawn_blocking(move || {
+                    verify_dual_with_keys(&message, &signature, &ml_pk, &slh_pk, falcon.as_ref(), mode, &untrusted)
+                })
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(handles.len());
+        for handle in handles {
+            results.push(handle.await.unwrap_or_else(|_| VerificationResult {
+                valid: false,
+                mode: format!("{:?}", mode).to_lowercase(),
+                ml_dsa_valid: false,
+                slh_dsa_valid: false,
+                falcon_valid: None,
+                verify_time_ms: 0.0,
+                error: Some("verification task failed".to_string()),
+            }));
+        }
+        results
+    }
+
     /// Verify hybrid signature (ECDSA + PQC)
     pub async fn verify_hybrid(&self, message: &[u8], hybrid_sig: &HybridSignature) -> bool {
         // Verify ECDSA
         let ecdsa_keys = self.ecdsa_keys.lock().await;
-        let (ecdsa_valid, _) = EcdsaKeyPair::verify(message, &hybrid_sig.ecdsa_sig, &ecdsa_keys.verifying_key);
+        let ecdsa_valid = EcdsaKeyPair::verify(message, &hybrid_sig.ecdsa_sig, &ecdsa_keys.verifying_key)
+            .is_ok_and(|(valid, _)| valid);
         drop(ecdsa_keys);
 
         // Verify PQC dual
@@ -266,9 +590,10 @@ impl AdaptivePqcLayer {
                 size_bytes: hybrid_sig.slhdsa_sig.len(),
                 sign_time_ms: 0.0,
             },
+            falcon: None,
             combined_size_bytes: hybrid_sig.total_size(),
         };
-        let pqc_result = self.verify_dual(message, &dual_sig, CombinerMode::And).await;
+        let pqc_result = self.verify_dual(message, &dual_sig, self.combiner_mode).await;
 
         // Both ECDSA and PQC must be valid
         ecdsa_valid && pqc_result.valid
@@ -286,20 +611,18 @@ impl AdaptivePqcLayer {
         let (hqc_ct, hqc_ss, hqc_time) = hqc_keys.encapsulate();
         drop(hqc_keys);
 
-        // Combine shared secrets
-        let mut hasher = Sha256::new();
-        hasher.update(&ml_ss);
-        hasher.update(&hqc_ss);
-        let shared_secret = hex::encode(&hasher.finalize()[..16]);
+        let shared_secret = hex::encode(combine_kem_secrets(&ml_ss, &hqc_ss));
 
         HybridKemResult {
             ml_kem: KemPartResult {
                 algorithm: KemAlgorithm::MlKem1024.name().to_string(),
+                ciphertext: hex::encode(&ml_ct),
                 ciphertext_size: ml_ct.len(),
                 encaps_time_ms: ml_time,
             },
             hqc: KemPartResult {
                 algorithm: KemAlgorithm::Hqc256.name().to_string(),
+                ciphertext: hex::encode(&hqc_ct),
                 ciphertext_size: hqc_ct.len(),
                 encaps_time_ms: hqc_time,
             },
@@ -308,6 +631,48 @@ impl AdaptivePqcLayer {
         }
     }
 
+    /// Recover the combined hybrid KEM secret from both ciphertexts
+    pub async fn decapsulate_hybrid(&self, ml_kem_ct: &[u8], hqc_ct: &[u8]) -> Option<String> {
+        let mlkem_keys = self.mlkem_keys.lock().await;
+        let (ml_ss, _) = mlkem_keys.decapsulate(ml_kem_ct)?;
+        drop(mlkem_keys);
+
+        let hqc_keys = self.hqc_keys.lock().await;
+        let (hqc_ss, _) = hqc_keys.decapsulate(hqc_ct)?;
+        drop(hqc_keys);
+
+        Some(hex::encode(combine_kem_secrets(&ml_ss, &hqc_ss)))
+    }
+
+    /// Benchmark ML-KEM and HQC sizes and encaps/decaps timings
+    pub async fn benchmark_kems(&self, iterations: usize) -> KemBenchmark {
+        self.kem_keys().await.benchmark_kems(iterations)
+    }
+
+    /// Copy of the KEM key pairs, usable without holding this layer
+    pub async fn kem_keys(&self) -> KemKeys {
+        KemKeys {
+            mlkem: self.mlkem_keys.lock().await.clone(),
+            hqc: self.hqc_keys.lock().await.clone(),
+        }
+    }
+
+    /// Benchmark sign+verify latency of each active signature algorithm
+    /// Fails on a verification-only layer built with `from_public_keys`.
+    pub async fn benchmark_signatures(&self, iterations: usize) -> Result<SigningBenchmark, CryptoError> {
+        self.signing_keys().await.benchmark_signatures(iterations)
+    }
+
+    /// Copy of the active signature set and its keys, usable without holding this layer
+    pub async fn signing_keys(&self) -> SigningKeys {
+        SigningKeys {
+            algorithms: self.active_signatures.clone(),
+            mldsa: self.mldsa_keys.lock().await.clone(),
+            slhdsa: self.slhdsa_keys.lock().await.clone(),
+            falcon: self.active_falcon_keys().await,
+        }
+    }
+
     /// Generate new key pairs for rotation
     pub async fn generate_rotation_keys(&mut self) {
         *self.pending_mldsa_keys.lock().await = Some(MldsaKeyPair::generate());
@@ -322,16 +687,20 @@ impl AdaptivePqcLayer {
     }
 
     /// Execute rotation (swap to pending keys)
+    /// The replaced keys are kept so `rollback_rotation` can restore them.
     pub async fn execute_rotation(&mut self) -> RotationResult {
         if let Some(new_mldsa) = self.pending_mldsa_keys.lock().await.take() {
-            *self.mldsa_keys.lock().await = new_mldsa;
+            let old = std::mem::replace(&mut *self.mldsa_keys.lock().await, new_mldsa);
+            *self.previous_mldsa_keys.lock().await = Some(old);
         }
         if let Some(new_slhdsa) = self.pending_slhdsa_keys.lock().await.take() {
-            *self.slhdsa_keys.lock().await = new_slhdsa;
+            let old = std::mem::replace(&mut *self.slhdsa_keys.lock().await, new_slhdsa);
+            *self.previous_slhdsa_keys.lock().await = Some(old);
         }
         
         self.rotation_pending = false;
         self.rotation_block = None;
+        self.rotation_count += 1;
         
         RotationResult {
             status: "rotated".to_string(),
@@ -339,6 +708,46 @@ impl AdaptivePqcLayer {
         }
     }
 
+    /// Execute rotation and record the block it happened at for cooldown tracking
+    pub async fn execute_rotation_at(&mut self, block: u64) -> RotationResult {
+        let result = self.execute_rotation().await;
+        self.last_rotation_block = Some(block);
+        result
+    }
+
+    /// Whether the cooldown since the last recorded rotation has elapsed
+    pub fn rotation_allowed(&self, current_block: u64) -> bool {
+        self.last_rotation_block.is_none_or(|last| {
+            current_block >= last.saturating_add(self.rotation_cooldown_blocks)
+        })
+    }
+
+    /// Restore the key pairs replaced by the last rotation
+    pub async fn rollback_rotation(&mut self) -> Result<RotationResult, RotationError> {
+        let previous_mldsa = self.previous_mldsa_keys.lock().await.take();
+        let previous_slhdsa = self.previous_slhdsa_keys.lock().await.take();
+        if previous_mldsa.is_none() && previous_slhdsa.is_none() {
+            return Err(RotationError::NoPreviousKeys);
+        }
+
+        if let Some(keys) = previous_mldsa {
+            *self.mldsa_keys.lock().await = keys;
+        }
+        if let Some(keys) = previous_slhdsa {
+            *self.slhdsa_keys.lock().await = keys;
+        }
+
+        Ok(RotationResult {
+            status: "rolled_back".to_string(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    /// Sign with the active ML-DSA key only (cheap integrity tag for event streams)
+    pub async fn sign_mldsa(&self, message: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.mldsa_keys.lock().await.sign(message).map(|(sig, _)| sig)
+    }
+
     /// Get public keys for on-chain registration
     pub async fn get_public_keys(&self) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
         let mldsa = self.mldsa_keys.lock().await.public_key_bytes();
@@ -354,8 +763,578 @@ pub struct RotationResult {
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Errors from rotation management
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RotationError {
+    /// No rotation has replaced keys since startup or the last rollback
+    NoPreviousKeys,
+}
+
+impl fmt::Display for RotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPreviousKeys => write!(f, "no previous keys to roll back to"),
+        }
+    }
+}
+
+impl std::error::Error for RotationError {}
+
+/// Errors from reconfiguring the active algorithm sets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlgorithmSetError {
+    /// The named set ("signature" or "KEM") would be left empty
+    Empty(&'static str),
+    Duplicate(&'static str),
+    /// The algorithm has no key pair to sign with
+    MissingKeys(&'static str),
+    /// `sign_dual` cannot sign without the algorithm
+    Required(&'static str),
+}
+
+impl fmt::Display for AlgorithmSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty(set) => write!(f, "at least one {} algorithm must remain active", set),
+            Self::Duplicate(algorithm) => write!(f, "{} is listed more than once", algorithm),
+            Self::MissingKeys(algorithm) => write!(f, "no {} keys have been generated", algorithm),
+            Self::Required(algorithm) => write!(f, "{} is required for dual signing", algorithm),
+        }
+    }
+}
+
+impl std::error::Error for AlgorithmSetError {}
+
+/// Reject empty sets and repeated algorithms
+fn check_algorithm_set<A: PartialEq>(
+    set: &'static str,
+    algorithms: &[A],
+    name: impl Fn(&A) -> &'static str,
+) -> Result<(), AlgorithmSetError> {
+    if algorithms.is_empty() {
+        return Err(AlgorithmSetError::Empty(set));
+    }
+    for (i, algorithm) in algorithms.iter().enumerate() {
+        if algorithms[..i].contains(algorithm) {
+            return Err(AlgorithmSetError::Duplicate(name(algorithm)));
+        }
+    }
+    Ok(())
+}
+
+/// Domain-separation salt for the hybrid KEM combiner
+const HYBRID_KEM_SALT: &[u8] = b"QuantumAegis-HybridKEM-v1";
+
+/// HKDF info binding the derived key to the component KEMs
+const HYBRID_KEM_INFO: &[u8] = b"ML-KEM-1024+HQC-256 shared secret";
+
+/// Derive the 256-bit hybrid secret from both component secrets via HKDF-SHA256
+fn combine_kem_secrets(ml_kem_ss: &[u8], hqc_ss: &[u8]) -> [u8; 32] {
+    let mut ikm = Vec::with_capacity(ml_kem_ss.len() + hqc_ss.len());
+    ikm.extend_from_slice(ml_kem_ss);
+    ikm.extend_from_slice(hqc_ss);
+
+    let mut okm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(HYBRID_KEM_SALT), &ikm)
+        .expand(HYBRID_KEM_INFO, &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    okm
+}
+
+/// KEM key pairs copied out of an `AdaptivePqcLayer`
+#[derive(Clone)]
+pub struct KemKeys {
+    mlkem: MlKemKeyPair,
+    hqc: HqcKeyPair,
+}
+
+impl KemKeys {
+    /// Benchmark ML-KEM and HQC sizes and encaps/decaps timings; blocks for the whole run
+    pub fn benchmark_kems(&self, iterations: usize) -> KemBenchmark {
+        let iterations = iterations.max(1);
+
+        let (mut ml_ct_size, mut ml_ss) = (0, Vec::new());
+        let (mut ml_encaps, mut ml_decaps) = (0.0, 0.0);
+        for _ in 0..iterations {
+            let (ct, ss, encaps_time) = self.mlkem.encapsulate();
+            let decaps_time = self.mlkem.decapsulate(&ct).map(|(_, t)| t).unwrap_or(0.0);
+            ml_ct_size = ct.len();
+            ml_ss = ss;
+            ml_encaps += encaps_time;
+            ml_decaps += decaps_time;
+        }
+
+        let (mut hqc_ct_size, mut hqc_ss) = (0, Vec::new());
+        let (mut hqc_encaps, mut hqc_decaps) = (0.0, 0.0);
+        for _ in 0..iterations {
+            let (ct, ss, encaps_time) = self.hqc.encapsulate();
+            let decaps_time = self.hqc.decapsulate(&ct).map(|(_, t)| t).unwrap_or(0.0);
+            hqc_ct_size = ct.len();
+            hqc_ss = ss;
+            hqc_encaps += encaps_time;
+            hqc_decaps += decaps_time;
+        }
+
+        // AND derives one secret from both components; OR accepts either
+        // component's secret, so both ciphertexts still travel on the wire
+        let and_secret_size = combine_kem_secrets(&ml_ss, &hqc_ss).len();
+        let combined_ct_size = ml_ct_size + hqc_ct_size;
+        let (ml_ss_size, hqc_ss_size) = (ml_ss.len(), hqc_ss.len());
+
+        KemBenchmark {
+            iterations,
+            ml_kem: KemAlgorithmBenchmark {
+                algorithm: KemAlgorithm::MlKem1024.name().to_string(),
+                ciphertext_size: ml_ct_size,
+                shared_secret_size: ml_ss_size,
+                mean_encaps_time_ms: ml_encaps / iterations as f64,
+                mean_decaps_time_ms: ml_decaps / iterations as f64,
+            },
+            hqc: KemAlgorithmBenchmark {
+                algorithm: KemAlgorithm::Hqc256.name().to_string(),
+                ciphertext_size: hqc_ct_size,
+                shared_secret_size: hqc_ss_size,
+                mean_encaps_time_ms: hqc_encaps / iterations as f64,
+                mean_decaps_time_ms: hqc_decaps / iterations as f64,
+            },
+            combiners: vec![
+                KemCombinerBenchmark {
+                    mode: CombinerMode::And,
+                    combined_ct_size,
+                    combined_secret_size: and_secret_size,
+                },
+                KemCombinerBenchmark {
+                    mode: CombinerMode::Or,
+                    combined_ct_size,
+                    combined_secret_size: ml_ss_size.max(hqc_ss_size),
+                },
+            ],
+        }
+    }
+}
+
+/// Active signing keys copied out of an `AdaptivePqcLayer`
+#[derive(Clone)]
+pub struct SigningKeys {
+    pub algorithms: Vec<SignatureAlgorithm>,
+    mldsa: MldsaKeyPair,
+    slhdsa: SlhDsaKeyPair,
+    falcon: Option<FalconKeyPair>,
+}
+
+impl SigningKeys {
+    /// Benchmark sign+verify latency of each algorithm; blocks for the whole run
+    /// Fails for keys copied from a verification-only layer.
+    pub fn benchmark_signatures(&self, iterations: usize) -> Result<SigningBenchmark, CryptoError> {
+        let iterations = iterations.max(1);
+        // Roughly one serialized batch of transactions
+        let message = vec![0x5a; 1024];
+
+        let mut algorithms = Vec::with_capacity(self.algorithms.len());
+        for algorithm in &self.algorithms {
+            let mut sign_times = Vec::with_capacity(iterations);
+            let mut verify_times = Vec::with_capacity(iterations);
+            let mut signature_size = 0;
+            match algorithm {
+                SignatureAlgorithm::MlDsa87 => {
+                    let keys = &self.mldsa;
+                    for _ in 0..iterations {
+                        let (sig, sign_time) = keys.sign(&message)?;
+                        let (_, verify_time) = MldsaKeyPair::verify(&message, &sig, &keys.public_key)?;
+                        signature_size = sig.len();
+                        sign_times.push(sign_time);
+                        verify_times.push(verify_time);
+                    }
+                }
+                SignatureAlgorithm::SlhDsa256s => {
+                    let keys = &self.slhdsa;
+                    for _ in 0..iterations {
+                        let (sig, sign_time) = keys.sign(&message)?;
+                        let (_, verify_time) = SlhDsaKeyPair::verify(&message, &sig, &keys.public_key)?;
+                        signature_size = sig.len();
+                        sign_times.push(sign_time);
+                        verify_times.push(verify_time);
+                    }
+                }
+                SignatureAlgorithm::Falcon512 | SignatureAlgorithm::Falcon1024 => {
+                    let Some(keys) = &self.falcon else {
+                        continue;
+                    };
+                    for _ in 0..iterations {
+                        let (sig, sign_time) = keys.sign(&message)?;
+                        let (_, verify_time) = keys.verify(&message, &sig)?;
+                        signature_size = signature_size.max(sig.len());
+                        sign_times.push(sign_time);
+                        verify_times.push(verify_time);
+                    }
+                }
+            }
+
+            let mean_sign_time_ms = mean(&sign_times);
+            let mean_verify_time_ms = mean(&verify_times);
+            algorithms.push(SignatureAlgorithmBenchmark {
+                algorithm: algorithm.name().to_string(),
+                signature_size,
+                mean_sign_time_ms,
+                p95_sign_time_ms: p95(&mut sign_times),
+                mean_verify_time_ms,
+                p95_verify_time_ms: p95(&mut verify_times),
+                signatures_per_second: per_second(mean_sign_time_ms + mean_verify_time_ms),
+            });
+        }
+
+        let dual_cycle_ms = algorithms.iter()
+            .map(|a| a.mean_sign_time_ms + a.mean_verify_time_ms)
+            .sum();
+
+        Ok(SigningBenchmark {
+            iterations,
+            message_size: message.len(),
+            algorithms,
+            dual_signatures_per_second: per_second(dual_cycle_ms),
+        })
+    }
+}
+
+/// Arithmetic mean; 0.0 for no samples
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len().max(1) as f64
+}
+
+/// 95th percentile by nearest rank
+fn p95(samples: &mut [f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.sort_by(f64::total_cmp);
+    let rank = (samples.len() as f64 * 0.95).ceil() as usize;
+    samples[rank.clamp(1, samples.len()) - 1]
+}
+
+/// Operations per second for a given per-operation latency
+fn per_second(latency_ms: f64) -> f64 {
+    if latency_ms > 0.0 { 1000.0 / latency_ms } else { 0.0 }
+}
+
+/// Verify every part of a dual signature against the given public keys
+/// A Falcon part counts as one more component under the combiner mode;
+/// without Falcon keys to check it against, it is invalid. Components from
+/// `untrusted` algorithms are reported but never count toward validity.
+fn verify_dual_with_keys(
+    message: &[u8],
+    signature: &DualSignature,
+    ml_pk: &MldsaPublicKey,
+    slh_pk: &SlhDsaPublicKey,
+    falcon_keys: Option<&FalconKeyPair>,
+    mode: CombinerMode,
+    untrusted: &[SignatureAlgorithm],
+) -> VerificationResult {
+    let mut errors = Vec::new();
+    let mut outcome = |result: Result<(bool, f64), CryptoError>| {
+        result.unwrap_or_else(|e| {
+            errors.push(e.to_string());
+            (false, 0.0)
+        })
+    };
+
+    // Verify ML-DSA
+    let (ml_dsa_valid, ml_time) = outcome(
+        decode_signature_hex("ML-DSA-87", &signature.ml_dsa.signature)
+            .and_then(|sig| MldsaKeyPair::verify(message, &sig, ml_pk)),
+    );
+
+    // Verify SLH-DSA
+    let (slh_dsa_valid, slh_time) = outcome(
+        decode_signature_hex("SLH-DSA-256s", &signature.slh_dsa.signature)
+            .and_then(|sig| SlhDsaKeyPair::verify(message, &sig, slh_pk)),
+    );
+
+    // Verify Falcon, if the signer included it
+    let (falcon_valid, falcon_time) = match &signature.falcon {
+        Some(falcon) => match falcon_keys.filter(|k| k.variant.name() == falcon.algorithm) {
+            Some(keys) => {
+                let (valid, time) = outcome(
+                    decode_signature_hex(keys.variant.name(), &falcon.signature)
+                        .and_then(|sig| keys.verify(message, &sig)),
+                );
+                (Some(valid), time)
+            }
+            None => {
+                errors.push(format!("no {} public key configured", falcon.algorithm));
+                (Some(false), 0.0)
+            }
+        },
+        None => (None, 0.0),
+    };
+
+    let falcon_name = signature.falcon.as_ref().map_or("", |f| f.algorithm.as_str());
+    let counted: Vec<bool> = [
+        (SignatureAlgorithm::MlDsa87.name(), Some(ml_dsa_valid)),
+        (SignatureAlgorithm::SlhDsa256s.name(), Some(slh_dsa_valid)),
+        (falcon_name, falcon_valid),
+    ]
+    .into_iter()
+    .filter(|(name, _)| !untrusted.iter().any(|a| a.name() == *name))
+    .filter_map(|(_, valid)| valid)
+    .collect();
+
+    let valid = !counted.is_empty() && match mode {
+        CombinerMode::And => counted.iter().all(|v| *v),
+        CombinerMode::Or => counted.iter().any(|v| *v),
+    };
+
+    VerificationResult {
+        valid,
+        mode: format!("{:?}", mode).to_lowercase(),
+        ml_dsa_valid,
+        slh_dsa_valid,
+        falcon_valid,
+        verify_time_ms: ml_time + slh_time + falcon_time,
+        error: (!errors.is_empty()).then(|| errors.join("; ")),
+    }
+}
+
 impl Default for AdaptivePqcLayer {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_kem_benchmark_sizes() {
+        let apqc = AdaptivePqcLayer::new();
+        let bench = apqc.benchmark_kems(3).await;
+
+        assert_eq!(bench.ml_kem.ciphertext_size, KemAlgorithm::MlKem1024.ciphertext_size());
+        assert_eq!(bench.hqc.ciphertext_size, KemAlgorithm::Hqc256.ciphertext_size());
+        for combiner in &bench.combiners {
+            assert_eq!(combiner.combined_ct_size, bench.ml_kem.ciphertext_size + bench.hqc.ciphertext_size);
+            assert!(combiner.combined_secret_size > 0);
+        }
+        for kem in [&bench.ml_kem, &bench.hqc] {
+            assert!(kem.mean_encaps_time_ms >= 0.0);
+            assert!(kem.mean_decaps_time_ms >= 0.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signing_benchmark_orders_algorithms_by_cost() {
+        let apqc = AdaptivePqcLayer::new();
+        let bench = apqc.benchmark_signatures(2).await.unwrap();
+
+        let find = |name: &str| bench.algorithms.iter().find(|a| a.algorithm == name).unwrap();
+        let (ml_dsa, slh_dsa) = (find("ML-DSA-87"), find("SLH-DSA-256s"));
+        for algorithm in [ml_dsa, slh_dsa] {
+            assert!(algorithm.signatures_per_second > 0.0);
+            assert!(algorithm.p95_sign_time_ms >= algorithm.mean_sign_time_ms);
+        }
+        assert_eq!(slh_dsa.signature_size, SignatureAlgorithm::SlhDsa256s.signature_size());
+        assert!(slh_dsa.mean_sign_time_ms > ml_dsa.mean_sign_time_ms);
+        assert!(slh_dsa.signatures_per_second < ml_dsa.signatures_per_second);
+        assert!(bench.dual_signatures_per_second < ml_dsa.signatures_per_second);
+    }
+
+    #[test]
+    fn test_kem_combiner_derives_full_width_secret() {
+        let (ml_ss, hqc_ss) = ([1u8; 32], [2u8; 64]);
+        let combined = combine_kem_secrets(&ml_ss, &hqc_ss);
+        assert_eq!(combined.len(), 32);
+
+        let mut other_ml = ml_ss;
+        other_ml[0] ^= 1;
+        let mut other_hqc = hqc_ss;
+        other_hqc[63] ^= 1;
+        assert_ne!(combine_kem_secrets(&other_ml, &hqc_ss), combined);
+        assert_ne!(combine_kem_secrets(&ml_ss, &other_hqc), combined);
+    }
+
+    #[tokio::test]
+    async fn test_falcon_joins_dual_signature_when_enabled() {
+        let mut apqc = AdaptivePqcLayer::new();
+        let message = b"falcon in the active set";
+        assert!(apqc.sign_dual(message).await.unwrap().falcon.is_none());
+
+        apqc.enable_falcon(FalconVariant::Falcon512).await;
+        assert!(apqc.active_signatures.contains(&SignatureAlgorithm::Falcon512));
+        let signature = apqc.sign_dual(message).await.unwrap();
+        assert_eq!(signature.falcon.as_ref().unwrap().algorithm, "Falcon-512");
+
+        let result = apqc.verify_dual(message, &signature, CombinerMode::And).await;
+        assert!(result.valid);
+        assert_eq!(result.falcon_valid, Some(true));
+
+        let mut tampered = signature.clone();
+        tampered.falcon.as_mut().unwrap().signature = hex::encode([0u8; 40]);
+        let result = apqc.verify_dual(message, &tampered, CombinerMode::And).await;
+        assert!(!result.valid);
+        assert_eq!(result.falcon_valid, Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_mldsa_signature_reports_reason() {
+        let mut apqc = AdaptivePqcLayer::new();
+        let message = b"truncated signature";
+        let mut signature = apqc.sign_dual(message).await.unwrap();
+        let valid = apqc.verify_dual(message, &signature, CombinerMode::And).await;
+        assert!(valid.valid);
+        assert!(valid.error.is_none());
+
+        signature.ml_dsa.signature.truncate(200);
+        let result = apqc.verify_dual(message, &signature, CombinerMode::And).await;
+
+        assert!(!result.valid);
+        assert!(!result.ml_dsa_valid);
+        assert!(result.slh_dsa_valid);
+        let error = result.error.expect("error reason populated");
+        assert!(error.contains("ML-DSA-87 signature is 100 bytes"), "{}", error);
+
+        signature.ml_dsa.signature = "not hex".to_string();
+        let result = apqc.verify_dual(message, &signature, CombinerMode::Or).await;
+        assert!(result.valid);
+        assert_eq!(result.error.as_deref(), Some("ML-DSA-87 signature is not valid hex"));
+    }
+
+    #[tokio::test]
+    async fn test_untrusted_algorithm_never_satisfies_combiner() {
+        let mut apqc = AdaptivePqcLayer::new();
+        let message = b"ml-dsa disclosure";
+        let signature = apqc.sign_dual(message).await.unwrap();
+        let mut ml_only = signature.clone();
+        ml_only.slh_dsa.signature = hex::encode([0u8; 64]);
+        let mut slh_only = signature.clone();
+        slh_only.ml_dsa.signature = hex::encode([0u8; 64]);
+
+        assert!(apqc.verify_dual(message, &ml_only, CombinerMode::Or).await.valid);
+        apqc.set_trusted(SignatureAlgorithm::MlDsa87, false);
+
+        let rejected = apqc.verify_dual(message, &ml_only, CombinerMode::Or).await;
+        assert!(!rejected.valid);
+        assert!(rejected.ml_dsa_valid);
+        assert!(apqc.verify_dual(message, &slh_only, CombinerMode::Or).await.valid);
+
+        // The policy relaxes AND so the surviving algorithm alone suffices
+        let adaptive = apqc.verify_dual_adaptive(message, &slh_only, RiskRecommendation::MonitorClosely).await;
+        assert!(adaptive.valid);
+        assert_eq!(adaptive.mode, "or");
+
+        apqc.set_trusted(SignatureAlgorithm::MlDsa87, true);
+        assert_eq!(apqc.adaptive_combiner.select_mode(CombinerMode::And, RiskRecommendation::Continue), CombinerMode::And);
+        assert!(!apqc.verify_dual_adaptive(message, &slh_only, RiskRecommendation::Continue).await.valid);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_kem_round_trip() {
+        let apqc = AdaptivePqcLayer::new();
+        let encapsulated = apqc.encapsulate_hybrid().await;
+
+        let ml_ct = hex::decode(&encapsulated.ml_kem.ciphertext).unwrap();
+        let hqc_ct = hex::decode(&encapsulated.hqc.ciphertext).unwrap();
+        let recovered = apqc.decapsulate_hybrid(&ml_ct, &hqc_ct).await;
+
+        assert_eq!(recovered.as_deref(), Some(encapsulated.shared_secret.as_str()));
+        assert_eq!(hex::decode(&encapsulated.shared_secret).unwrap().len(), 32);
+        assert!(apqc.decapsulate_hybrid(&ml_ct[1..], &hqc_ct).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reconfigured_signature_set_gates_dual_signing() {
+        let mut apqc = AdaptivePqcLayer::new();
+
+        apqc.active_signatures.retain(|a| *a != SignatureAlgorithm::SlhDsa256s);
+        assert_eq!(
+            apqc.sign_dual(b"msg").await.unwrap_err(),
+            CryptoError::AlgorithmInactive("SLH-DSA-256s")
+        );
+
+        assert_eq!(apqc.set_active_signatures(vec![]).await, Err(AlgorithmSetError::Empty("signature")));
+        assert_eq!(apqc.set_active_kems(vec![]), Err(AlgorithmSetError::Empty("KEM")));
+        assert_eq!(
+            apqc.set_active_signatures(vec![SignatureAlgorithm::Falcon512]).await,
+            Err(AlgorithmSetError::MissingKeys("Falcon-512"))
+        );
+        assert_eq!(
+            apqc.set_active_kems(vec![KemAlgorithm::Hqc256, KemAlgorithm::Hqc256]),
+            Err(AlgorithmSetError::Duplicate("HQC-256"))
+        );
+        assert_eq!(apqc.active_signatures, vec![SignatureAlgorithm::MlDsa87]);
+
+        apqc.set_active_signatures(vec![SignatureAlgorithm::MlDsa87, SignatureAlgorithm::SlhDsa256s]).await.unwrap();
+        assert!(apqc.sign_dual(b"msg").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_signature_set_must_keep_dual_algorithms() {
+        let mut apqc = AdaptivePqcLayer::new();
+        assert_eq!(
+            apqc.set_active_signatures(vec![SignatureAlgorithm::MlDsa87]).await,
+            Err(AlgorithmSetError::Required("SLH-DSA-256s"))
+        );
+        apqc.enable_falcon(FalconVariant::Falcon512).await;
+        assert_eq!(
+            apqc.set_active_signatures(vec![SignatureAlgorithm::SlhDsa256s, SignatureAlgorithm::Falcon512]).await,
+            Err(AlgorithmSetError::Required("ML-DSA-87"))
+        );
+        assert!(apqc.sign_dual(b"msg").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_rotation_restores_keys() {
+        let mut apqc = AdaptivePqcLayer::new();
+        assert_eq!(apqc.rollback_rotation().await.unwrap_err(), RotationError::NoPreviousKeys);
+
+        let message = b"signed before rotation";
+        let signature = apqc.sign_dual(message).await.unwrap();
+
+        apqc.generate_rotation_keys().await;
+        apqc.execute_rotation().await;
+        assert!(!apqc.verify_dual(message, &signature, CombinerMode::And).await.valid);
+
+        let result = apqc.rollback_rotation().await.unwrap();
+        assert_eq!(result.status, "rolled_back");
+        assert!(apqc.verify_dual(message, &signature, CombinerMode::And).await.valid);
+        assert!(apqc.rollback_rotation().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_only_layer_from_public_keys() {
+        let mut signer = AdaptivePqcLayer::new();
+        let message = b"batch signed elsewhere";
+        let dual = signer.sign_dual(message).await.unwrap();
+        let hybrid = signer.sign_hybrid(message).await.unwrap();
+
+        let (mldsa_pk, slhdsa_pk, ecdsa_pk) = signer.get_public_keys().await;
+        let mut verifier = AdaptivePqcLayer::from_public_keys(&mldsa_pk, &slhdsa_pk, &ecdsa_pk).unwrap();
+
+        assert!(verifier.verify_dual(message, &dual, CombinerMode::And).await.valid);
+        assert!(verifier.verify_hybrid(message, &hybrid).await);
+        assert!(!verifier.verify_dual(b"tampered", &dual, CombinerMode::And).await.valid);
+        assert_eq!(
+            verifier.sign_dual(message).await.unwrap_err(),
+            CryptoError::MissingSecretKey("ML-DSA-87"),
+        );
+        assert!(AdaptivePqcLayer::from_public_keys(&mldsa_pk[1..], &slhdsa_pk, &ecdsa_pk).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_dual_batch_flags_tampered_items() {
+        let mut apqc = AdaptivePqcLayer::new();
+        let mut items = Vec::new();
+        for i in 0..5 {
+            let message = format!("batch-{}", i).into_bytes();
+            let signature = apqc.sign_dual(&message).await.unwrap();
+            items.push((message, signature));
+        }
+        // Tamper with items 1 and 3 after signing
+        items[1].0 = b"forged-1".to_vec();
+        items[3].0 = b"forged-3".to_vec();
+
+        let results = apqc.verify_dual_batch(&items, CombinerMode::And).await;
+
+        let valid: Vec<bool> = results.iter().map(|r| r.valid).collect();
+        assert_eq!(valid, vec![true, false, true, false, true]);
+    }
+}
@@ -27,6 +27,20 @@ use tokio::sync::mpsc;
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use serde::Deserialize;
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::PublicKey as _;
+
+// Shared with the server, which signs what this verifies
+#[allow(dead_code)]
+#[path = "../event_signature.rs"]
+mod event_signature;
+use event_signature::verify_event_signature;
+
+// Wire format of the server's binary event frames
+#[allow(dead_code)]
+#[path = "../binary_event.rs"]
+mod binary_event;
+use binary_event::BinaryEvent;
 
 // ============================================================================
 // Data Structures
@@ -45,6 +59,12 @@ struct StatusResponse {
 #[derive(Debug, Clone, Deserialize)]
 struct QvmStatus {
     processor: String,
+    #[serde(default)]
+    processor_mean_fidelity: f64,
+    #[serde(default)]
+    processor_qubit_count: usize,
+    #[serde(default)]
+    processor_quantum_volume: u64,
     current_era: String,
     qrm_risk_score: u32,
     oracle_risk_score: u32,
@@ -79,10 +99,14 @@ struct ApqcStatus {
 #[derive(Debug, Clone, Deserialize)]
 struct SequencerStatus {
     mempool_size: usize,
+    #[serde(default)]
+    mempool_capacity: usize,
     ordered_queue: usize,
     batch_count: usize,
     tee_platform: String,
     mrenclave: String,
+    #[serde(default)]
+    intelligence_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -122,6 +146,8 @@ struct CategoryRisk {
     category: String,
     score: u32,
     indicator_count: usize,
+    #[serde(default)]
+    delta: i32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -212,6 +238,51 @@ enum WsEvent {
     QvmAssessment { grover_threats: Vec<GroverThreat>, shor_threats: Vec<ShorThreat>, composite_risk: u32 },
 }
 
+impl From<BinaryEvent> for WsEvent {
+    fn from(event: BinaryEvent) -> Self {
+        match event {
+            BinaryEvent::BatchCreated { batch, block } => WsEvent::BatchCreated {
+                batch: Batch {
+                    batch_id: batch.batch_id,
+                    transactions: batch.transactions.into_iter().map(|tx| Transaction {
+                        tx_id: tx.tx_id,
+                        sender: tx.sender,
+                        data: tx.data,
+                        priority_fee: tx.priority_fee,
+                        status: tx.status,
+                    }).collect(),
+                    ml_dsa_sig: batch.ml_dsa_sig,
+                    slh_dsa_sig: batch.slh_dsa_sig,
+                    timestamp: batch.timestamp.to_rfc3339(),
+                },
+                block: BlockInfo {
+                    height: block.height,
+                    batch_id: block.batch_id,
+                    tx_count: block.tx_count,
+                    risk_score: block.risk_score,
+                },
+            },
+            BinaryEvent::QvmAssessment { grover_threats, shor_threats, composite_risk } => WsEvent::QvmAssessment {
+                grover_threats: grover_threats.into_iter().map(|t| GroverThreat {
+                    target_algorithm: t.target_algorithm,
+                    classical_bits: t.classical_bits,
+                    required_physical_qubits: t.required_physical_qubits,
+                    estimated_time_years: t.estimated_time_years,
+                    threat_level: t.threat_level,
+                }).collect(),
+                shor_threats: shor_threats.into_iter().map(|t| ShorThreat {
+                    target_algorithm: t.target_algorithm,
+                    key_bits: t.key_bits,
+                    required_physical_qubits: t.required_physical_qubits,
+                    estimated_time_hours: t.estimated_time_hours,
+                    threat_level: t.threat_level,
+                }).collect(),
+                composite_risk,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct GroverThreat {
     target_algorithm: String,
@@ -230,6 +301,29 @@ struct ShorThreat {
     threat_level: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+struct PublicKeysResponse {
+    keys: Vec<PublicKeyInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PublicKeyInfo {
+    algorithm: String,
+    public_key: String,
+}
+
+// ============================================================================
+// Event Verification
+// ============================================================================
+
+/// Fetch the server's published ML-DSA-87 public key
+async fn fetch_event_key(host: &str) -> Option<dilithium5::PublicKey> {
+    let resp = reqwest::get(format!("http://{}/api/pubkeys", host)).await.ok()?;
+    let keys = resp.json::<PublicKeysResponse>().await.ok()?;
+    let info = keys.keys.into_iter().find(|k| k.algorithm == "ML-DSA-87")?;
+    dilithium5::PublicKey::from_bytes(&hex::decode(info.public_key).ok()?).ok()
+}
+
 // ============================================================================
 // App State
 // ============================================================================
@@ -644,6 +738,16 @@ fn render_qvm(f: &mut Frame, app: &App, area: Rect) {
                     Span::styled(" Era: ", Style::default().fg(Color::Cyan)),
                     Span::styled(&qvm.current_era, Style::default().fg(Color::Yellow)),
                 ]),
+                Line::from(vec![
+                    Span::styled(" Qubits: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(format!("{}", qvm.processor_qubit_count)),
+                    Span::raw(" │ "),
+                    Span::styled(" QV: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(format!("{}", qvm.processor_quantum_volume)),
+                    Span::raw(" │ "),
+                    Span::styled(" Mean Fidelity: ", Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{:.4}", qvm.processor_mean_fidelity), Style::default().fg(Color::Green)),
+                ]),
                 Line::from(vec![
                     Span::styled(" QRM Risk: ", Style::default().fg(Color::Cyan)),
                     Span::styled(format!("{:>5}", qvm.qrm_risk_score), Style::default().fg(if qvm.qrm_risk_score < 3000 { Color::Green } else if qvm.qrm_risk_score < 6000 { Color::Yellow } else { Color::Red })),
@@ -856,12 +960,18 @@ fn render_qrm(f: &mut Frame, app: &App, area: Rect) {
         };
         let bar_len = (c.score as usize * 20) / 10000;
         let bar: String = "█".repeat(bar_len) + &"░".repeat(20 - bar_len);
+        let trend = match c.delta {
+            d if d > 0 => Span::styled(" ▲", Style::default().fg(Color::Red)),
+            d if d < 0 => Span::styled(" ▼", Style::default().fg(Color::Green)),
+            _ => Span::raw("  "),
+        };
         ListItem::new(Line::from(vec![
             Span::styled(format!("{:>20}", c.category), Style::default().fg(Color::Cyan)),
             Span::raw(" "),
             Span::styled(bar, Style::default().fg(color)),
             Span::raw(" "),
             Span::styled(format!("{:>5}", c.score), Style::default().fg(color)),
+            trend,
             Span::raw(format!(" ({:>2})", c.indicator_count)),
         ]))
     }).collect();
@@ -1003,6 +1113,10 @@ fn render_sequencer(f: &mut Frame, app: &App, area: Rect) {
                 Span::styled(" MRENCLAVE:    ", Style::default().fg(Color::Cyan)),
                 Span::styled(&status.sequencer.mrenclave, Style::default().fg(Color::Green)),
             ]),
+            Line::from(vec![
+                Span::styled(" Ordering:     ", Style::default().fg(Color::Cyan)),
+                Span::raw(status.sequencer.intelligence_mode.as_deref().unwrap_or("-")),
+            ]),
             Line::from(vec![
                 Span::styled(" Attestation:  ", Style::default().fg(Color::Cyan)),
                 Span::styled("PQC-Signed", Style::default().fg(Color::Green)),
@@ -1120,7 +1234,7 @@ fn render_all(f: &mut Frame, app: &App, area: Rect) {
     // SEQ mini
     let seq_text = if let Some(ref s) = app.status {
         vec![
-            Line::from(format!("Mempool: {}", s.sequencer.mempool_size)),
+            Line::from(format!("Mempool: {}/{}", s.sequencer.mempool_size, s.sequencer.mempool_capacity)),
             Line::from(format!("Ordered: {}", s.sequencer.ordered_queue)),
             Line::from(format!("Batches: {}", s.sequencer.batch_count)),
             Line::from(""),
@@ -1220,6 +1334,18 @@ async fn main() -> io::Result<()> {
     
     let mut app = App::new();
     app.log(LogLevel::Info, "SYS", format!("Connecting to ws://{}...", host));
+
+    // Require signed events when QRMS_VERIFY_EVENTS is set (server needs QRMS_SIGN_EVENTS)
+    let event_key = if std::env::var("QRMS_VERIFY_EVENTS").is_ok() {
+        let key = fetch_event_key(host).await;
+        match key {
+            Some(_) => app.log(LogLevel::Info, "SYS", "Verifying event signatures (ML-DSA-87)".to_string()),
+            None => app.log(LogLevel::Error, "SYS", "No server public key; all events will be rejected".to_string()),
+        }
+        Some(key)
+    } else {
+        None
+    };
     
     // WebSocket connection
     let ws_url = format!("ws://{}/ws", host);
@@ -1232,16 +1358,30 @@ async fn main() -> io::Result<()> {
             match connect_async(&ws_url).await {
                 Ok((ws_stream, _)) => {
                     let (mut write, mut read) = ws_stream.split();
+                    // Binary frames are never signed, so only ask for them when not verifying
+                    if event_key.is_none() {
+                        let _ = write.send(Message::Text(r#"{"command":"binary"}"#.to_string())).await;
+                    }
                     
                     loop {
                         tokio::select! {
                             Some(msg) = read.next() => {
                                 match msg {
                                     Ok(Message::Text(text)) => {
+                                        if let Some(key) = &event_key {
+                                            if !key.as_ref().is_some_and(|pk| verify_event_signature(&text, pk)) {
+                                                continue;
+                                            }
+                                        }
                                         if let Ok(event) = serde_json::from_str::<WsEvent>(&text) {
                                             let _ = tx.send(event).await;
                                         }
                                     }
+                                    Ok(Message::Binary(frame)) => {
+                                        if let Ok(event) = BinaryEvent::from_bincode(&frame) {
+                                            let _ = tx.send(event.into()).await;
+                                        }
+                                    }
                                     Err(_) => break,
                                     _ => {}
                                 }
@@ -0,0 +1,113 @@
+//! Bincode frames for WebSocket clients that negotiated binary events
+//! Shared by the server and `qrms-cli`, which includes this file directly.
+//! Bincode is not self-describing, so every field is always encoded; these
+//! mirror the JSON events without their `skip_serializing_if` fields.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BinaryEvent {
+    BatchCreated {
+        batch: BatchFrame,
+        block: BlockFrame,
+    },
+    QvmAssessment {
+        grover_threats: Vec<GroverThreatFrame>,
+        shor_threats: Vec<ShorThreatFrame>,
+        composite_risk: u32,
+    },
+}
+
+impl BinaryEvent {
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(self)
+    }
+
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> {
+        bincode::deserialize(bytes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionFrame {
+    pub tx_id: String,
+    pub sender: String,
+    pub data: String,
+    pub timestamp: DateTime<Utc>,
+    pub priority_fee: u64,
+    pub nonce: u64,
+    pub risk_level: u32,
+    /// Lowercase status name, as in the JSON event
+    pub status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttestationFrame {
+    pub platform: String,
+    pub mrenclave: String,
+    pub mrsigner: String,
+    pub report_data: String,
+    pub nonce: String,
+    pub timestamp: DateTime<Utc>,
+    pub pqc_signed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchFrame {
+    pub batch_id: String,
+    pub transactions: Vec<TransactionFrame>,
+    pub ml_dsa_sig: String,
+    pub slh_dsa_sig: String,
+    pub attestation: AttestationFrame,
+    pub timestamp: DateTime<Utc>,
+    pub clearing_price: Option<u64>,
+    pub signature_algorithms: Vec<String>,
+    pub beacon: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockFrame {
+    pub height: u64,
+    pub batch_id: String,
+    pub tx_count: usize,
+    pub timestamp: DateTime<Utc>,
+    pub attestation_valid: bool,
+    pub risk_score: u32,
+    pub algorithms: AlgorithmSetFrame,
+    pub downgraded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlgorithmSetFrame {
+    pub signatures: Vec<String>,
+    pub kems: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroverThreatFrame {
+    pub target_algorithm: String,
+    pub classical_bits: usize,
+    pub quantum_speedup: f64,
+    pub estimated_iterations: usize,
+    pub required_logical_qubits: usize,
+    pub required_physical_qubits: usize,
+    pub estimated_time_years: f64,
+    /// Threat level name, as in the JSON event
+    pub threat_level: String,
+    pub noise_adjusted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShorThreatFrame {
+    pub target_algorithm: String,
+    pub key_bits: usize,
+    pub required_logical_qubits: usize,
+    pub required_t_gates: usize,
+    pub required_physical_qubits: usize,
+    pub error_correction_overhead: f64,
+    pub estimated_time_hours: f64,
+    /// Threat level name, as in the JSON event
+    pub threat_level: String,
+    pub note: Option<String>,
+}
@@ -3,9 +3,10 @@
 
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
 use std::collections::VecDeque;
 
-use crate::qrm::RiskAssessment;
+use crate::qrm::{QuantumEra, RiskAssessment, ThreatCategory, ThreatIndicator};
 use crate::sequencer::Batch;
 
 /// Algorithm set configuration
@@ -34,6 +35,43 @@ pub struct Block {
     pub attestation_valid: bool,
     pub risk_score: u32,
     pub algorithms: AlgorithmSet,
+    /// Batch was signed without every signature algorithm the chain required
+    #[serde(default)]
+    pub downgraded: bool,
+}
+
+/// Full blocks kept before older ones are compacted
+pub const DEFAULT_RETAINED_BLOCKS: usize = 1000;
+
+impl Block {
+    /// SHA-256 of the block's canonical JSON encoding
+    pub fn hash(&self) -> String {
+        let encoded = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(Sha256::digest(&encoded))
+    }
+}
+
+/// Rolling summary of blocks evicted from the retained window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompactedHistory {
+    /// Number of blocks folded into the summary
+    pub block_count: u64,
+    /// Height of the newest compacted block
+    pub through_height: Option<u64>,
+    /// Chained hash: H(previous || block hash), empty before the first compaction
+    pub cumulative_hash: String,
+}
+
+impl CompactedHistory {
+    /// Fold one evicted block into the summary
+    fn absorb(&mut self, block: &Block) {
+        let mut hasher = Sha256::new();
+        hasher.update(self.cumulative_hash.as_bytes());
+        hasher.update(block.hash().as_bytes());
+        self.cumulative_hash = hex::encode(hasher.finalize());
+        self.block_count += 1;
+        self.through_height = Some(block.height);
+    }
 }
 
 /// Pending rotation info
@@ -51,17 +89,44 @@ pub struct ChainState {
     pub risk_score: u32,
     pub pending_rotation: Option<PendingRotation>,
     max_blocks: usize,
+    compacted: CompactedHistory,
 }
 
 impl ChainState {
     pub fn new() -> Self {
         Self {
-            blocks: VecDeque::with_capacity(1000),
+            blocks: VecDeque::with_capacity(DEFAULT_RETAINED_BLOCKS),
             current_height: 0,
             algorithm_set: AlgorithmSet::default(),
             risk_score: 0,
             pending_rotation: None,
-            max_blocks: 1000,
+            max_blocks: DEFAULT_RETAINED_BLOCKS,
+            compacted: CompactedHistory::default(),
+        }
+    }
+
+    /// Keep the last `retained` full blocks (minimum 1), compacting any excess now
+    pub fn set_retention(&mut self, retained: usize) {
+        self.max_blocks = retained.max(1);
+        self.compact();
+    }
+
+    /// Full blocks kept before compaction
+    pub fn retention(&self) -> usize {
+        self.max_blocks
+    }
+
+    /// Summary of blocks no longer held in full
+    pub fn compacted(&self) -> &CompactedHistory {
+        &self.compacted
+    }
+
+    /// Fold blocks beyond the retention window into the compacted summary
+    fn compact(&mut self) {
+        while self.blocks.len() > self.max_blocks {
+            if let Some(block) = self.blocks.pop_front() {
+                self.compacted.absorb(&block);
+            }
         }
     }
 
@@ -75,12 +140,11 @@ impl ChainState {
             attestation_valid: true,
             risk_score: risk_assessment.score,
             algorithms: self.algorithm_set.clone(),
+            downgraded: !self.missing_signatures(batch).is_empty(),
         };
 
         self.blocks.push_back(block.clone());
-        while self.blocks.len() > self.max_blocks {
-            self.blocks.pop_front();
-        }
+        self.compact();
 
         self.current_height += 1;
         self.risk_score = risk_assessment.score;
@@ -88,6 +152,50 @@ impl ChainState {
         block
     }
 
+    /// Required signature algorithms the batch was not signed with
+    fn missing_signatures(&self, batch: &Batch) -> Vec<String> {
+        self.algorithm_set
+            .signatures
+            .iter()
+            .filter(|required| !batch.signature_algorithms.contains(required))
+            .cloned()
+            .collect()
+    }
+
+    /// Raise an "Algorithm Downgrade" indicator if the batch was signed with
+    /// weaker or older algorithms than the current required set
+    pub fn detect_downgrade(&self, batch: &Batch) -> Option<ThreatIndicator> {
+        let missing = self.missing_signatures(batch);
+        if missing.is_empty() {
+            return None;
+        }
+
+        let used = if batch.signature_algorithms.is_empty() {
+            "no declared algorithms".to_string()
+        } else {
+            batch.signature_algorithms.join(", ")
+        };
+
+        Some(ThreatIndicator {
+            category: ThreatCategory::MigrationAgility,
+            sub_category: "Algorithm Downgrade".to_string(),
+            severity: 0.9,
+            confidence: 0.95,
+            source: "Chain State".to_string(),
+            timestamp: Utc::now(),
+            description: format!(
+                "Batch {} signed with {}; missing required {}",
+                batch.batch_id,
+                used,
+                missing.join(", ")
+            ),
+            era_relevance: QuantumEra::PreQuantum,
+            references: vec![],
+            tags: vec!["downgrade".to_string()],
+            expires_at: None,
+        })
+    }
+
     /// Get recent blocks
     pub fn get_recent_blocks(&self, count: usize) -> Vec<Block> {
         self.blocks.iter().rev().take(count).cloned().collect()
@@ -119,3 +227,82 @@ impl Default for ChainState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qrm::QuantumResistanceMonitor;
+    use crate::sequencer::TeeAttestation;
+
+    fn batch_signed_with(algorithms: &[&str]) -> Batch {
+        Batch {
+            batch_id: "downgrade-test".to_string(),
+            transactions: vec![],
+            ml_dsa_sig: String::new(),
+            slh_dsa_sig: String::new(),
+            attestation: TeeAttestation {
+                platform: "test".to_string(),
+                mrenclave: String::new(),
+                mrsigner: String::new(),
+                report_data: String::new(),
+                nonce: String::new(),
+                timestamp: Utc::now(),
+                pqc_signed: false,
+            },
+            timestamp: Utc::now(),
+            clearing_price: None,
+            signature_algorithms: algorithms.iter().map(|a| a.to_string()).collect(),
+            beacon: None,
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_only_batch_raises_downgrade_indicator() {
+        let mut chain = ChainState::new();
+        let risk = QuantumResistanceMonitor::new().calculate_risk();
+        let batch = batch_signed_with(&["ECDSA-secp256k1"]);
+
+        let indicator = chain.detect_downgrade(&batch).expect("downgrade should be detected");
+        let block = chain.commit_batch(&batch, &risk);
+
+        assert!(block.downgraded);
+        assert_eq!(indicator.category, ThreatCategory::MigrationAgility);
+        assert_eq!(indicator.sub_category, "Algorithm Downgrade");
+        assert!(indicator.severity >= 0.8);
+        assert!(indicator.description.contains("ML-DSA-87"));
+        assert!(indicator.description.contains("SLH-DSA-256s"));
+    }
+
+    #[test]
+    fn test_batch_with_required_set_is_not_downgraded() {
+        let mut chain = ChainState::new();
+        let risk = QuantumResistanceMonitor::new().calculate_risk();
+        let batch = batch_signed_with(&["ML-DSA-87", "SLH-DSA-256s", "Falcon-512"]);
+
+        assert!(chain.detect_downgrade(&batch).is_none());
+        assert!(!chain.commit_batch(&batch, &risk).downgraded);
+    }
+
+    #[test]
+    fn test_blocks_beyond_retention_are_compacted() {
+        let mut chain = ChainState::new();
+        chain.set_retention(3);
+        let risk = QuantumResistanceMonitor::new().calculate_risk();
+        let batch = batch_signed_with(&["ML-DSA-87", "SLH-DSA-256s"]);
+
+        let committed: Vec<Block> = (0..5).map(|_| chain.commit_batch(&batch, &risk)).collect();
+
+        let recent = chain.get_recent_blocks(10);
+        assert_eq!(recent.iter().map(|b| b.height).collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(recent[0].hash(), committed[4].hash());
+
+        let mut expected = CompactedHistory::default();
+        expected.absorb(&committed[0]);
+        expected.absorb(&committed[1]);
+        let compacted = chain.compacted();
+        assert_eq!(compacted.block_count, 2);
+        assert_eq!(compacted.through_height, Some(1));
+        assert_eq!(compacted.cumulative_hash, expected.cumulative_hash);
+        assert_eq!(chain.current_height, 5);
+    }
+}
@@ -3,16 +3,87 @@
 
 use pqcrypto_dilithium::dilithium5 as dilithium5_mod;
 use pqcrypto_sphincsplus::sphincssha256256fsimple as sphincs_mod;
-use pqcrypto_traits::sign::{DetachedSignature as PqcDetachedSignature, PublicKey as PqcPublicKey};
+use pqcrypto_mlkem::mlkem1024 as mlkem_mod;
+use pqcrypto_hqc::hqc256 as hqc_mod;
+use pqcrypto_falcon::{falcon512 as falcon512_mod, falcon1024 as falcon1024_mod};
+use pqcrypto_traits::kem::{
+    Ciphertext as PqcCiphertext, PublicKey as PqcKemPublicKey, SecretKey as PqcKemSecretKey,
+    SharedSecret as PqcSharedSecret,
+};
+use pqcrypto_traits::sign::{
+    DetachedSignature as PqcDetachedSignature, PublicKey as PqcPublicKey, SecretKey as PqcSecretKey,
+};
 use k256::ecdsa::{SigningKey, VerifyingKey, Signature, signature::Signer, signature::Verifier};
 use rand::rngs::OsRng;
+use rand::RngCore;
 use hex;
+use chacha20poly1305::{aead::{Aead, KeyInit, Payload}, ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use std::fmt;
 use std::time::Instant;
 
+/// ML-DSA-87 public key
+pub type MldsaPublicKey = dilithium5_mod::PublicKey;
+
+/// SLH-DSA-256s public key
+pub type SlhDsaPublicKey = sphincs_mod::PublicKey;
+
+/// Errors from key reconstruction, signing and signature decoding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptoError {
+    /// Public key bytes could not be parsed for the named algorithm
+    InvalidPublicKey(&'static str),
+    /// Signing was requested on a verification-only key pair
+    MissingSecretKey(&'static str),
+    /// Stored secret key bytes could not be parsed for the named algorithm
+    InvalidSecretKey(&'static str),
+    /// Signature was not valid hex
+    InvalidHex(&'static str),
+    /// Signature length does not match what the algorithm produces
+    WrongSignatureLength { algorithm: &'static str, expected: usize, actual: usize },
+    /// Signature bytes had the right length but could not be parsed
+    DecodeFailed(&'static str),
+    /// Signing needs an algorithm that is not in the active set
+    AlgorithmInactive(&'static str),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPublicKey(algorithm) => write!(f, "invalid {} public key", algorithm),
+            Self::MissingSecretKey(algorithm) => write!(f, "no {} secret key (verification-only)", algorithm),
+            Self::InvalidSecretKey(algorithm) => write!(f, "invalid {} secret key", algorithm),
+            Self::InvalidHex(algorithm) => write!(f, "{} signature is not valid hex", algorithm),
+            Self::WrongSignatureLength { algorithm, expected, actual } => {
+                write!(f, "{} signature is {} bytes, expected {}", algorithm, actual, expected)
+            }
+            Self::DecodeFailed(algorithm) => write!(f, "{} signature could not be decoded", algorithm),
+            Self::AlgorithmInactive(algorithm) => write!(f, "{} is not in the active signature set", algorithm),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// Decode a hex-encoded signature, naming the algorithm on failure
+pub fn decode_signature_hex(algorithm: &'static str, signature: &str) -> Result<Vec<u8>, CryptoError> {
+    hex::decode(signature).map_err(|_| CryptoError::InvalidHex(algorithm))
+}
+
+/// Reject signatures that are not exactly `expected` bytes
+fn check_signature_length(algorithm: &'static str, signature: &[u8], expected: usize) -> Result<(), CryptoError> {
+    if signature.len() != expected {
+        return Err(CryptoError::WrongSignatureLength { algorithm, expected, actual: signature.len() });
+    }
+    Ok(())
+}
+
 /// ML-DSA-87 (Dilithium-5) key pair
+#[derive(Clone)]
 pub struct MldsaKeyPair {
     pub public_key: dilithium5_mod::PublicKey,
-    pub secret_key: dilithium5_mod::SecretKey,
+    pub secret_key: Option<dilithium5_mod::SecretKey>,
 }
 
 impl MldsaKeyPair {
@@ -20,23 +91,35 @@ impl MldsaKeyPair {
         let (pk, sk) = dilithium5_mod::keypair();
         Self {
             public_key: pk,
-            secret_key: sk,
+            secret_key: Some(sk),
         }
     }
 
-    pub fn sign(&self, message: &[u8]) -> (Vec<u8>, f64) {
+    /// Verification-only key pair from exported public key bytes
+    pub fn from_public_key(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let public_key = <dilithium5_mod::PublicKey as PqcPublicKey>::from_bytes(bytes)
+            .map_err(|_| CryptoError::InvalidPublicKey("ML-DSA-87"))?;
+        Ok(Self { public_key, secret_key: None })
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, f64), CryptoError> {
+        let secret_key = self.secret_key.as_ref().ok_or(CryptoError::MissingSecretKey("ML-DSA-87"))?;
         let start = Instant::now();
-        let sig = dilithium5_mod::detached_sign(message, &self.secret_key);
+        let sig = dilithium5_mod::detached_sign(message, secret_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (sig.as_bytes().to_vec(), elapsed)
+        Ok((sig.as_bytes().to_vec(), elapsed))
     }
 
-    pub fn verify(message: &[u8], signature: &[u8], public_key: &dilithium5_mod::PublicKey) -> (bool, f64) {
+    /// Verify a detached signature; malformed signatures are an error, a
+    /// well-formed signature that does not match is `Ok((false, _))`
+    pub fn verify(message: &[u8], signature: &[u8], public_key: &dilithium5_mod::PublicKey) -> Result<(bool, f64), CryptoError> {
+        check_signature_length("ML-DSA-87", signature, dilithium5_mod::signature_bytes())?;
         let start = Instant::now();
-        let sig = <dilithium5_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature).ok();
-        let valid = sig.map(|s| dilithium5_mod::verify_detached_signature(&s, message, public_key).is_ok()).unwrap_or(false);
+        let sig = <dilithium5_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature)
+            .map_err(|_| CryptoError::DecodeFailed("ML-DSA-87"))?;
+        let valid = dilithium5_mod::verify_detached_signature(&sig, message, public_key).is_ok();
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (valid, elapsed)
+        Ok((valid, elapsed))
     }
 
     pub fn public_key_bytes(&self) -> Vec<u8> {
@@ -55,9 +138,10 @@ impl MldsaKeyPair {
 }
 
 /// SLH-DSA-256s (SPHINCS+) key pair
+#[derive(Clone)]
 pub struct SlhDsaKeyPair {
     pub public_key: sphincs_mod::PublicKey,
-    pub secret_key: sphincs_mod::SecretKey,
+    pub secret_key: Option<sphincs_mod::SecretKey>,
 }
 
 impl SlhDsaKeyPair {
@@ -65,23 +149,33 @@ impl SlhDsaKeyPair {
         let (pk, sk) = sphincs_mod::keypair();
         Self {
             public_key: pk,
-            secret_key: sk,
+            secret_key: Some(sk),
         }
     }
 
-    pub fn sign(&self, message: &[u8]) -> (Vec<u8>, f64) {
+    /// Verification-only key pair from exported public key bytes
+    pub fn from_public_key(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let public_key = <sphincs_mod::PublicKey as PqcPublicKey>::from_bytes(bytes)
+            .map_err(|_| CryptoError::InvalidPublicKey("SLH-DSA-256s"))?;
+        Ok(Self { public_key, secret_key: None })
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, f64), CryptoError> {
+        let secret_key = self.secret_key.as_ref().ok_or(CryptoError::MissingSecretKey("SLH-DSA-256s"))?;
         let start = Instant::now();
-        let sig = sphincs_mod::detached_sign(message, &self.secret_key);
+        let sig = sphincs_mod::detached_sign(message, secret_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (sig.as_bytes().to_vec(), elapsed)
+        Ok((sig.as_bytes().to_vec(), elapsed))
     }
 
-    pub fn verify(message: &[u8], signature: &[u8], public_key: &sphincs_mod::PublicKey) -> (bool, f64) {
+    pub fn verify(message: &[u8], signature: &[u8], public_key: &sphincs_mod::PublicKey) -> Result<(bool, f64), CryptoError> {
+        check_signature_length("SLH-DSA-256s", signature, sphincs_mod::signature_bytes())?;
         let start = Instant::now();
-        let sig = <sphincs_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature).ok();
-        let valid = sig.map(|s| sphincs_mod::verify_detached_signature(&s, message, public_key).is_ok()).unwrap_or(false);
+        let sig = <sphincs_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature)
+            .map_err(|_| CryptoError::DecodeFailed("SLH-DSA-256s"))?;
+        let valid = sphincs_mod::verify_detached_signature(&sig, message, public_key).is_ok();
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (valid, elapsed)
+        Ok((valid, elapsed))
     }
 
     pub fn public_key_bytes(&self) -> Vec<u8> {
@@ -99,97 +193,208 @@ impl SlhDsaKeyPair {
     }
 }
 
-/// ML-KEM-1024 key pair (temporary mock until AVX2 issues resolved)
+/// Falcon parameter set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FalconVariant {
+    Falcon512,
+    Falcon1024,
+}
+
+impl FalconVariant {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Falcon512 => "Falcon-512",
+            Self::Falcon1024 => "Falcon-1024",
+        }
+    }
+}
+
+/// Falcon key pair; keys are held as bytes so one type covers both parameter sets
+#[derive(Clone)]
+pub struct FalconKeyPair {
+    pub variant: FalconVariant,
+    public_key: Vec<u8>,
+    secret_key: Option<Vec<u8>>,
+}
+
+impl FalconKeyPair {
+    pub fn generate(variant: FalconVariant) -> Self {
+        let (public_key, secret_key) = match variant {
+            FalconVariant::Falcon512 => {
+                let (pk, sk) = falcon512_mod::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+            FalconVariant::Falcon1024 => {
+                let (pk, sk) = falcon1024_mod::keypair();
+                (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+            }
+        };
+        Self { variant, public_key, secret_key: Some(secret_key) }
+    }
+
+    /// Verification-only key pair from exported public key bytes
+    pub fn from_public_key(variant: FalconVariant, bytes: &[u8]) -> Result<Self, CryptoError> {
+        let valid = match variant {
+            FalconVariant::Falcon512 => <falcon512_mod::PublicKey as PqcPublicKey>::from_bytes(bytes).is_ok(),
+            FalconVariant::Falcon1024 => <falcon1024_mod::PublicKey as PqcPublicKey>::from_bytes(bytes).is_ok(),
+        };
+        if !valid {
+            return Err(CryptoError::InvalidPublicKey(variant.name()));
+        }
+        Ok(Self { variant, public_key: bytes.to_vec(), secret_key: None })
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, f64), CryptoError> {
+        let secret_key = self.secret_key.as_ref().ok_or(CryptoError::MissingSecretKey(self.variant.name()))?;
+        let start = Instant::now();
+        let sig = match self.variant {
+            FalconVariant::Falcon512 => {
+                let sk = <falcon512_mod::SecretKey as PqcSecretKey>::from_bytes(secret_key)
+                    .map_err(|_| CryptoError::InvalidSecretKey(self.variant.name()))?;
+                falcon512_mod::detached_sign(message, &sk).as_bytes().to_vec()
+            }
+            FalconVariant::Falcon1024 => {
+                let sk = <falcon1024_mod::SecretKey as PqcSecretKey>::from_bytes(secret_key)
+                    .map_err(|_| CryptoError::InvalidSecretKey(self.variant.name()))?;
+                falcon1024_mod::detached_sign(message, &sk).as_bytes().to_vec()
+            }
+        };
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        Ok((sig, elapsed))
+    }
+
+    /// Falcon signatures are variable-length, so only an oversized signature
+    /// is reported as a length error
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(bool, f64), CryptoError> {
+        let algorithm = self.variant.name();
+        let max_len = Self::signature_size(self.variant);
+        if signature.is_empty() || signature.len() > max_len {
+            return Err(CryptoError::WrongSignatureLength { algorithm, expected: max_len, actual: signature.len() });
+        }
+        let start = Instant::now();
+        let valid = match self.variant {
+            FalconVariant::Falcon512 => {
+                let pk = <falcon512_mod::PublicKey as PqcPublicKey>::from_bytes(&self.public_key)
+                    .map_err(|_| CryptoError::InvalidPublicKey(algorithm))?;
+                let sig = <falcon512_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature)
+                    .map_err(|_| CryptoError::DecodeFailed(algorithm))?;
+                falcon512_mod::verify_detached_signature(&sig, message, &pk).is_ok()
+            }
+            FalconVariant::Falcon1024 => {
+                let pk = <falcon1024_mod::PublicKey as PqcPublicKey>::from_bytes(&self.public_key)
+                    .map_err(|_| CryptoError::InvalidPublicKey(algorithm))?;
+                let sig = <falcon1024_mod::DetachedSignature as PqcDetachedSignature>::from_bytes(signature)
+                    .map_err(|_| CryptoError::DecodeFailed(algorithm))?;
+                falcon1024_mod::verify_detached_signature(&sig, message, &pk).is_ok()
+            }
+        };
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0;
+        Ok((valid, elapsed))
+    }
+
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.public_key.clone()
+    }
+
+    /// Maximum signature size; Falcon signatures are variable-length
+    pub fn signature_size(variant: FalconVariant) -> usize {
+        match variant {
+            FalconVariant::Falcon512 => 752,
+            FalconVariant::Falcon1024 => 1462,
+        }
+    }
+
+    pub fn public_key_size(variant: FalconVariant) -> usize {
+        match variant {
+            FalconVariant::Falcon512 => 897,
+            FalconVariant::Falcon1024 => 1793,
+        }
+    }
+}
+
+/// ML-KEM-1024 (FIPS 203) key pair
+#[derive(Clone)]
 pub struct MlKemKeyPair {
-    pubkey: Vec<u8>,
-    seckey: Vec<u8>,
+    pub public_key: mlkem_mod::PublicKey,
+    pub secret_key: mlkem_mod::SecretKey,
 }
 
 impl MlKemKeyPair {
     pub fn generate() -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let (pk, sk) = mlkem_mod::keypair();
         Self {
-            pubkey: (0..1568).map(|_| rng.gen()).collect(),
-            seckey: (0..3168).map(|_| rng.gen()).collect(),
+            public_key: pk,
+            secret_key: sk,
         }
     }
 
     pub fn encapsulate(&self) -> (Vec<u8>, Vec<u8>, f64) {
-        use rand::Rng;
         let start = Instant::now();
-        let mut rng = rand::thread_rng();
-        let ct: Vec<u8> = (0..1568).map(|_| rng.gen()).collect();
-        let ss: Vec<u8> = (0..32).map(|_| rng.gen()).collect();
+        let (ss, ct) = mlkem_mod::encapsulate(&self.public_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (ct, ss, elapsed)
+        (ct.as_bytes().to_vec(), ss.as_bytes().to_vec(), elapsed)
     }
 
-    pub fn decapsulate(&self, _ciphertext: &[u8]) -> Option<(Vec<u8>, f64)> {
-        use rand::Rng;
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Option<(Vec<u8>, f64)> {
         let start = Instant::now();
-        let mut rng = rand::thread_rng();
-        let ss: Vec<u8> = (0..32).map(|_| rng.gen()).collect();
+        let ct = <mlkem_mod::Ciphertext as PqcCiphertext>::from_bytes(ciphertext).ok()?;
+        let ss = mlkem_mod::decapsulate(&ct, &self.secret_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        Some((ss, elapsed))
+        Some((ss.as_bytes().to_vec(), elapsed))
     }
 
     pub fn public_key_bytes(&self) -> Vec<u8> {
-        self.pubkey.clone()
+        <mlkem_mod::PublicKey as PqcKemPublicKey>::as_bytes(&self.public_key).to_vec()
     }
 
     pub fn ciphertext_size() -> usize {
-        1568
+        mlkem_mod::ciphertext_bytes()
     }
 }
 
-/// HQC-256 key pair (temporary mock until AVX2 issues resolved)
+/// HQC-256 (code-based) key pair
+#[derive(Clone)]
 pub struct HqcKeyPair {
-    pubkey: Vec<u8>,
-    seckey: Vec<u8>,
+    pub public_key: hqc_mod::PublicKey,
+    pub secret_key: hqc_mod::SecretKey,
 }
 
 impl HqcKeyPair {
     pub fn generate() -> Self {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let (pk, sk) = hqc_mod::keypair();
         Self {
-            pubkey: (0..6730).map(|_| rng.gen()).collect(),
-            seckey: (0..6730).map(|_| rng.gen()).collect(),
+            public_key: pk,
+            secret_key: sk,
         }
     }
 
     pub fn encapsulate(&self) -> (Vec<u8>, Vec<u8>, f64) {
-        use rand::Rng;
         let start = Instant::now();
-        let mut rng = rand::thread_rng();
-        let ct: Vec<u8> = (0..6730).map(|_| rng.gen()).collect();
-        let ss: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+        let (ss, ct) = hqc_mod::encapsulate(&self.public_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (ct, ss, elapsed)
+        (ct.as_bytes().to_vec(), ss.as_bytes().to_vec(), elapsed)
     }
 
-    pub fn decapsulate(&self, _ciphertext: &[u8]) -> Option<(Vec<u8>, f64)> {
-        use rand::Rng;
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Option<(Vec<u8>, f64)> {
         let start = Instant::now();
-        let mut rng = rand::thread_rng();
-        let ss: Vec<u8> = (0..64).map(|_| rng.gen()).collect();
+        let ct = <hqc_mod::Ciphertext as PqcCiphertext>::from_bytes(ciphertext).ok()?;
+        let ss = hqc_mod::decapsulate(&ct, &self.secret_key);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        Some((ss, elapsed))
+        Some((ss.as_bytes().to_vec(), elapsed))
     }
 
     pub fn public_key_bytes(&self) -> Vec<u8> {
-        self.pubkey.clone()
+        <hqc_mod::PublicKey as PqcKemPublicKey>::as_bytes(&self.public_key).to_vec()
     }
 
     pub fn ciphertext_size() -> usize {
-        6730
+        hqc_mod::ciphertext_bytes()
     }
 }
 
 /// ECDSA (Secp256k1) key pair for hybrid signatures
 pub struct EcdsaKeyPair {
-    pub signing_key: SigningKey,
+    pub signing_key: Option<SigningKey>,
     pub verifying_key: VerifyingKey,
 }
 
@@ -198,24 +403,33 @@ impl EcdsaKeyPair {
         let signing_key = SigningKey::random(&mut OsRng);
         let verifying_key = *signing_key.verifying_key();
         Self {
-            signing_key,
+            signing_key: Some(signing_key),
             verifying_key,
         }
     }
 
-    pub fn sign(&self, message: &[u8]) -> (Vec<u8>, f64) {
+    /// Verification-only key pair from SEC1-encoded public key bytes
+    pub fn from_public_key(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let verifying_key = VerifyingKey::from_sec1_bytes(bytes)
+            .map_err(|_| CryptoError::InvalidPublicKey("ECDSA-secp256k1"))?;
+        Ok(Self { signing_key: None, verifying_key })
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<(Vec<u8>, f64), CryptoError> {
+        let signing_key = self.signing_key.as_ref().ok_or(CryptoError::MissingSecretKey("ECDSA-secp256k1"))?;
         let start = Instant::now();
-        let sig: Signature = self.signing_key.sign(message);
+        let sig: Signature = signing_key.sign(message);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (sig.to_bytes().to_vec(), elapsed)
+        Ok((sig.to_bytes().to_vec(), elapsed))
     }
 
-    pub fn verify(message: &[u8], signature: &[u8], verifying_key: &VerifyingKey) -> (bool, f64) {
+    pub fn verify(message: &[u8], signature: &[u8], verifying_key: &VerifyingKey) -> Result<(bool, f64), CryptoError> {
+        check_signature_length("ECDSA-secp256k1", signature, 64)?;
         let start = Instant::now();
-        let sig = Signature::from_bytes(signature.into()).ok();
-        let valid = sig.map(|s| verifying_key.verify(message, &s).is_ok()).unwrap_or(false);
+        let sig = Signature::from_slice(signature).map_err(|_| CryptoError::DecodeFailed("ECDSA-secp256k1"))?;
+        let valid = verifying_key.verify(message, &sig).is_ok();
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
-        (valid, elapsed)
+        Ok((valid, elapsed))
     }
 
     pub fn public_key_bytes(&self) -> Vec<u8> {
@@ -243,3 +457,328 @@ impl HybridSignature {
         self.ecdsa_sig.len() + self.mldsa_sig.len() + self.slhdsa_sig.len()
     }
 }
+
+/// HKDF info labels for the transaction cipher's key and nonce
+const TX_CIPHER_KEY_INFO: &[u8] = b"QRMS-tx-key-v2";
+const TX_CIPHER_NONCE_INFO: &[u8] = b"QRMS-tx-nonce-v2";
+/// Poly1305 tag appended by ChaCha20-Poly1305
+const TX_CIPHER_TAG_SIZE: usize = 16;
+
+/// Shamir share sets that cannot be interpolated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareError {
+    /// No shares were supplied
+    Empty,
+    /// Index 0 holds the secret itself and is never a valid share
+    ZeroIndex,
+    /// Two shares carry the same index
+    DuplicateIndex(u8),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no key shares supplied"),
+            Self::ZeroIndex => write!(f, "key share index 0 is invalid"),
+            Self::DuplicateIndex(index) => write!(f, "duplicate key share index {}", index),
+        }
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+/// Encrypted-mempool cipher held by the TEE
+/// ML-KEM-1024 wraps a fresh key per transaction; the enclave secret key can be
+/// split into t-of-n Shamir shares so no single operator holds it.
+pub struct ThresholdCipher {
+    keys: MlKemKeyPair,
+}
+
+/// One Shamir share of the enclave secret key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    pub index: u8,
+    pub bytes: Vec<u8>,
+}
+
+impl ThresholdCipher {
+    pub fn generate() -> Self {
+        Self {
+            keys: MlKemKeyPair::generate(),
+        }
+    }
+
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.keys.public_key_bytes()
+    }
+
+    pub fn secret_key_bytes(&self) -> Vec<u8> {
+        self.keys.secret_key.as_bytes().to_vec()
+    }
+
+    /// Split the secret key so any `threshold` of `shares` reconstruct it
+    /// `threshold` is clamped to 1..=shares
+    pub fn split_key(&self, threshold: u8, shares: u8) -> Vec<KeyShare> {
+        let threshold = threshold.clamp(1, shares.max(1));
+        let secret = self.secret_key_bytes();
+        let mut rng = rand::thread_rng();
+        let mut result: Vec<KeyShare> = (1..=shares)
+            .map(|index| KeyShare { index, bytes: Vec::with_capacity(secret.len()) })
+            .collect();
+
+        let mut coefficients = vec![0u8; threshold as usize];
+        for &byte in &secret {
+            coefficients[0] = byte;
+            rng.fill_bytes(&mut coefficients[1..]);
+            for share in &mut result {
+                // Horner evaluation of the share polynomial at x = index
+                let y = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0u8, |acc, &c| gf256_mul(acc, share.index) ^ c);
+                share.bytes.push(y);
+            }
+        }
+        result
+    }
+
+    /// Reconstruct a secret key from shares via Lagrange interpolation at zero
+    /// Fewer than `threshold` shares yield an unrelated key.
+    pub fn combine_shares(shares: &[KeyShare]) -> Result<Vec<u8>, ShareError> {
+        if shares.is_empty() {
+            return Err(ShareError::Empty);
+        }
+        let mut seen = [false; 256];
+        for share in shares {
+            if share.index == 0 {
+                return Err(ShareError::ZeroIndex);
+            }
+            if std::mem::replace(&mut seen[share.index as usize], true) {
+                return Err(ShareError::DuplicateIndex(share.index));
+            }
+        }
+
+        let len = shares.iter().map(|s| s.bytes.len()).min().unwrap_or(0);
+        let basis: Vec<u8> = shares
+            .iter()
+            .map(|si| {
+                shares
+                    .iter()
+                    .filter(|sj| sj.index != si.index)
+                    .fold(1u8, |acc, sj| {
+                        gf256_mul(acc, gf256_mul(sj.index, gf256_inv(sj.index ^ si.index)))
+                    })
+            })
+            .collect();
+
+        Ok((0..len)
+            .map(|i| {
+                shares
+                    .iter()
+                    .zip(&basis)
+                    .fold(0u8, |acc, (share, &l)| acc ^ gf256_mul(share.bytes[i], l))
+            })
+            .collect())
+    }
+}
+
+/// Encrypt a transaction payload to the enclave's ML-KEM public key
+/// Layout: KEM ciphertext || ChaCha20-Poly1305 ciphertext and tag; the KEM
+/// ciphertext is authenticated as associated data.
+pub fn encrypt_transaction(plaintext: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let pk = <mlkem_mod::PublicKey as PqcKemPublicKey>::from_bytes(public_key)
+        .map_err(|_| CryptoError::InvalidPublicKey("ML-KEM"))?;
+    let (ss, kem_ct) = mlkem_mod::encapsulate(&pk);
+    let (cipher, nonce) = tx_cipher(ss.as_bytes());
+
+    let mut out = kem_ct.as_bytes().to_vec();
+    let sealed = cipher
+        .encrypt(&nonce, Payload { msg: plaintext, aad: &out })
+        .expect("ChaCha20-Poly1305 encryption of an in-memory payload cannot fail");
+    out.extend(sealed);
+    Ok(out)
+}
+
+/// Decrypt a transaction payload inside the enclave
+/// Returns None for a malformed ciphertext, a wrong key, or a failed tag check.
+pub fn decrypt_transaction(ciphertext: &[u8], secret_key: &[u8]) -> Option<Vec<u8>> {
+    let kem_len = MlKemKeyPair::ciphertext_size();
+    if ciphertext.len() < kem_len + TX_CIPHER_TAG_SIZE {
+        return None;
+    }
+    let sk = <mlkem_mod::SecretKey as PqcKemSecretKey>::from_bytes(secret_key).ok()?;
+    let (kem_bytes, sealed) = ciphertext.split_at(kem_len);
+    let kem_ct = <mlkem_mod::Ciphertext as PqcCiphertext>::from_bytes(kem_bytes).ok()?;
+    let ss = mlkem_mod::decapsulate(&kem_ct, &sk);
+    let (cipher, nonce) = tx_cipher(ss.as_bytes());
+
+    cipher.decrypt(&nonce, Payload { msg: sealed, aad: kem_bytes }).ok()
+}
+
+/// AEAD and nonce for one transaction, derived from its KEM shared secret via HKDF-SHA256
+/// Every transaction encapsulates a fresh secret, so each key seals exactly one message.
+fn tx_cipher(shared_secret: &[u8]) -> (ChaCha20Poly1305, Nonce) {
+    let hkdf = Hkdf::<Sha256>::new(None, shared_secret);
+    let mut key = [0u8; 32];
+    let mut nonce = [0u8; 12];
+    hkdf.expand(TX_CIPHER_KEY_INFO, &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    hkdf.expand(TX_CIPHER_NONCE_INFO, &mut nonce)
+        .expect("12 bytes is a valid HKDF-SHA256 output length");
+    (ChaCha20Poly1305::new(Key::from_slice(&key)), Nonce::clone_from_slice(&nonce))
+}
+
+/// Multiply in GF(2^8) with the AES polynomial
+fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8) as a^254
+fn gf256_inv(a: u8) -> u8 {
+    (0..7).fold((1, gf256_mul(a, a)), |(acc, sq), _| {
+        (gf256_mul(acc, sq), gf256_mul(sq, sq))
+    }).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_falcon512_sign_verify_round_trip() {
+        let keys = FalconKeyPair::generate(FalconVariant::Falcon512);
+        let message = b"falcon batch";
+        let (signature, _) = keys.sign(message).unwrap();
+
+        assert!(keys.verify(message, &signature).unwrap().0);
+        assert!(!keys.verify(b"other batch", &signature).unwrap().0);
+        assert!(signature.len() <= FalconKeyPair::signature_size(FalconVariant::Falcon512));
+
+        let verifier = FalconKeyPair::from_public_key(FalconVariant::Falcon512, &keys.public_key_bytes()).unwrap();
+        assert!(verifier.verify(message, &signature).unwrap().0);
+        assert_eq!(verifier.sign(message).unwrap_err(), CryptoError::MissingSecretKey("Falcon-512"));
+    }
+
+    #[test]
+    fn test_unparseable_secret_key_is_invalid_not_missing() {
+        let mut keys = FalconKeyPair::generate(FalconVariant::Falcon512);
+        keys.secret_key = Some(vec![0u8; 3]);
+
+        assert_eq!(keys.sign(b"msg").unwrap_err(), CryptoError::InvalidSecretKey("Falcon-512"));
+    }
+
+    #[test]
+    fn test_malformed_signatures_are_errors() {
+        let keys = MldsaKeyPair::generate();
+        let (signature, _) = keys.sign(b"msg").unwrap();
+
+        assert_eq!(
+            MldsaKeyPair::verify(b"msg", &signature[..10], &keys.public_key).unwrap_err(),
+            CryptoError::WrongSignatureLength { algorithm: "ML-DSA-87", expected: signature.len(), actual: 10 },
+        );
+        assert!(!MldsaKeyPair::verify(b"other", &signature, &keys.public_key).unwrap().0);
+        assert_eq!(decode_signature_hex("ML-DSA-87", "zz").unwrap_err(), CryptoError::InvalidHex("ML-DSA-87"));
+    }
+
+    #[test]
+    fn test_falcon_signature_much_smaller_than_slhdsa() {
+        let message = b"size comparison";
+        let (falcon_sig, _) = FalconKeyPair::generate(FalconVariant::Falcon512).sign(message).unwrap();
+        let (slh_sig, _) = SlhDsaKeyPair::generate().sign(message).unwrap();
+
+        assert!(falcon_sig.len() * 20 < slh_sig.len(), "{} vs {}", falcon_sig.len(), slh_sig.len());
+    }
+
+    #[test]
+    fn test_mlkem_round_trip() {
+        let keys = MlKemKeyPair::generate();
+        let (ct, ss, _) = keys.encapsulate();
+        let (recovered, _) = keys.decapsulate(&ct).expect("valid ciphertext");
+
+        assert_eq!(ct.len(), MlKemKeyPair::ciphertext_size());
+        assert_eq!(ss.len(), 32);
+        assert_eq!(ss, recovered);
+        assert!(keys.decapsulate(&ct[..ct.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_hqc_round_trip() {
+        let keys = HqcKeyPair::generate();
+        let (ct, ss, _) = keys.encapsulate();
+        let (recovered, _) = keys.decapsulate(&ct).expect("valid ciphertext");
+
+        assert_eq!(ct.len(), HqcKeyPair::ciphertext_size());
+        assert_eq!(ss.len(), 64);
+        assert_eq!(ss, recovered);
+        assert!(keys.decapsulate(&ct[..ct.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn test_transaction_cipher_round_trip() {
+        let cipher = ThresholdCipher::generate();
+        let plaintext = b"0xabcdef01 transfer(42)";
+        let ciphertext = encrypt_transaction(plaintext, &cipher.public_key_bytes()).unwrap();
+
+        assert!(!ciphertext.windows(plaintext.len()).any(|w| w == plaintext));
+        assert_eq!(decrypt_transaction(&ciphertext, &cipher.secret_key_bytes()).unwrap(), plaintext);
+
+        let other = ThresholdCipher::generate();
+        assert!(decrypt_transaction(&ciphertext, &other.secret_key_bytes()).is_none());
+        assert!(decrypt_transaction(&ciphertext, &[0u8; 32]).is_none());
+    }
+
+    #[test]
+    fn test_threshold_key_shares() {
+        let cipher = ThresholdCipher::generate();
+        let shares = cipher.split_key(3, 5);
+        assert_eq!(shares.len(), 5);
+
+        let recovered = ThresholdCipher::combine_shares(&[shares[4].clone(), shares[0].clone(), shares[2].clone()]);
+        assert_eq!(recovered.unwrap(), cipher.secret_key_bytes());
+
+        let too_few = ThresholdCipher::combine_shares(&shares[..2]).unwrap();
+        assert_ne!(too_few, cipher.secret_key_bytes());
+    }
+
+    #[test]
+    fn test_combine_shares_rejects_empty_input() {
+        assert_eq!(ThresholdCipher::combine_shares(&[]), Err(ShareError::Empty));
+    }
+
+    #[test]
+    fn test_combine_shares_rejects_zero_index() {
+        let shares = ThresholdCipher::generate().split_key(2, 3);
+        let zero = KeyShare { index: 0, bytes: shares[0].bytes.clone() };
+        assert_eq!(ThresholdCipher::combine_shares(&[zero, shares[1].clone()]), Err(ShareError::ZeroIndex));
+    }
+
+    #[test]
+    fn test_combine_shares_rejects_duplicate_index() {
+        let shares = ThresholdCipher::generate().split_key(2, 3);
+        assert_eq!(
+            ThresholdCipher::combine_shares(&[shares[1].clone(), shares[0].clone(), shares[1].clone()]),
+            Err(ShareError::DuplicateIndex(2)),
+        );
+    }
+
+    #[test]
+    fn test_tampered_transaction_ciphertext_is_rejected() {
+        let cipher = ThresholdCipher::generate();
+        let mut ciphertext = encrypt_transaction(b"transfer(7)", &cipher.public_key_bytes()).unwrap();
+        let last = ciphertext.len() - 1;
+        ciphertext[last] ^= 1;
+        assert!(decrypt_transaction(&ciphertext, &cipher.secret_key_bytes()).is_none());
+    }
+}
@@ -0,0 +1,33 @@
+//! ML-DSA signatures on WebSocket events
+//! Shared by the server and `qrms-cli`, which includes this file directly
+
+use pqcrypto_dilithium::dilithium5;
+use pqcrypto_traits::sign::DetachedSignature as _;
+
+/// Prefix of the trailing field that carries an event's ML-DSA signature
+pub const EVENT_SIGNATURE_FIELD: &str = r#","signature":""#;
+
+/// Append a hex signature over `json` (a serialized event object) as a trailing field
+/// The signed bytes are exactly `json`, recovered by stripping the field again.
+pub fn append_event_signature(json: &str, signature: &[u8]) -> String {
+    let body = json.strip_suffix('}').unwrap_or(json);
+    format!("{}{}{}\"}}", body, EVENT_SIGNATURE_FIELD, hex::encode(signature))
+}
+
+/// Check a signed event against the server's ML-DSA public key
+/// Unsigned, malformed, or tampered events are rejected.
+pub fn verify_event_signature(text: &str, public_key: &dilithium5::PublicKey) -> bool {
+    let Some(idx) = text.rfind(EVENT_SIGNATURE_FIELD) else {
+        return false;
+    };
+    let Some(sig_hex) = text[idx + EVENT_SIGNATURE_FIELD.len()..].strip_suffix("\"}") else {
+        return false;
+    };
+    let Some(signature) = hex::decode(sig_hex).ok()
+        .and_then(|bytes| dilithium5::DetachedSignature::from_bytes(&bytes).ok())
+    else {
+        return false;
+    };
+    let signed = format!("{}}}", &text[..idx]);
+    dilithium5::verify_detached_signature(&signature, signed.as_bytes(), public_key).is_ok()
+}
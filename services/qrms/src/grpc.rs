@@ -0,0 +1,167 @@
+//! gRPC Interface
+//! Tonic service exposing status and the live event stream over the shared AppState
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::net::SocketAddr;
+use futures::{Stream, StreamExt};
+use tokio_stream::wrappers::BroadcastStream;
+use tonic::{Request, Response, Status};
+
+use crate::state::{self, AppState, Event};
+
+pub mod proto {
+    tonic::include_proto!("qrms");
+}
+
+use proto::qrms_server::{Qrms, QrmsServer};
+
+/// gRPC service backed by the same state as the axum server
+pub struct QrmsGrpc {
+    state: Arc<AppState>,
+}
+
+impl QrmsGrpc {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+}
+
+/// Serialize a serde enum to its string form (e.g. "schedule_rotation")
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+impl From<state::StatusResponse> for proto::StatusResponse {
+    fn from(status: state::StatusResponse) -> Self {
+        Self {
+            qrm: Some(proto::QrmStatus {
+                risk_score: status.qrm.risk_score,
+                recommendation: enum_name(&status.qrm.recommendation),
+                indicator_count: status.qrm.indicator_count as u64,
+                thresholds: Some(proto::Thresholds {
+                    scheduled: status.qrm.thresholds.scheduled,
+                    emergency: status.qrm.thresholds.emergency,
+                }),
+            }),
+            apqc: Some(proto::ApqcStatus {
+                signatures: status.apqc.signatures,
+                kems: status.apqc.kems,
+                rotation_pending: status.apqc.rotation_pending,
+                rotation_block: status.apqc.rotation_block,
+                combiner_mode: enum_name(&status.apqc.combiner_mode),
+            }),
+            sequencer: Some(proto::SequencerStatus {
+                mempool_size: status.sequencer.mempool_size as u64,
+                ordered_queue: status.sequencer.ordered_queue as u64,
+                batch_count: status.sequencer.batch_count as u64,
+                tee_platform: status.sequencer.tee_platform,
+                mrenclave: status.sequencer.mrenclave,
+                intelligence_mode: enum_name(&status.sequencer.intelligence_mode),
+            }),
+            chain: Some(proto::ChainStatus {
+                height: status.chain.height,
+                algorithm_set: Some(proto::AlgorithmSet {
+                    signatures: status.chain.algorithm_set.signatures,
+                    kems: status.chain.algorithm_set.kems,
+                }),
+                risk_score: status.chain.risk_score,
+            }),
+        }
+    }
+}
+
+impl From<&Event> for proto::Event {
+    fn from(event: &Event) -> Self {
+        let value = serde_json::to_value(event).unwrap_or_default();
+        Self {
+            r#type: value["type"].as_str().unwrap_or_default().to_string(),
+            data_json: value.get("data").map(|d| d.to_string()).unwrap_or_default(),
+        }
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::Event, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Qrms for QrmsGrpc {
+    async fn get_status(
+        &self,
+        _request: Request<proto::StatusRequest>,
+    ) -> Result<Response<proto::StatusResponse>, Status> {
+        Ok(Response::new(state::build_status(&self.state).await.into()))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        _request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        // Lagged receivers skip missed events rather than ending the stream
+        let stream = BroadcastStream::new(self.state.subscribe())
+            .filter_map(|event| async move { event.ok().map(|e| proto::Event::from(&e)) })
+            .map(Ok);
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Run the gRPC server until `shutdown` resolves or it fails
+pub async fn serve(
+    state: Arc<AppState>,
+    addr: SocketAddr,
+    shutdown: impl std::future::Future<Output = ()>,
+) -> Result<(), tonic::transport::Error> {
+    tonic::transport::Server::builder()
+        .add_service(QrmsServer::new(QrmsGrpc::new(state)))
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio_stream::wrappers::TcpListenerStream;
+    use proto::qrms_client::QrmsClient;
+
+    #[tokio::test]
+    async fn test_grpc_status_and_event_stream() {
+        let state = Arc::new(AppState::new());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(QrmsServer::new(QrmsGrpc::new(server_state)))
+                .serve_with_incoming(TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+
+        let mut client = QrmsClient::connect(format!("http://{}", addr)).await.unwrap();
+
+        let status = client.get_status(proto::StatusRequest {}).await.unwrap().into_inner();
+        let qrm = status.qrm.unwrap();
+        assert_eq!(qrm.thresholds.unwrap().scheduled, 6000);
+        assert_eq!(status.sequencer.unwrap().intelligence_mode, "hybrid");
+        assert_eq!(status.chain.unwrap().height, 0);
+
+        let mut events = client.stream_events(proto::StreamEventsRequest {}).await.unwrap().into_inner();
+        state::inject_high_threats(&state).await;
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.message())
+            .await
+            .expect("event within timeout")
+            .unwrap()
+            .expect("stream open");
+        assert_eq!(event.r#type, "qrm_update");
+        let data: serde_json::Value = serde_json::from_str(&event.data_json).unwrap();
+        assert!(data["risk"]["score"].as_u64().unwrap() > 0);
+    }
+}
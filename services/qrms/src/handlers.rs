@@ -1,72 +1,169 @@
 //! HTTP and WebSocket Handlers
 
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use axum::{
-    extract::{State, ws::{WebSocket, WebSocketUpgrade, Message}},
+    extract::{ConnectInfo, Path, Query, State, ws::{WebSocket, WebSocketUpgrade, Message}},
+    http::{header, StatusCode},
     response::IntoResponse,
     Json,
 };
 use futures::{StreamExt, SinkExt};
 use serde::{Deserialize, Serialize};
 
-use crate::state::{AppState, StatusResponse, QrmStatus, ApqcStatus, SequencerStatus, ChainStatus, Thresholds, Event, inject_high_threats};
+use crate::state::{AppState, StatusResponse, Event, SimulationConfig, build_status, inject_high_threats};
+use crate::aegis_tee::{AccessPolicy, AssetProtection, AssetType, IntelligenceOrdering, MigrationState};
+use crate::sequencer::{OrderingMode, MAX_BATCH_SIZE};
 use crate::qrm::{ThreatCategory, ThreatIndicator, QuantumEra};
+use crate::apqc::{AlgorithmSetError, KemAlgorithm, KemBenchmark, SignatureAlgorithm, SigningBenchmark};
+use crate::qvm::{AssessmentDiff, CircuitResult, OracleAssessment, QuantumCircuit, QuantumProcessor, QvmSimulator};
+use crate::hndl::HndlExposure;
 
 /// GET /api/status
 pub async fn get_status(State(state): State<Arc<AppState>>) -> Json<StatusResponse> {
-    // Acquire locks one at a time and release before next to avoid deadlocks
-    let (risk, indicator_count, threshold_scheduled, threshold_emergency) = {
-        let mut qrm = state.qrm.lock().await;
-        let risk = qrm.calculate_risk();
-        (risk, qrm.indicator_count(), qrm.threshold_scheduled, qrm.threshold_emergency)
-    };
-    
-    let apqc_status = {
-        let apqc = state.apqc.lock().await;
-        ApqcStatus {
-            signatures: apqc.active_signatures.iter().map(|s| s.name().to_string()).collect(),
-            kems: apqc.active_kems.iter().map(|k| k.name().to_string()).collect(),
-            rotation_pending: apqc.rotation_pending,
-            rotation_block: apqc.rotation_block,
-        }
+    Json(build_status(&state).await)
+}
+
+/// GET /healthz
+/// Liveness: answers as soon as the HTTP server is up, without taking any lock
+pub async fn get_healthz() -> StatusCode {
+    StatusCode::OK
+}
+
+/// GET /readyz
+/// 503 until the simulation task is running
+pub async fn get_readyz(State(state): State<Arc<AppState>>) -> (StatusCode, Json<ReadinessResponse>) {
+    let readiness = &state.readiness;
+    let ready = readiness.is_ready();
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(ReadinessResponse {
+        ready,
+        simulation_started: readiness.simulation_started.load(std::sync::atomic::Ordering::Acquire),
+    }))
+}
+
+#[derive(Serialize)]
+pub struct ReadinessResponse {
+    ready: bool,
+    simulation_started: bool,
+}
+
+/// GET /metrics (Prometheus text exposition format)
+pub async fn get_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render_metrics(&state).await,
+    )
+}
+
+async fn render_metrics(state: &AppState) -> String {
+    let (risk, indicator_count) = {
+        let qrm = state.qrm.lock().await;
+        let risk = qrm.current_risk();
+        (risk, qrm.indicator_count())
     };
-    
-    let sequencer_status = {
+    let (mempool_size, batch_count) = {
         let sequencer = state.sequencer.lock().await;
-        SequencerStatus {
-            mempool_size: sequencer.mempool_size(),
-            ordered_queue: sequencer.ordered_queue_size(),
-            batch_count: sequencer.batch_count(),
-            tee_platform: sequencer.tee_platform.clone(),
-            mrenclave: sequencer.mrenclave.clone(),
-        }
+        (sequencer.mempool_size(), sequencer.batch_count())
     };
-    
-    let chain_status = {
-        let chain = state.chain.lock().await;
-        ChainStatus {
-            height: chain.current_height,
-            algorithm_set: chain.algorithm_set.clone(),
-            risk_score: chain.risk_score,
+    let chain_height = state.chain.lock().await.current_height;
+    let rotations = state.apqc.lock().await.rotation_count;
+    let qvm_risk = state.qvm.lock().await
+        .last_assessment.as_ref()
+        .map(|a| a.composite_risk)
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, f64)]| {
+        out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", name, help, name, kind));
+        for (labels, value) in samples {
+            out.push_str(&format!("{}{} {}\n", name, labels, value));
         }
     };
 
-    Json(StatusResponse {
-        qrm: QrmStatus {
-            risk_score: risk.score,
-            recommendation: risk.recommendation,
-            indicator_count,
-            thresholds: Thresholds {
-                scheduled: threshold_scheduled,
-                emergency: threshold_emergency,
-            },
+    metric("qrms_risk_score", "gauge", "Aggregate quantum risk score in basis points",
+        &[(String::new(), risk.score as f64)]);
+    // Every category gets a series, scoring 0 until it has indicators
+    let categories: Vec<(String, f64)> = ThreatCategory::all().iter()
+        .map(|&category| {
+            let score = risk.category_breakdown.iter()
+                .find(|c| c.category == category)
+                .map(|c| c.score)
+                .unwrap_or(0);
+            (format!("{{category=\"{}\"}}", category_label(category)), score as f64)
+        })
+        .collect();
+    metric("qrms_category_risk_score", "gauge", "Per-category risk score in basis points", &categories);
+    metric("qrms_indicator_count", "gauge", "Threat indicators currently tracked",
+        &[(String::new(), indicator_count as f64)]);
+    metric("qrms_mempool_size", "gauge", "Transactions waiting in the sequencer mempool",
+        &[(String::new(), mempool_size as f64)]);
+    metric("qrms_batches_total", "counter", "Batches produced by the sequencer",
+        &[(String::new(), batch_count as f64)]);
+    metric("qrms_chain_height", "gauge", "Current chain height",
+        &[(String::new(), chain_height as f64)]);
+    metric("qrms_rotations_total", "counter", "PQC key rotations executed",
+        &[(String::new(), rotations as f64)]);
+    metric("qrms_qvm_composite_risk", "gauge", "Composite risk from the last QVM oracle assessment",
+        &[(String::new(), qvm_risk as f64)]);
+
+    out
+}
+
+/// snake_case category name, matching the JSON API
+fn category_label(category: ThreatCategory) -> String {
+    serde_json::to_value(category)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// GET /api/pubkeys
+pub async fn get_public_keys(State(state): State<Arc<AppState>>) -> Json<PublicKeysResponse> {
+    let apqc = state.apqc.lock().await;
+    let (mldsa, slhdsa, ecdsa) = apqc.get_public_keys().await;
+    let key = |algorithm, bytes: Vec<u8>| PublicKeyInfo {
+        algorithm,
+        size: bytes.len(),
+        public_key: hex::encode(bytes),
+    };
+
+    Json(PublicKeysResponse {
+        keys: vec![
+            key("ML-DSA-87", mldsa),
+            key("SLH-DSA-256s", slhdsa),
+            key("ECDSA-secp256k1", ecdsa),
+        ],
+        rotation: RotationStatus {
+            pending: apqc.rotation_pending,
+            effective_block: apqc.rotation_block,
         },
-        apqc: apqc_status,
-        sequencer: sequencer_status,
-        chain: chain_status,
     })
 }
 
+#[derive(Serialize)]
+pub struct PublicKeysResponse {
+    keys: Vec<PublicKeyInfo>,
+    /// Keys are replaced once a pending rotation executes
+    rotation: RotationStatus,
+}
+
+#[derive(Serialize)]
+pub struct PublicKeyInfo {
+    algorithm: &'static str,
+    /// Key length in bytes
+    size: usize,
+    public_key: String,
+}
+
+#[derive(Serialize)]
+pub struct RotationStatus {
+    pending: bool,
+    effective_block: Option<u64>,
+}
+
 /// GET /api/qrm/history
 pub async fn get_qrm_history(State(state): State<Arc<AppState>>) -> Json<QrmHistoryResponse> {
     let qrm = state.qrm.lock().await;
@@ -83,40 +180,410 @@ pub struct QrmHistoryResponse {
     risk_history: Vec<crate::qrm::RiskAssessment>,
 }
 
+/// GET /api/qrm/indicators?category=key_management&min_severity=0.9&since=RFC3339&limit=N
+pub async fn get_indicators(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<IndicatorQuery>,
+) -> Result<Json<IndicatorsResponse>, (StatusCode, String)> {
+    let category: Option<ThreatCategory> = params.category.as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let limit = params.limit.unwrap_or(20).clamp(1, 200);
+    let qrm = state.qrm.lock().await;
+
+    let indicators = qrm.get_indicators()
+        .into_iter()
+        .rev()
+        .filter(|i| category.is_none_or(|c| i.category == c))
+        .filter(|i| params.min_severity.is_none_or(|min| i.severity >= min))
+        .filter(|i| params.since.is_none_or(|since| i.timestamp >= since))
+        .take(limit)
+        .collect();
+
+    Ok(Json(IndicatorsResponse { indicators }))
+}
+
+#[derive(Deserialize)]
+pub struct IndicatorQuery {
+    category: Option<String>,
+    min_severity: Option<f64>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct IndicatorsResponse {
+    indicators: Vec<ThreatIndicator>,
+}
+
+/// GET /api/qrm/category_history?category=digital_signatures&points=N
+pub async fn get_category_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CategoryHistoryQuery>,
+) -> Json<CategoryHistoryResponse> {
+    let points = params.points.unwrap_or(50).clamp(1, 500);
+    let qrm = state.qrm.lock().await;
+
+    Json(CategoryHistoryResponse {
+        category: params.category,
+        points: qrm.get_category_history(params.category, points),
+    })
+}
+
+#[derive(Deserialize)]
+pub struct CategoryHistoryQuery {
+    category: ThreatCategory,
+    points: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct CategoryHistoryResponse {
+    category: ThreatCategory,
+    points: Vec<crate::qrm::CategoryHistoryPoint>,
+}
+
+/// GET /api/qrm/risk?tags=production,theoretical
+pub async fn get_filtered_risk(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RiskFilterQuery>,
+) -> Json<crate::qrm::RiskAssessment> {
+    let tags: Vec<String> = params.tags
+        .unwrap_or_default()
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let qrm = state.qrm.lock().await;
+
+    Json(qrm.calculate_risk_filtered(&tags))
+}
+
+#[derive(Deserialize)]
+pub struct RiskFilterQuery {
+    tags: Option<String>,
+}
+
 /// GET /api/blocks
 pub async fn get_blocks(State(state): State<Arc<AppState>>) -> Json<BlocksResponse> {
     let chain = state.chain.lock().await;
     
     Json(BlocksResponse {
         blocks: chain.get_recent_blocks(20),
+        compacted: chain.compacted().clone(),
     })
 }
 
+/// GET /api/batch/:batch_id
+/// Full batch (transactions, signatures, attestation) for auditing a block
+pub async fn get_batch(
+    State(state): State<Arc<AppState>>,
+    Path(batch_id): Path<String>,
+) -> Result<Json<crate::sequencer::Batch>, (StatusCode, String)> {
+    let sequencer = state.sequencer.lock().await;
+    sequencer.get_batch(&batch_id)
+        .map(|batch| Json(batch.clone()))
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("batch '{}' not found", batch_id)))
+}
+
 #[derive(Serialize)]
 pub struct BlocksResponse {
     blocks: Vec<crate::chain::Block>,
+    compacted: crate::chain::CompactedHistory,
+}
+
+/// GET /api/hndl
+pub async fn get_hndl_exposure(State(state): State<Arc<AppState>>) -> Json<HndlResponse> {
+    let hndl = state.hndl.lock().await;
+    let qvm = state.qvm.lock().await;
+
+    Json(HndlResponse {
+        processor_qubits: qvm.oracle.simulator().processor().qubit_count(),
+        qubit_doubling_years: hndl.qubit_doubling_years,
+        assets: hndl.assess_all(&qvm.oracle),
+    })
+}
+
+#[derive(Serialize)]
+pub struct HndlResponse {
+    processor_qubits: usize,
+    qubit_doubling_years: f64,
+    assets: Vec<HndlExposure>,
+}
+
+/// GET /api/apqc/kem/benchmark
+pub async fn get_kem_benchmark(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BenchmarkQuery>,
+) -> Result<Json<KemBenchmark>, (StatusCode, String)> {
+    let iterations = params.iterations.unwrap_or(10).clamp(1, 100);
+    // Copy the keys out so hybrid encapsulation is not blocked for the whole run
+    let keys = state.apqc.lock().await.kem_keys().await;
+
+    tokio::task::spawn_blocking(move || keys.benchmark_kems(iterations))
+        .await
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// GET /api/apqc/benchmark
+/// SLH-DSA signing dominates, so iterations are capped lower than the KEM benchmark
+pub async fn get_signing_benchmark(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BenchmarkQuery>,
+) -> Result<Json<SigningBenchmark>, (StatusCode, String)> {
+    let iterations = params.iterations.unwrap_or(3).clamp(1, 20);
+    // Copy the keys out so signing and rotation are not blocked for the whole run
+    let keys = state.apqc.lock().await.signing_keys().await;
+
+    tokio::task::spawn_blocking(move || keys.benchmark_signatures(iterations))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[derive(Deserialize)]
+pub struct BenchmarkQuery {
+    iterations: Option<usize>,
+}
+
+/// POST /api/apqc/algorithms
+/// Replace the active signature and/or KEM sets; nothing changes if either is rejected
+pub async fn update_active_algorithms(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ActiveAlgorithmsRequest>,
+) -> Result<Json<ActiveAlgorithmsResponse>, (StatusCode, String)> {
+    let mut apqc = state.apqc.lock().await;
+    let bad_request = |e: AlgorithmSetError| (StatusCode::BAD_REQUEST, e.to_string());
+
+    // Validate both sets before applying either
+    if let Some(kems) = &payload.kems {
+        apqc.validate_kems(kems).map_err(bad_request)?;
+    }
+    if let Some(signatures) = &payload.signatures {
+        apqc.validate_signatures(signatures).await.map_err(bad_request)?;
+    }
+
+    if let Some(kems) = payload.kems {
+        apqc.set_active_kems(kems).map_err(bad_request)?;
+    }
+    if let Some(signatures) = payload.signatures {
+        apqc.set_active_signatures(signatures).await.map_err(bad_request)?;
+    }
+
+    Ok(Json(ActiveAlgorithmsResponse {
+        signatures: apqc.active_signatures.clone(),
+        kems: apqc.active_kems.clone(),
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct ActiveAlgorithmsRequest {
+    signatures: Option<Vec<SignatureAlgorithm>>,
+    kems: Option<Vec<KemAlgorithm>>,
+}
+
+#[derive(Serialize)]
+pub struct ActiveAlgorithmsResponse {
+    signatures: Vec<SignatureAlgorithm>,
+    kems: Vec<KemAlgorithm>,
+}
+
+/// POST /api/apqc/compromise
+/// Record a compromise disclosure for a signature algorithm
+pub async fn disclose_compromise(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CompromiseRequest>,
+) -> Json<CompromiseResponse> {
+    let description = payload.description
+        .unwrap_or_else(|| format!("{} compromise disclosed", payload.algorithm.name()));
+    let risk = crate::state::disclose_compromise(&state, payload.algorithm, &description).await;
+
+    Json(CompromiseResponse {
+        status: state.apqc.lock().await.algorithm_status().to_vec(),
+        risk,
+    })
+}
+
+#[derive(Deserialize)]
+pub struct CompromiseRequest {
+    algorithm: SignatureAlgorithm,
+    description: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CompromiseResponse {
+    status: Vec<crate::apqc::AlgorithmStatus>,
+    risk: crate::qrm::RiskAssessment,
+}
+
+/// GET /api/qvm/selftest
+pub async fn get_qvm_selftest() -> Json<crate::qvm::QvmSelfTestReport> {
+    Json(crate::qvm::run_golden_selftest())
+}
+
+/// POST /api/qvm/assess
+/// The oracle simulation is CPU-bound, so it runs on the blocking pool
+pub async fn run_qvm_assessment(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<OracleAssessment>, (StatusCode, String)> {
+    let worker_state = state.clone();
+    let assessment = tokio::task::spawn_blocking(move || {
+        let mut qvm = worker_state.qvm.blocking_lock();
+        qvm.assess_and_update();
+        qvm.last_assessment.clone()
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or_else(|| (StatusCode::INTERNAL_SERVER_ERROR, "assessment was not recorded".to_string()))?;
+
+    state.broadcast(Event::QvmAssessment {
+        grover_threats: assessment.grover_assessments.clone(),
+        shor_threats: assessment.shor_assessments.clone(),
+        composite_risk: assessment.composite_risk,
+    });
+
+    Ok(Json(assessment))
+}
+
+/// GET /api/qvm/history?offset=N&limit=N
+/// Oracle assessments, newest first
+pub async fn get_qvm_history(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<QvmHistoryQuery>,
+) -> Json<QvmHistoryResponse> {
+    let offset = params.offset.unwrap_or(0);
+    let limit = params.limit.unwrap_or(20).clamp(1, 200);
+    let qvm = state.qvm.lock().await;
+
+    Json(QvmHistoryResponse {
+        total: qvm.oracle.get_threat_history().len(),
+        offset,
+        assessments: qvm.oracle.get_history_page(offset, limit),
+    })
+}
+
+#[derive(Deserialize)]
+pub struct QvmHistoryQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct QvmHistoryResponse {
+    total: usize,
+    offset: usize,
+    assessments: Vec<OracleAssessment>,
+}
+
+/// GET /api/qvm/diff?from=N&to=N
+/// Indices address the retained history, oldest first; defaults to the two most recent
+pub async fn get_qvm_diff(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<QvmDiffQuery>,
+) -> Result<Json<AssessmentDiff>, (StatusCode, String)> {
+    let qvm = state.qvm.lock().await;
+    let history = qvm.oracle.get_threat_history();
+    let to = params.to.unwrap_or(history.len().saturating_sub(1));
+    let from = params.from.unwrap_or(to.saturating_sub(1));
+
+    let lookup = |index: usize| history.get(index).ok_or_else(|| (
+        StatusCode::NOT_FOUND,
+        format!("no assessment at index {} ({} retained)", index, history.len()),
+    ));
+    Ok(Json(lookup(from)?.diff(lookup(to)?)))
+}
+
+#[derive(Deserialize)]
+pub struct QvmDiffQuery {
+    from: Option<usize>,
+    to: Option<usize>,
+}
+
+/// GET /api/qvm/fidelity_curve?qubits=N&max_depth=N&processor=WillowPink
+/// Defaults to the stack's configured processor
+pub async fn get_fidelity_curve(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<FidelityCurveQuery>,
+) -> Result<Json<FidelityCurveResponse>, (StatusCode, String)> {
+    let processor = match params.processor {
+        Some(processor) => processor,
+        None => state.qvm.lock().await.config.processor,
+    };
+    if processor.qubit_count() == 0 {
+        return Err((StatusCode::BAD_REQUEST, "processor has no qubits".to_string()));
+    }
+    let qubits = params.qubits.unwrap_or(8).clamp(1, processor.qubit_count());
+    let max_depth = params.max_depth.unwrap_or(50).clamp(1, 1000);
+
+    let points = QvmSimulator::new(processor)
+        .fidelity_curve(qubits, max_depth)
+        .into_iter()
+        .map(|(depth, fidelity)| FidelityPoint { depth, fidelity })
+        .collect();
+
+    Ok(Json(FidelityCurveResponse { processor, qubits, points }))
+}
+
+#[derive(Deserialize)]
+pub struct FidelityCurveQuery {
+    qubits: Option<usize>,
+    max_depth: Option<usize>,
+    processor: Option<QuantumProcessor>,
+}
+
+#[derive(Serialize)]
+pub struct FidelityPoint {
+    depth: usize,
+    fidelity: f64,
+}
+
+#[derive(Serialize)]
+pub struct FidelityCurveResponse {
+    processor: QuantumProcessor,
+    qubits: usize,
+    points: Vec<FidelityPoint>,
+}
+
+/// POST /api/qvm/circuit
+pub async fn run_qvm_circuit(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CircuitRequest>,
+) -> Result<Json<CircuitResult>, (StatusCode, String)> {
+    let repetitions = payload.repetitions.unwrap_or(1000).clamp(1, 10_000);
+    payload.circuit.validate().map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let mut qvm = state.qvm.lock().await;
+
+    qvm.run_custom_circuit(&payload.circuit, repetitions)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+#[derive(Deserialize)]
+pub struct CircuitRequest {
+    circuit: QuantumCircuit,
+    repetitions: Option<usize>,
+}
+
+/// Refuse a threat injection once the client has exhausted its per-minute budget
+async fn check_inject_rate(state: &AppState, client: IpAddr) -> Result<(), (StatusCode, String)> {
+    if state.inject_limiter.lock().await.check(client) {
+        Ok(())
+    } else {
+        Err((StatusCode::TOO_MANY_REQUESTS, "threat injection rate limit exceeded".to_string()))
+    }
 }
 
 /// POST /api/inject_threat
 pub async fn inject_threat(
     State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Json(payload): Json<InjectThreatRequest>,
-) -> Json<InjectThreatResponse> {
-    let category = match payload.category.as_str() {
-        "digital_signatures" => ThreatCategory::DigitalSignatures,
-        "zk_proof_forgery" => ThreatCategory::ZkProofForgery,
-        "decryption_hndl" => ThreatCategory::DecryptionHndl,
-        "hash_reversal" => ThreatCategory::HashReversal,
-        "consensus_attacks" => ThreatCategory::ConsensusAttacks,
-        "cross_chain_bridge" => ThreatCategory::CrossChainBridge,
-        "network_layer" => ThreatCategory::NetworkLayer,
-        "key_management" => ThreatCategory::KeyManagement,
-        "mev_ordering" => ThreatCategory::MevOrdering,
-        "smart_contracts" => ThreatCategory::SmartContracts,
-        "side_channel" => ThreatCategory::SideChannel,
-        "migration_agility" => ThreatCategory::MigrationAgility,
-        _ => ThreatCategory::DigitalSignatures,
-    };
+) -> Result<Json<InjectThreatResponse>, (StatusCode, String)> {
+    check_inject_rate(&state, addr.ip()).await?;
+    let category: ThreatCategory = payload.category.parse()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
     
     let era = match payload.era_relevance.as_deref() {
         Some("pre_quantum") => QuantumEra::PreQuantum,
@@ -135,6 +602,8 @@ pub async fn inject_threat(
         description: payload.description.unwrap_or_else(|| "Manually injected threat".to_string()),
         era_relevance: era,
         references: payload.references.unwrap_or_default(),
+        tags: payload.tags.unwrap_or_default(),
+        expires_at: payload.expires_at,
     };
 
     let risk = {
@@ -148,7 +617,7 @@ pub async fn inject_threat(
         risk: risk.clone(),
     });
 
-    Json(InjectThreatResponse { indicator, risk })
+    Ok(Json(InjectThreatResponse { indicator, risk }))
 }
 
 #[derive(Deserialize)]
@@ -161,6 +630,8 @@ pub struct InjectThreatRequest {
     description: Option<String>,
     era_relevance: Option<String>,
     references: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize)]
@@ -169,6 +640,199 @@ pub struct InjectThreatResponse {
     risk: crate::qrm::RiskAssessment,
 }
 
+/// POST /api/assets
+/// Register an asset with the Aegis-TEE sequencer; ids cannot be re-registered
+pub async fn register_asset(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<RegisterAssetRequest>,
+) -> Result<(StatusCode, Json<AssetView>), (StatusCode, String)> {
+    let asset = AssetProtection {
+        asset_id: payload.asset_id,
+        asset_type: payload.asset_type,
+        chain_id: payload.chain_id,
+        contract_address: payload.contract_address,
+        encryption_key: payload.encryption_key,
+        access_policy: payload.access_policy,
+        migration_state: MigrationState::Active,
+    };
+    asset.validate().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut aegis_tee = state.aegis_tee.lock().await;
+    if aegis_tee.get_asset_protection(&asset.asset_id).is_some() {
+        return Err((StatusCode::CONFLICT, format!("asset '{}' is already registered", asset.asset_id)));
+    }
+    let view = AssetView::from(&asset);
+    aegis_tee.register_asset(asset);
+
+    Ok((StatusCode::CREATED, Json(view)))
+}
+
+/// GET /api/assets
+pub async fn list_assets(State(state): State<Arc<AppState>>) -> Json<Vec<AssetView>> {
+    let aegis_tee = state.aegis_tee.lock().await;
+    Json(aegis_tee.registered_assets().into_iter().map(AssetView::from).collect())
+}
+
+/// GET /api/assets/:id
+pub async fn get_asset(
+    State(state): State<Arc<AppState>>,
+    Path(asset_id): Path<String>,
+) -> Result<Json<AssetView>, (StatusCode, String)> {
+    let aegis_tee = state.aegis_tee.lock().await;
+    aegis_tee.get_asset_protection(&asset_id)
+        .map(|asset| Json(AssetView::from(asset)))
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("asset '{}' is not registered", asset_id)))
+}
+
+#[derive(Deserialize)]
+pub struct RegisterAssetRequest {
+    asset_id: String,
+    asset_type: AssetType,
+    #[serde(default)]
+    chain_id: Option<u64>,
+    #[serde(default)]
+    contract_address: Option<String>,
+    #[serde(default)]
+    encryption_key: Vec<u8>,
+    access_policy: AccessPolicy,
+}
+
+/// Asset protection as served over HTTP, with the encryption key withheld
+#[derive(Serialize)]
+pub struct AssetView {
+    asset_id: String,
+    asset_type: AssetType,
+    chain_id: Option<u64>,
+    contract_address: Option<String>,
+    encryption_key: &'static str,
+    access_policy: AccessPolicy,
+    migration_state: MigrationState,
+}
+
+impl From<&AssetProtection> for AssetView {
+    fn from(asset: &AssetProtection) -> Self {
+        Self {
+            asset_id: asset.asset_id.clone(),
+            asset_type: asset.asset_type.clone(),
+            chain_id: asset.chain_id,
+            contract_address: asset.contract_address.clone(),
+            encryption_key: if asset.encryption_key.is_empty() { "" } else { "[REDACTED]" },
+            access_policy: asset.access_policy.clone(),
+            migration_state: asset.migration_state,
+        }
+    }
+}
+
+/// POST /api/config
+pub async fn update_config(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ConfigRequest>,
+) -> Json<ConfigResponse> {
+    let intelligence_mode = {
+        let mut aegis_tee = state.aegis_tee.lock().await;
+        if let Some(mode) = payload.intelligence_mode {
+            aegis_tee.set_intelligence_mode(mode);
+        }
+        aegis_tee.intelligence_mode()
+    };
+
+    let min_block_interval_ms = {
+        // The simulation loop orders through this sequencer, so it takes the mode too
+        let mut sequencer = state.sequencer.lock().await;
+        if let Some(mode) = payload.intelligence_mode {
+            sequencer.intelligence_mode = Some(mode);
+        }
+        if let Some(ms) = payload.min_block_interval_ms {
+            sequencer.min_block_interval = std::time::Duration::from_millis(ms);
+        }
+        sequencer.min_block_interval.as_millis() as u64
+    };
+
+    Json(ConfigResponse {
+        intelligence_mode,
+        min_block_interval_ms,
+    })
+}
+
+#[derive(Deserialize)]
+pub struct ConfigRequest {
+    intelligence_mode: Option<IntelligenceOrdering>,
+    min_block_interval_ms: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct ConfigResponse {
+    intelligence_mode: IntelligenceOrdering,
+    min_block_interval_ms: u64,
+}
+
+/// POST /api/sequencer/config
+pub async fn update_sequencer_config(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SequencerConfigRequest>,
+) -> Result<Json<SequencerConfigResponse>, (StatusCode, String)> {
+    if let Some(size) = payload.batch_size {
+        if !(1..=MAX_BATCH_SIZE).contains(&size) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("batch_size must be between 1 and {}", MAX_BATCH_SIZE),
+            ));
+        }
+    }
+
+    let mut sequencer = state.sequencer.lock().await;
+    if let Some(size) = payload.batch_size {
+        sequencer.batch_size = size;
+    }
+    if let Some(mode) = payload.ordering_mode {
+        sequencer.ordering_mode = mode;
+    }
+
+    Ok(Json(SequencerConfigResponse {
+        batch_size: sequencer.batch_size,
+        ordering_mode: sequencer.ordering_mode,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct SequencerConfigRequest {
+    batch_size: Option<usize>,
+    ordering_mode: Option<OrderingMode>,
+}
+
+#[derive(Serialize)]
+pub struct SequencerConfigResponse {
+    batch_size: usize,
+    ordering_mode: OrderingMode,
+}
+
+/// POST /api/simulation/config
+/// Omitted fields keep their current value; the merged config must validate.
+pub async fn update_simulation_config(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<SimulationConfigRequest>,
+) -> Result<Json<SimulationConfig>, (StatusCode, String)> {
+    let mut config = state.simulation_config.lock().await;
+    let updated = SimulationConfig {
+        tick_ms: payload.tick_ms.unwrap_or(config.tick_ms),
+        min_txs: payload.min_txs.unwrap_or(config.min_txs),
+        max_txs: payload.max_txs.unwrap_or(config.max_txs),
+        threat_probability: payload.threat_probability.unwrap_or(config.threat_probability),
+    };
+    updated.validate().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    *config = updated;
+    Ok(Json(updated))
+}
+
+#[derive(Deserialize)]
+pub struct SimulationConfigRequest {
+    tick_ms: Option<u64>,
+    min_txs: Option<usize>,
+    max_txs: Option<usize>,
+    threat_probability: Option<f64>,
+}
+
 /// POST /api/simulation/start
 pub async fn start_simulation(State(state): State<Arc<AppState>>) -> Json<SimulationResponse> {
     let mut running = state.simulation_running.lock().await;
@@ -193,93 +857,63 @@ pub struct SimulationResponse {
 }
 
 /// POST /api/inject_high_threat
-pub async fn inject_high_threat(State(state): State<Arc<AppState>>) -> Json<SimulationResponse> {
+pub async fn inject_high_threat(
+    State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<Json<SimulationResponse>, (StatusCode, String)> {
+    check_inject_rate(&state, addr.ip()).await?;
     inject_high_threats(&state).await;
-    Json(SimulationResponse { status: "injected".to_string() })
+    Ok(Json(SimulationResponse { status: "injected".to_string() }))
 }
 
 /// WebSocket handler
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, addr.ip()))
 }
 
-async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
-    let (mut sender, mut receiver) = socket.split();
+async fn handle_socket(socket: WebSocket, state: Arc<AppState>, client: IpAddr) {
+    let (sender, mut receiver) = socket.split();
+    let sender = Arc::new(tokio::sync::Mutex::new(sender));
     
-    // Subscribe to events
-    let mut rx = state.subscribe();
+    // Subscribe to encoded events
+    let mut rx = state.subscribe_frames();
+    // Event names this client asked for; None forwards everything
+    let filter: Arc<tokio::sync::Mutex<Option<HashSet<String>>>> = Arc::new(tokio::sync::Mutex::new(None));
+    // Set by the `binary` command; signed deployments keep text frames
+    let binary = Arc::new(AtomicBool::new(false));
     
-    // Build initial status without nested locks
-    let initial_status = {
-        let (risk, indicator_count, threshold_scheduled, threshold_emergency) = {
-            let mut qrm = state.qrm.lock().await;
-            let risk = qrm.calculate_risk();
-            (risk, qrm.indicator_count(), qrm.threshold_scheduled, qrm.threshold_emergency)
-        };
-        
-        let apqc_status = {
-            let apqc = state.apqc.lock().await;
-            ApqcStatus {
-                signatures: apqc.active_signatures.iter().map(|s| s.name().to_string()).collect(),
-                kems: apqc.active_kems.iter().map(|k| k.name().to_string()).collect(),
-                rotation_pending: apqc.rotation_pending,
-                rotation_block: apqc.rotation_block,
-            }
-        };
-        
-        let sequencer_status = {
-            let sequencer = state.sequencer.lock().await;
-            SequencerStatus {
-                mempool_size: sequencer.mempool_size(),
-                ordered_queue: sequencer.ordered_queue_size(),
-                batch_count: sequencer.batch_count(),
-                tee_platform: sequencer.tee_platform.clone(),
-                mrenclave: sequencer.mrenclave.clone(),
-            }
-        };
-        
-        let chain_status = {
-            let chain = state.chain.lock().await;
-            ChainStatus {
-                height: chain.current_height,
-                algorithm_set: chain.algorithm_set.clone(),
-                risk_score: chain.risk_score,
-            }
-        };
-        
-        StatusResponse {
-            qrm: QrmStatus {
-                risk_score: risk.score,
-                recommendation: risk.recommendation,
-                indicator_count,
-                thresholds: Thresholds {
-                    scheduled: threshold_scheduled,
-                    emergency: threshold_emergency,
-                },
-            },
-            apqc: apqc_status,
-            sequencer: sequencer_status,
-            chain: chain_status,
-        }
-    };
+    let initial_status = build_status(&state).await;
     
     // Send initial status
     if let Ok(status_json) = serde_json::to_string(&initial_status) {
-        let _ = sender.send(Message::Text(format!(r#"{{"type":"status","data":{}}}"#, status_json))).await;
+        let _ = sender.lock().await.send(Message::Text(format!(r#"{{"type":"status","data":{}}}"#, status_json))).await;
     }
 
     // Handle incoming messages and broadcast events
     let state_clone = state.clone();
+    let event_sender = sender.clone();
+    let event_filter = filter.clone();
+    let event_binary = binary.clone();
     let send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
-            if let Ok(json) = serde_json::to_string(&event) {
-                if sender.send(Message::Text(json)).await.is_err() {
-                    break;
+        while let Ok(frame) = rx.recv().await {
+            if event_filter.lock().await.as_ref().is_some_and(|names| !names.contains(frame.event.name())) {
+                continue;
+            }
+            if event_binary.load(Ordering::Relaxed) {
+                if let Some(binary) = &frame.binary {
+                    if event_sender.lock().await.send(Message::Binary(binary.clone())).await.is_err() {
+                        break;
+                    }
+                    continue;
                 }
             }
+            if event_sender.lock().await.send(Message::Text(frame.text.clone())).await.is_err() {
+                break;
+            }
         }
     });
 
@@ -301,7 +935,24 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                                 state_clone.broadcast(Event::SimulationStopped);
                             }
                             "inject_high" => {
-                                inject_high_threats(&state_clone).await;
+                                if check_inject_rate(&state_clone, client).await.is_ok() {
+                                    inject_high_threats(&state_clone).await;
+                                } else {
+                                    tracing::warn!(%client, "WebSocket threat injection rate limited");
+                                }
+                            }
+                            "subscribe" => {
+                                // Omitting `events` restores the default of all events
+                                let names = cmd.events.map(|events| events.into_iter().collect::<HashSet<_>>());
+                                let ack = serde_json::json!({ "type": "subscribed", "data": names });
+                                *filter.lock().await = names;
+                                let _ = sender.lock().await.send(Message::Text(ack.to_string())).await;
+                            }
+                            "binary" => {
+                                let enabled = cmd.enabled.unwrap_or(true) && !state_clone.sign_events;
+                                binary.store(enabled, Ordering::Relaxed);
+                                let ack = serde_json::json!({ "type": "binary", "data": enabled });
+                                let _ = sender.lock().await.send(Message::Text(ack.to_string())).await;
                             }
                             _ => {}
                         }
@@ -322,4 +973,528 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
 #[derive(Deserialize)]
 struct ClientCommand {
     command: String,
+    /// Event names for the `subscribe` command
+    #[serde(default)]
+    events: Option<Vec<String>>,
+    /// Turn binary batch frames on or off for the `binary` command (default on)
+    #[serde(default)]
+    enabled: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::MldsaKeyPair;
+    use crate::event_signature::{append_event_signature, verify_event_signature};
+
+    #[tokio::test]
+    async fn test_category_history_returns_requested_points() {
+        let state = Arc::new(AppState::new());
+        {
+            let mut qrm = state.qrm.lock().await;
+            for _ in 0..8 {
+                qrm.simulate_threat_feed(&mut rand::thread_rng());
+                qrm.calculate_risk();
+            }
+        }
+
+        let Json(response) = get_category_history(
+            State(state.clone()),
+            Query(CategoryHistoryQuery { category: ThreatCategory::DigitalSignatures, points: Some(5) }),
+        ).await;
+
+        assert_eq!(response.points.len(), 5);
+        assert!(response.points.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    }
+
+    #[tokio::test]
+    async fn test_indicator_query_filters_category_and_severity() {
+        let state = Arc::new(AppState::new());
+        {
+            let mut qrm = state.qrm.lock().await;
+            for _ in 0..40 {
+                qrm.simulate_threat_feed(&mut rand::thread_rng());
+            }
+            for (category, severity) in [
+                (ThreatCategory::KeyManagement, 0.95),
+                (ThreatCategory::KeyManagement, 0.5),
+                (ThreatCategory::DigitalSignatures, 0.99),
+            ] {
+                let mut indicator = qrm.get_indicators()[0].clone();
+                indicator.category = category;
+                indicator.severity = severity;
+                indicator.timestamp = chrono::Utc::now();
+                qrm.add_indicator(indicator);
+            }
+        }
+
+        let query: IndicatorQuery = serde_json::from_str(
+            r#"{"category": "key_management", "min_severity": 0.9, "limit": 200}"#,
+        ).unwrap();
+        let Json(response) = get_indicators(State(state.clone()), Query(query)).await.unwrap();
+
+        assert!(!response.indicators.is_empty());
+        assert!(response.indicators.iter()
+            .all(|i| i.category == ThreatCategory::KeyManagement && i.severity >= 0.9));
+        assert!(response.indicators.windows(2).all(|w| w[0].timestamp >= w[1].timestamp));
+
+        let bad: IndicatorQuery = serde_json::from_str(r#"{"category": "bogus"}"#).unwrap();
+        assert!(get_indicators(State(state), Query(bad)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_exposes_risk_gauges() {
+        let state = Arc::new(AppState::new());
+        let body = render_metrics(&state).await;
+
+        assert!(body.contains("# TYPE qrms_risk_score gauge"));
+        assert!(body.lines().any(|l| l.starts_with("qrms_risk_score ")));
+        for category in ThreatCategory::all() {
+            let series = format!("qrms_category_risk_score{{category=\"{}\"}}", category_label(*category));
+            assert!(body.contains(&series), "missing {}", series);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_reads_do_not_record_risk_history() {
+        let state = Arc::new(AppState::new());
+        state.qrm.lock().await.simulate_threat_feed(&mut rand::thread_rng());
+        let recorded = state.qrm.lock().await.calculate_risk();
+
+        for _ in 0..3 {
+            render_metrics(&state).await;
+            build_status(&state).await;
+        }
+
+        let qrm = state.qrm.lock().await;
+        assert_eq!(qrm.get_risk_history().len(), 1);
+        assert_eq!(qrm.current_risk().smoothed_score, recorded.smoothed_score);
+    }
+
+    #[tokio::test]
+    async fn test_registered_asset_is_served_with_key_redacted() {
+        let state = Arc::new(AppState::new());
+        let request: RegisterAssetRequest = serde_json::from_value(serde_json::json!({
+            "asset_id": "vault",
+            "asset_type": "OnChainToken",
+            "chain_id": 1,
+            "encryption_key": [1, 2, 3, 4],
+            "access_policy": {
+                "allowed_operations": ["withdraw"],
+                "requires_pqc": true,
+                "requires_tee": true,
+                "risk_threshold": 4000
+            }
+        })).unwrap();
+
+        let (status, _) = register_asset(State(state.clone()), Json(request)).await.unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+
+        let Json(asset) = get_asset(State(state.clone()), Path("vault".to_string())).await.unwrap();
+        let json = serde_json::to_value(&asset).unwrap();
+        assert_eq!(json["encryption_key"], "[REDACTED]");
+        assert_eq!(json["access_policy"]["risk_threshold"], 4000);
+        assert_eq!(list_assets(State(state.clone())).await.0.len(), 1);
+
+        let missing = get_asset(State(state.clone()), Path("other".to_string())).await;
+        assert_eq!(missing.err().unwrap().0, StatusCode::NOT_FOUND);
+
+        let off_chain: RegisterAssetRequest = serde_json::from_value(serde_json::json!({
+            "asset_id": "db",
+            "asset_type": "OffChainDatabase",
+            "chain_id": 1,
+            "access_policy": {"allowed_operations": [], "requires_pqc": false, "requires_tee": false, "risk_threshold": 0}
+        })).unwrap();
+        let rejected = register_asset(State(state), Json(off_chain)).await;
+        assert_eq!(rejected.err().unwrap().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_sequencer_config_changes_batch_size() {
+        let state = Arc::new(AppState::new());
+        let request: SequencerConfigRequest = serde_json::from_str(r#"{"batch_size": 2}"#).unwrap();
+        let Json(config) = update_sequencer_config(State(state.clone()), Json(request)).await.unwrap();
+        assert_eq!(config.batch_size, 2);
+
+        let mut sequencer = state.sequencer.lock().await;
+        for i in 0..5 {
+            let tx = crate::sequencer::Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, 0);
+            sequencer.submit_transaction(tx).unwrap();
+        }
+        assert_eq!(sequencer.decrypt_and_order().len(), 2);
+        let mut apqc = state.apqc.lock().await;
+        let batch = sequencer.create_batch(&mut apqc).await.unwrap().expect("batch");
+        assert_eq!(batch.transactions.len(), 2);
+        drop((sequencer, apqc));
+
+        let request: SequencerConfigRequest = serde_json::from_str(r#"{"batch_size": 0}"#).unwrap();
+        let err = update_sequencer_config(State(state.clone()), Json(request)).await.err().unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        assert_eq!(state.sequencer.lock().await.batch_size, 2);
+    }
+
+    #[tokio::test]
+    async fn test_config_intelligence_mode_reorders_simulation_sequencer() {
+        let state = Arc::new(AppState::new());
+        let submit_varied_risk = |sequencer: &mut crate::sequencer::TeeSequencer| {
+            for (i, risk) in [10u32, 80, 40].into_iter().enumerate() {
+                let mut tx = crate::sequencer::Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, 0);
+                tx.risk_level = risk;
+                sequencer.submit_transaction(tx).unwrap();
+            }
+        };
+        let risks = |txs: Vec<crate::sequencer::Transaction>| txs.iter().map(|tx| tx.risk_level).collect::<Vec<_>>();
+
+        {
+            let mut sequencer = state.sequencer.lock().await;
+            submit_varied_risk(&mut sequencer);
+            assert_eq!(risks(sequencer.decrypt_and_order()), vec![10, 80, 40]);
+        }
+
+        let request: ConfigRequest = serde_json::from_str(r#"{"intelligence_mode": "risk_aware"}"#).unwrap();
+        let Json(config) = update_config(State(state.clone()), Json(request)).await;
+        assert_eq!(config.intelligence_mode, IntelligenceOrdering::RiskAware);
+
+        let mut sequencer = state.sequencer.lock().await;
+        submit_varied_risk(&mut sequencer);
+        assert_eq!(risks(sequencer.decrypt_and_order()), vec![80, 40, 10]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_signature_set_leaves_kems_untouched() {
+        let state = Arc::new(AppState::new());
+        let kems_before = state.apqc.lock().await.active_kems.clone();
+
+        let request: ActiveAlgorithmsRequest = serde_json::from_str(
+            r#"{"kems": ["HQC-256"], "signatures": ["ML-DSA-87"]}"#,
+        ).unwrap();
+        let err = update_active_algorithms(State(state.clone()), Json(request)).await.err().unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+        let apqc = state.apqc.lock().await;
+        assert_eq!(apqc.active_kems, kems_before);
+        assert_eq!(apqc.active_signatures, crate::apqc::DUAL_SIGNATURE_ALGORITHMS.to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_signing_benchmark_runs_off_the_apqc_lock() {
+        let state = Arc::new(AppState::new());
+        let query = Query(serde_json::from_str::<BenchmarkQuery>(r#"{"iterations": 1}"#).unwrap());
+        let Json(bench) = get_signing_benchmark(State(state.clone()), query).await.unwrap();
+        assert_eq!(bench.algorithms.len(), 2);
+        assert!(state.apqc.try_lock().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_kem_benchmark_runs_off_the_apqc_lock() {
+        let state = Arc::new(AppState::new());
+        let query = Query(serde_json::from_str::<BenchmarkQuery>(r#"{"iterations": 2}"#).unwrap());
+        let Json(bench) = get_kem_benchmark(State(state.clone()), query).await.unwrap();
+        assert_eq!(bench.iterations, 2);
+        assert_eq!(bench.combiners[0].combined_secret_size, 32);
+        assert!(state.apqc.try_lock().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_pubkeys_serves_full_mldsa_key() {
+        let state = Arc::new(AppState::new());
+        state.apqc.lock().await.schedule_rotation(12);
+
+        let Json(response) = get_public_keys(State(state.clone())).await;
+        let mldsa = response.keys.iter().find(|k| k.algorithm == "ML-DSA-87").unwrap();
+
+        assert_eq!(hex::decode(&mldsa.public_key).unwrap().len(), MldsaKeyPair::public_key_size());
+        assert_eq!(mldsa.size, MldsaKeyPair::public_key_size());
+        assert!(response.rotation.pending);
+        assert_eq!(response.rotation.effective_block, Some(12));
+    }
+
+    #[tokio::test]
+    async fn test_signed_event_detects_tampering() {
+        let state = Arc::new(AppState::new());
+        let json = serde_json::to_string(&Event::RotationScheduled { effective_block: 42 }).unwrap();
+        let signature = state.apqc.lock().await.sign_mldsa(json.as_bytes()).await.unwrap();
+        let signed = append_event_signature(&json, &signature);
+
+        let (mldsa_pk, _, _) = state.apqc.lock().await.get_public_keys().await;
+        let public_key = MldsaKeyPair::from_public_key(&mldsa_pk).unwrap().public_key;
+
+        assert!(verify_event_signature(&signed, &public_key));
+        // Clients that ignore the field still parse the event
+        assert!(matches!(serde_json::from_str(&signed), Ok(Event::RotationScheduled { effective_block: 42 })));
+
+        let tampered = signed.replace("42", "99");
+        assert!(!verify_event_signature(&tampered, &public_key));
+        assert!(!verify_event_signature(&json, &public_key));
+    }
+
+    #[tokio::test]
+    async fn test_qvm_assess_returns_shor_assessments() {
+        let state = Arc::new(AppState::new());
+        let mut rx = state.subscribe();
+
+        let Json(assessment) = run_qvm_assessment(State(state.clone())).await.unwrap();
+
+        assert!(!assessment.shor_assessments.is_empty());
+        assert!(matches!(rx.try_recv(), Ok(Event::QvmAssessment { .. })));
+        assert_eq!(state.qvm.try_lock().unwrap().assessments_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_qvm_assessment_has_binary_frame() {
+        let state = Arc::new(AppState::new());
+        let mut rx = state.subscribe();
+        let Json(assessment) = run_qvm_assessment(State(state.clone())).await.unwrap();
+        let event = rx.try_recv().unwrap();
+
+        let frame = event.to_binary().expect("assessments have a binary form");
+        let crate::binary_event::BinaryEvent::QvmAssessment { grover_threats, shor_threats, composite_risk } =
+            crate::binary_event::BinaryEvent::from_bincode(&frame).unwrap()
+        else {
+            panic!("expected an assessment frame");
+        };
+        assert_eq!(composite_risk, assessment.composite_risk);
+        assert_eq!(grover_threats.len(), assessment.grover_assessments.len());
+        let shor_names: Vec<_> = shor_threats.iter().map(|t| t.target_algorithm.clone()).collect();
+        let expected: Vec<_> = assessment.shor_assessments.iter().map(|t| t.target_algorithm.clone()).collect();
+        assert_eq!(shor_names, expected);
+        assert_eq!(
+            serde_json::to_value(&shor_threats[0].threat_level).unwrap(),
+            serde_json::to_value(assessment.shor_assessments[0].threat_level).unwrap()
+        );
+    }
+
+    fn client() -> ConnectInfo<SocketAddr> {
+        ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 40000)))
+    }
+
+    #[tokio::test]
+    async fn test_readiness_waits_for_simulation_task() {
+        assert_eq!(get_healthz().await, StatusCode::OK);
+
+        let state = Arc::new(AppState::new());
+        let (status, Json(body)) = get_readyz(State(state.clone())).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!body.simulation_started);
+
+        tokio::spawn(crate::state::run_simulation(state.clone()));
+        for _ in 0..50 {
+            if state.readiness.is_ready() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let (status, Json(body)) = get_readyz(State(state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.ready);
+    }
+
+    #[tokio::test]
+    async fn test_injection_rate_limited_per_client() {
+        let state = Arc::new(AppState::new());
+        *state.inject_limiter.lock().await = crate::rate_limit::RateLimiter::per_minute(2);
+
+        for _ in 0..2 {
+            assert!(inject_high_threat(State(state.clone()), client()).await.is_ok());
+        }
+        let limited = inject_high_threat(State(state.clone()), client()).await;
+        assert_eq!(limited.err().map(|(status, _)| status), Some(StatusCode::TOO_MANY_REQUESTS));
+
+        let request: InjectThreatRequest = serde_json::from_str(r#"{"category": "digital_signatures"}"#).unwrap();
+        let result = inject_threat(State(state.clone()), client(), Json(request)).await;
+        assert_eq!(result.err().map(|(status, _)| status), Some(StatusCode::TOO_MANY_REQUESTS));
+
+        let other = ConnectInfo(SocketAddr::from(([127, 0, 0, 2], 40000)));
+        assert!(inject_high_threat(State(state), other).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_inject_threat_rejects_unknown_category() {
+        let state = Arc::new(AppState::new());
+        let request: InjectThreatRequest = serde_json::from_str(r#"{"category": "mev_ordring"}"#).unwrap();
+
+        let result = inject_threat(State(state.clone()), client(), Json(request)).await;
+
+        assert_eq!(result.err().map(|(status, _)| status), Some(StatusCode::BAD_REQUEST));
+        assert_eq!(state.qrm.lock().await.indicator_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_hndl_lists_registered_assets() {
+        let state = Arc::new(AppState::new());
+
+        let Json(response) = get_hndl_exposure(State(state.clone())).await;
+
+        assert_eq!(response.assets.len(), state.hndl.lock().await.assets().len());
+        let rsa = response.assets.iter().find(|e| e.asset.algorithm == "RSA-2048").unwrap();
+        assert!(rsa.estimated_decryption_year.is_some());
+        let kem = response.assets.iter().find(|e| e.asset.algorithm == "ML-KEM-1024").unwrap();
+        assert!(kem.years_of_exposure_remaining.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_qvm_circuit_runs_bell_state() {
+        let state = Arc::new(AppState::new());
+        let request = CircuitRequest {
+            circuit: crate::qvm::build_bell_state_circuit(),
+            repetitions: Some(500),
+        };
+
+        let Json(result) = run_qvm_circuit(State(state), Json(request)).await.unwrap();
+
+        // Readout noise may leak a few shots into |01⟩/|10⟩, but |00⟩/|11⟩ dominate
+        let total: usize = result.histogram.values().sum();
+        let correlated: usize = [0, 3].iter().filter_map(|k| result.histogram.get(k)).sum();
+        assert!(result.histogram.keys().all(|k| *k < 4));
+        assert!(correlated as f64 > 0.9 * total as f64, "{:?}", result.histogram);
+    }
+
+    #[tokio::test]
+    async fn test_qvm_circuit_rejects_out_of_range_gate() {
+        let state = Arc::new(AppState::new());
+        let mut circuit = crate::qvm::build_bell_state_circuit();
+        circuit.gates.push(vec![crate::qvm::QuantumGate::X(5)]);
+
+        let result = run_qvm_circuit(State(state), Json(CircuitRequest { circuit, repetitions: None })).await;
+
+        assert_eq!(result.err().map(|(status, _)| status), Some(StatusCode::BAD_REQUEST));
+    }
+
+    /// Serve `/ws` on an ephemeral port and return its address
+    async fn serve_websocket(state: Arc<AppState>) -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(websocket_handler))
+            .with_state(state.clone());
+        crate::state::spawn_event_encoder(state);
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_websocket_subscribe_filters_events() {
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let state = Arc::new(AppState::new());
+        let addr = serve_websocket(state.clone()).await;
+
+        let (mut ws, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        async fn next_type<S, E>(ws: &mut S) -> String
+        where
+            S: futures::Stream<Item = Result<WsMessage, E>> + Unpin,
+        {
+            loop {
+                if let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    return value["type"].as_str().unwrap().to_string();
+                }
+            }
+        }
+        assert_eq!(next_type(&mut ws).await, "status");
+
+        ws.send(WsMessage::Text(r#"{"command":"subscribe","events":["qrm_update"]}"#.to_string())).await.unwrap();
+        assert_eq!(next_type(&mut ws).await, "subscribed");
+
+        let (indicator, risk) = {
+            let mut qrm = state.qrm.lock().await;
+            (qrm.simulate_threat_feed(&mut rand::thread_rng()), qrm.calculate_risk())
+        };
+        let tx = crate::sequencer::Transaction::new("0x01".to_string(), "transfer(1)".to_string(), 1, 0);
+        state.broadcast(Event::TxSubmitted(tx));
+        state.broadcast(Event::QrmUpdate { indicator, risk });
+
+        assert_eq!(next_type(&mut ws).await, "qrm_update");
+    }
+
+    #[tokio::test]
+    async fn test_batch_detail_by_id() {
+        let state = Arc::new(AppState::new());
+        let created = {
+            let mut sequencer = state.sequencer.lock().await;
+            let mut apqc = state.apqc.lock().await;
+            for i in 0..3 {
+                let tx = crate::sequencer::Transaction::new(format!("0x0{}", i), format!("transfer({})", i), i, 0);
+                sequencer.submit_transaction(tx).unwrap();
+            }
+            sequencer.decrypt_and_order();
+            sequencer.create_batch(&mut apqc).await.unwrap().expect("batch")
+        };
+
+        let Json(fetched) = get_batch(State(state.clone()), Path(created.batch_id.clone())).await.unwrap();
+        let ids = |batch: &crate::sequencer::Batch| batch.transactions.iter().map(|tx| tx.tx_id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&fetched), ids(&created));
+        assert_eq!(fetched.transactions.len(), 3);
+        assert!(!fetched.ml_dsa_sig.is_empty());
+
+        let missing = get_batch(State(state), Path("unknown".to_string())).await;
+        assert_eq!(missing.err().map(|(status, _)| status), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn test_binary_client_receives_decodable_batch() {
+        use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+
+        let state = Arc::new(AppState::new());
+        let addr = serve_websocket(state.clone()).await;
+        let (mut ws, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+
+        ws.send(WsMessage::Text(r#"{"command":"binary"}"#.to_string())).await.unwrap();
+        loop {
+            if let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if value["type"] == "binary" {
+                    assert_eq!(value["data"], true);
+                    break;
+                }
+            }
+        }
+
+        let batch = {
+            let mut sequencer = state.sequencer.lock().await;
+            let mut apqc = state.apqc.lock().await;
+            for i in 0..3 {
+                let tx = crate::sequencer::Transaction::new(format!("0x0{}", i), "transfer(1)".to_string(), i, 0);
+                sequencer.submit_transaction(tx).unwrap();
+            }
+            sequencer.decrypt_and_order();
+            sequencer.create_batch(&mut apqc).await.unwrap().expect("batch")
+        };
+        let risk = state.qrm.lock().await.calculate_risk();
+        let block = state.chain.lock().await.commit_batch(&batch, &risk);
+        let event = Event::BatchCreated { batch, block };
+        state.broadcast(event.clone());
+
+        let frame = loop {
+            if let Some(Ok(WsMessage::Binary(frame))) = ws.next().await {
+                break frame;
+            }
+        };
+        let decoded = crate::binary_event::BinaryEvent::from_bincode(&frame).unwrap();
+        let crate::binary_event::BinaryEvent::BatchCreated { batch: batch_frame, block: block_frame } = decoded else {
+            panic!("expected a batch frame");
+        };
+        let Event::BatchCreated { batch, block } = &event else { unreachable!() };
+        // Frames carry the JSON event's fields, with the absent beacon encoded as null
+        let mut expected_batch = serde_json::to_value(batch).unwrap();
+        expected_batch["beacon"] = serde_json::Value::Null;
+        assert_eq!(serde_json::to_value(&batch_frame).unwrap(), expected_batch);
+        assert_eq!(serde_json::to_value(&block_frame).unwrap(), serde_json::to_value(block).unwrap());
+        assert!(frame.len() < serde_json::to_vec(&event).unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn test_fidelity_curve_rejects_processor_without_qubits() {
+        let state = Arc::new(AppState::new());
+        let query = FidelityCurveQuery {
+            qubits: None,
+            max_depth: None,
+            processor: Some(QuantumProcessor::Custom { qubits: 0, connectivity: crate::qvm::ConnectivityType::Linear }),
+        };
+
+        let (status, _) = get_fidelity_curve(State(state), Query(query)).await.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 }
@@ -0,0 +1,177 @@
+//! Harvest-Now-Decrypt-Later Exposure Estimation
+//! Turns the QVM oracle's Shor/Grover resource estimates into the year an
+//! adversary holding today's ciphertext could decrypt it, and how many years
+//! of protection an encrypted asset has left.
+//!
+//! Hardware is extrapolated from the oracle's processor: its physical qubit
+//! count today, doubling every `qubit_doubling_years`. An asset becomes
+//! decryptable once that curve reaches the attack's physical qubit estimate.
+
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::qvm::QvmOracle;
+
+/// Years for physical qubit counts to double
+pub const DEFAULT_QUBIT_DOUBLING_YEARS: f64 = 1.5;
+
+/// Grover attacks needing longer than this are treated as never feasible
+const MAX_GROVER_RUNTIME_YEARS: f64 = 1.0;
+
+/// Symmetric primitives, attacked with Grover rather than Shor
+const SYMMETRIC_FAMILIES: &[&str] = &["AES", "CHACHA", "SHA", "KECCAK"];
+
+/// Ciphertext an adversary could be harvesting today
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedAsset {
+    pub name: String,
+    /// Algorithm protecting the data, e.g. "RSA-2048", "AES-256", "ML-KEM-1024"
+    pub algorithm: String,
+    pub key_bits: usize,
+    /// How long the data must stay confidential from today
+    pub secrecy_years: f64,
+}
+
+/// Exposure window for one asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HndlExposure {
+    pub asset: EncryptedAsset,
+    /// Physical qubits the attack needs (0 when no quantum attack applies)
+    pub required_physical_qubits: usize,
+    /// Calendar year decryption becomes feasible; None if it never does
+    pub estimated_decryption_year: Option<i32>,
+    /// Years until harvested ciphertext becomes decryptable; None if never
+    pub years_of_exposure_remaining: Option<f64>,
+    /// Decryption becomes feasible while the data still needs secrecy
+    pub at_risk: bool,
+}
+
+/// Registered assets and the hardware growth model used to score them
+#[derive(Debug, Clone)]
+pub struct HndlEstimator {
+    assets: Vec<EncryptedAsset>,
+    pub qubit_doubling_years: f64,
+}
+
+impl HndlEstimator {
+    pub fn new() -> Self {
+        Self {
+            assets: Vec::new(),
+            qubit_doubling_years: DEFAULT_QUBIT_DOUBLING_YEARS,
+        }
+    }
+
+    /// Estimator seeded with the protocol's own long-lived ciphertext
+    pub fn with_default_assets() -> Self {
+        let mut estimator = Self::new();
+        for (name, algorithm, key_bits, secrecy_years) in [
+            ("Validator TLS session archive", "RSA-2048", 2048, 10.0),
+            ("Cold-storage key backups", "RSA-4096", 4096, 25.0),
+            ("P2P handshake transcripts", "ECDH-P256", 256, 5.0),
+            ("Encrypted mempool payloads", "AES-256", 256, 1.0),
+            ("Batch payload envelopes", "ML-KEM-1024", 1024, 25.0),
+        ] {
+            estimator.register(EncryptedAsset {
+                name: name.to_string(),
+                algorithm: algorithm.to_string(),
+                key_bits,
+                secrecy_years,
+            });
+        }
+        estimator
+    }
+
+    /// Track an asset; an existing asset with the same name is replaced
+    pub fn register(&mut self, asset: EncryptedAsset) {
+        self.assets.retain(|a| a.name != asset.name);
+        self.assets.push(asset);
+    }
+
+    pub fn assets(&self) -> &[EncryptedAsset] {
+        &self.assets
+    }
+
+    /// Exposure window for every registered asset, in registration order
+    pub fn assess_all(&self, oracle: &QvmOracle) -> Vec<HndlExposure> {
+        self.assets.iter().map(|asset| self.assess(asset, oracle)).collect()
+    }
+
+    /// Exposure window for one asset against the oracle's current hardware
+    pub fn assess(&self, asset: &EncryptedAsset, oracle: &QvmOracle) -> HndlExposure {
+        let required = required_physical_qubits(asset, oracle);
+        let years = required.map(|qubits| {
+            let available = oracle.simulator().processor().qubit_count().max(1) as f64;
+            let doublings = (qubits as f64 / available).log2().max(0.0);
+            doublings * self.qubit_doubling_years
+        });
+
+        HndlExposure {
+            asset: asset.clone(),
+            required_physical_qubits: required.unwrap_or(0),
+            estimated_decryption_year: years.map(|y| Utc::now().year() + y.ceil() as i32),
+            years_of_exposure_remaining: years,
+            at_risk: years.is_some_and(|y| y < asset.secrecy_years),
+        }
+    }
+}
+
+impl Default for HndlEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Physical qubits needed to break the asset, or None if no feasible attack exists
+fn required_physical_qubits(asset: &EncryptedAsset, oracle: &QvmOracle) -> Option<usize> {
+    let upper = asset.algorithm.to_ascii_uppercase();
+    if SYMMETRIC_FAMILIES.iter().any(|family| upper.contains(family)) {
+        let grover = oracle.assess_grover_threat(&asset.algorithm, asset.key_bits);
+        return (grover.estimated_time_years <= MAX_GROVER_RUNTIME_YEARS)
+            .then_some(grover.required_physical_qubits);
+    }
+
+    let shor = oracle.assess_shor_threat(&asset.algorithm, asset.key_bits);
+    shor.note.is_none().then_some(shor.required_physical_qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qvm::QuantumProcessor;
+
+    fn asset(name: &str, algorithm: &str, key_bits: usize) -> EncryptedAsset {
+        EncryptedAsset {
+            name: name.to_string(),
+            algorithm: algorithm.to_string(),
+            key_bits,
+            secrecy_years: 20.0,
+        }
+    }
+
+    #[test]
+    fn test_rsa2048_exposed_sooner_than_rsa4096() {
+        let oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        let mut estimator = HndlEstimator::new();
+        estimator.register(asset("short", "RSA-2048", 2048));
+        estimator.register(asset("long", "RSA-4096", 4096));
+
+        let exposures = estimator.assess_all(&oracle);
+        let rsa2048 = exposures[0].years_of_exposure_remaining.unwrap();
+        let rsa4096 = exposures[1].years_of_exposure_remaining.unwrap();
+
+        assert!(rsa2048 < rsa4096, "{} vs {}", rsa2048, rsa4096);
+        assert!(exposures[0].estimated_decryption_year <= exposures[1].estimated_decryption_year);
+    }
+
+    #[test]
+    fn test_pqc_and_strong_symmetric_never_decryptable() {
+        let oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        let estimator = HndlEstimator::new();
+
+        for protected in [asset("kem", "ML-KEM-1024", 1024), asset("aes", "AES-256", 256)] {
+            let exposure = estimator.assess(&protected, &oracle);
+            assert!(exposure.estimated_decryption_year.is_none(), "{:?}", exposure);
+            assert!(!exposure.at_risk);
+        }
+    }
+}
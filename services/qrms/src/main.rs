@@ -54,6 +54,9 @@ mod qrm;
 mod apqc;
 mod crypto;
 mod sequencer;
+mod event_signature;
+mod binary_event;
+mod ordering;
 mod aegis_tee;
 #[allow(deprecated)]
 mod phala_tee; // Deprecated: kept for backward compatibility, use aegis_tee instead
@@ -61,7 +64,12 @@ mod phala_deploy;
 mod chain;
 mod state;
 mod handlers;
+mod grpc;
+mod tuning;
+mod hndl;
+mod rate_limit;
 
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 use axum::{
     routing::{get, post},
@@ -71,10 +79,14 @@ use tower_http::{
     cors::{Any, CorsLayer},
     services::ServeDir,
 };
+use futures::FutureExt;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::state::AppState;
 
+/// gRPC listen address when `QRMS_GRPC_ADDR` is unset or invalid
+const DEFAULT_GRPC_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 50051);
+
 #[tokio::main]
 async fn main() {
     // Initialize logging
@@ -88,7 +100,39 @@ async fn main() {
     tracing::info!("Starting QRMS - Quantum Resistance Model System");
 
     // Create shared state
-    let state = Arc::new(AppState::new());
+    let mut app_state = AppState::new();
+
+    // Restore persisted QRM state, if configured
+    if let Ok(path) = std::env::var("QRMS_STATE_FILE") {
+        let path = std::path::PathBuf::from(path);
+        if path.exists() {
+            match qrm::QuantumResistanceMonitor::load_from_file(&path) {
+                Ok(monitor) => {
+                    tracing::info!("Loaded {} indicators from {}", monitor.indicator_count(), path.display());
+                    *app_state.qrm.get_mut() = monitor;
+                }
+                Err(e) => tracing::warn!("Failed to load QRM state from {}: {}", path.display(), e),
+            }
+        }
+        app_state.state_file = Some(path);
+    }
+
+    // Reproducible simulation runs
+    if let Some(seed) = std::env::var("QRMS_SEED").ok().and_then(|s| s.parse::<u64>().ok()) {
+        app_state.seed_simulation(seed);
+        tracing::info!("Simulation seeded with {}", seed);
+    }
+
+    // Sign WebSocket events so clients can detect tampering
+    app_state.sign_events = std::env::var("QRMS_SIGN_EVENTS").is_ok_and(|v| v == "1" || v == "true");
+    if app_state.sign_events {
+        tracing::info!("Signing WebSocket events with ML-DSA-87");
+    }
+
+    let state = Arc::new(app_state);
+
+    // Encode and sign each event once for all WebSocket clients
+    state::spawn_event_encoder(state.clone());
 
     // Start background simulation task
     let sim_state = state.clone();
@@ -96,15 +140,81 @@ async fn main() {
         state::run_simulation(sim_state).await;
     });
 
+    // Hot-reload scoring tuning from file, if configured
+    let _tuning_watcher = match std::env::var("QRMS_TUNING_FILE") {
+        Ok(path) => match tuning::watch(state.clone(), path.clone().into()).await {
+            Ok(watcher) => {
+                tracing::info!("Watching tuning file {}", path);
+                Some(watcher)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to watch tuning file {}: {}", path, e);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+
+    // One Ctrl-C stops both the gRPC and HTTP servers
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    }
+    .shared();
+
+    // Start gRPC server alongside the HTTP server
+    let grpc_addr = match std::env::var("QRMS_GRPC_ADDR") {
+        Ok(addr) => addr.parse().unwrap_or_else(|e| {
+            tracing::warn!("Invalid QRMS_GRPC_ADDR {}: {}; using {}", addr, e, DEFAULT_GRPC_ADDR);
+            DEFAULT_GRPC_ADDR
+        }),
+        Err(_) => DEFAULT_GRPC_ADDR,
+    };
+    let grpc_state = state.clone();
+    let grpc_shutdown = ctrl_c.clone();
+    tokio::spawn(async move {
+        tracing::info!("gRPC server running at {}", grpc_addr);
+        if let Err(e) = grpc::serve(grpc_state, grpc_addr, grpc_shutdown).await {
+            tracing::error!("gRPC server error: {}", e);
+        }
+    });
+
     // Build router
     let app = Router::new()
         // API routes
+        // Orchestration probes
+        .route("/healthz", get(handlers::get_healthz))
+        .route("/readyz", get(handlers::get_readyz))
         .route("/api/status", get(handlers::get_status))
+        .route("/api/pubkeys", get(handlers::get_public_keys))
+        .route("/metrics", get(handlers::get_metrics))
         .route("/api/qrm/history", get(handlers::get_qrm_history))
+        .route("/api/qrm/indicators", get(handlers::get_indicators))
+        .route("/api/qrm/category_history", get(handlers::get_category_history))
+        .route("/api/qrm/risk", get(handlers::get_filtered_risk))
         .route("/api/blocks", get(handlers::get_blocks))
+        .route("/api/batch/:batch_id", get(handlers::get_batch))
+        .route("/api/hndl", get(handlers::get_hndl_exposure))
+        .route("/api/apqc/kem/benchmark", get(handlers::get_kem_benchmark))
+        .route("/api/apqc/benchmark", get(handlers::get_signing_benchmark))
+        .route("/api/apqc/algorithms", post(handlers::update_active_algorithms))
+        .route("/api/apqc/compromise", post(handlers::disclose_compromise))
+        .route("/api/qvm/selftest", get(handlers::get_qvm_selftest))
+        .route("/api/qvm/assess", post(handlers::run_qvm_assessment))
+        .route("/api/qvm/circuit", post(handlers::run_qvm_circuit))
+        .route("/api/qvm/history", get(handlers::get_qvm_history))
+        .route("/api/qvm/diff", get(handlers::get_qvm_diff))
+        .route("/api/qvm/fidelity_curve", get(handlers::get_fidelity_curve))
+        .route("/api/config", post(handlers::update_config))
+        .route("/api/sequencer/config", post(handlers::update_sequencer_config))
+        .route("/api/assets", get(handlers::list_assets).post(handlers::register_asset))
+        .route("/api/assets/:id", get(handlers::get_asset))
         .route("/api/inject_threat", post(handlers::inject_threat))
         .route("/api/simulation/start", post(handlers::start_simulation))
         .route("/api/simulation/stop", post(handlers::stop_simulation))
+        .route("/api/simulation/config", post(handlers::update_simulation_config))
         .route("/api/inject_high_threat", post(handlers::inject_high_threat))
         // WebSocket for real-time updates
         .route("/ws", get(handlers::websocket_handler))
@@ -113,11 +223,14 @@ async fn main() {
         // CORS
         .layer(CorsLayer::new().allow_origin(Any).allow_methods(Any))
         // State
-        .with_state(state);
+        .with_state(state.clone());
 
     let addr = "0.0.0.0:5050";
     tracing::info!("Server running at http://{}", addr);
     
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(state::shutdown_on(state, ctrl_c))
+        .await
+        .unwrap();
 }
@@ -0,0 +1,126 @@
+//! Intelligence-based transaction ordering
+//! Shared by the Aegis-TEE and (deprecated) Phala TEE sequencers
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::aegis_tee::{AssetProtection, DecryptedTransaction, IntelligenceOrdering};
+
+/// A decrypted transaction awaiting ordering, with its risk level and asset refs
+pub type OrderingCandidate = (DecryptedTransaction, u32, Vec<String>);
+
+/// Sequencer state the ordering strategies depend on
+#[derive(Debug, Clone, Copy)]
+pub struct OrderingContext<'a> {
+    pub asset_registry: &'a HashMap<String, AssetProtection>,
+    pub migration_in_progress: bool,
+}
+
+/// Order candidates with the given strategy
+pub fn order(
+    mode: IntelligenceOrdering,
+    txs: Vec<OrderingCandidate>,
+    ctx: OrderingContext<'_>,
+) -> Vec<DecryptedTransaction> {
+    match mode {
+        IntelligenceOrdering::RiskAware => order_by_risk(txs),
+        IntelligenceOrdering::AssetProtection => order_by_asset_protection(txs, ctx),
+        IntelligenceOrdering::MigrationAware => order_by_migration(txs, ctx),
+        IntelligenceOrdering::Hybrid => order_hybrid(txs, ctx),
+    }
+}
+
+/// Order by risk level (high risk first for faster protection)
+pub fn order_by_risk(mut txs: Vec<OrderingCandidate>) -> Vec<DecryptedTransaction> {
+    txs.sort_by(|a, b| b.1.cmp(&a.1)); // Descending risk
+    txs.into_iter().map(|(tx, _, _)| tx).collect()
+}
+
+/// Order by asset protection priority
+pub fn order_by_asset_protection(
+    mut txs: Vec<OrderingCandidate>,
+    ctx: OrderingContext<'_>,
+) -> Vec<DecryptedTransaction> {
+    txs.sort_by(|a, b| {
+        let a_protected = a.2.iter()
+            .any(|asset_id| ctx.asset_registry.contains_key(asset_id));
+        let b_protected = b.2.iter()
+            .any(|asset_id| ctx.asset_registry.contains_key(asset_id));
+
+        match (a_protected, b_protected) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => a.1.cmp(&b.1), // Fallback to risk
+        }
+    });
+    txs.into_iter().map(|(tx, _, _)| tx).collect()
+}
+
+/// Order by migration requirements
+pub fn order_by_migration(
+    mut txs: Vec<OrderingCandidate>,
+    ctx: OrderingContext<'_>,
+) -> Vec<DecryptedTransaction> {
+    if ctx.migration_in_progress {
+        // Group migration-related transactions
+        txs.sort_by(|a, b| {
+            let a_migration = a.0.tx_id.contains("migration");
+            let b_migration = b.0.tx_id.contains("migration");
+
+            match (a_migration, b_migration) {
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                _ => a.1.cmp(&b.1),
+            }
+        });
+    } else {
+        txs.sort_by(|a, b| b.1.cmp(&a.1));
+    }
+    txs.into_iter().map(|(tx, _, _)| tx).collect()
+}
+
+/// Hybrid ordering: combines risk, asset protection, and migration
+pub fn order_hybrid(
+    mut txs: Vec<OrderingCandidate>,
+    ctx: OrderingContext<'_>,
+) -> Vec<DecryptedTransaction> {
+    // Score each transaction
+    txs.sort_by(|a, b| {
+        let a_score = priority_score(&a.0, a.1, &a.2, ctx);
+        let b_score = priority_score(&b.0, b.1, &b.2, ctx);
+        b_score.cmp(&a_score)
+    });
+    txs.into_iter().map(|(tx, _, _)| tx).collect()
+}
+
+/// Calculate priority score for hybrid ordering
+pub fn priority_score(
+    tx: &DecryptedTransaction,
+    risk: u32,
+    asset_refs: &[String],
+    ctx: OrderingContext<'_>,
+) -> u64 {
+    let mut score = risk as u64 * 100;
+
+    // Asset protection bonus
+    for asset_id in asset_refs {
+        if let Some(asset) = ctx.asset_registry.get(asset_id) {
+            if asset.access_policy.requires_pqc {
+                score += 1000;
+            }
+            if asset.access_policy.requires_tee {
+                score += 2000;
+            }
+        }
+    }
+
+    // Migration bonus
+    if ctx.migration_in_progress && tx.tx_id.contains("migration") {
+        score += 5000;
+    }
+
+    // Priority fee bonus
+    score += tx.priority_fee;
+
+    score
+}
@@ -27,7 +27,16 @@ use chrono::{DateTime, Utc};
 use std::collections::{VecDeque, HashMap};
 
 use crate::apqc::AdaptivePqcLayer;
+use crate::crypto::CryptoError;
+use crate::ordering::{self, OrderingCandidate, OrderingContext};
 use crate::qrm::{QuantumResistanceMonitor, RiskAssessment};
+use crate::sequencer::{Sequencer, SubmitError, DEFAULT_MAX_MEMPOOL};
+
+// Asset, migration, and transaction types are shared with Aegis-TEE
+pub use crate::aegis_tee::{
+    AssetProtection, AssetSnapshot, DecryptedTransaction, EncryptedTransaction,
+    IntelligenceOrdering, MigrationCheckpoint,
+};
 
 /// Phala TEE attestation (TDX/SEV)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,76 +52,6 @@ pub struct PhalaAttestation {
     pub phala_verification: bool,     // Verified by Phala network
 }
 
-/// Asset protection metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssetProtection {
-    pub asset_id: String,
-    pub asset_type: AssetType,
-    pub chain_id: Option<u64>,        // None for off-chain
-    pub contract_address: Option<String>,
-    pub encryption_key: Vec<u8>,      // Encrypted with TEE key
-    pub access_policy: AccessPolicy,
-    pub migration_state: MigrationState,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub enum AssetType {
-    OnChainToken,
-    OnChainNFT,
-    OnChainData,
-    OffChainDatabase,
-    OffChainFile,
-    OffChainStream,
-    CrossChainBridge,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AccessPolicy {
-    pub allowed_operations: Vec<String>,
-    pub requires_pqc: bool,
-    pub requires_tee: bool,
-    pub risk_threshold: u32,           // Minimum risk score to trigger protection
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub enum MigrationState {
-    Active,                            // Currently active
-    Preparing,                         // Preparing for migration
-    Migrating,                         // Migration in progress
-    Migrated,                          // Successfully migrated
-    Rollback,                          // Rolled back to previous state
-}
-
-/// Encrypted transaction with asset context
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EncryptedTransaction {
-    pub tx_id: String,
-    pub encrypted_data: Vec<u8>,       // Encrypted with TEE key
-    pub asset_refs: Vec<String>,       // Referenced asset IDs
-    pub priority_fee: u64,
-    pub timestamp: DateTime<Utc>,
-    pub risk_level: u32,               // Current QRM risk score
-    pub requires_migration: bool,      // Flag for migration-aware ordering
-}
-
-/// Migration checkpoint for state preservation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MigrationCheckpoint {
-    pub checkpoint_id: String,
-    pub block_number: u64,
-    pub state_hash: String,
-    pub asset_snapshots: Vec<AssetSnapshot>,
-    pub timestamp: DateTime<Utc>,
-    pub pqc_signature: String,         // ML-DSA signature
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AssetSnapshot {
-    pub asset_id: String,
-    pub state: Vec<u8>,                // Encrypted state
-    pub metadata: HashMap<String, String>,
-}
-
 /// Quantum-resistant batch with intelligence
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuantumResistantBatch {
@@ -128,29 +67,11 @@ pub struct QuantumResistantBatch {
     pub timestamp: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DecryptedTransaction {
-    pub tx_id: String,
-    pub sender: String,
-    pub data: String,
-    pub asset_refs: Vec<String>,
-    pub priority_fee: u64,
-    pub timestamp: DateTime<Utc>,
-}
-
-/// Intelligence-based ordering strategy
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum IntelligenceOrdering {
-    RiskAware,                         // Order by risk level (high risk first)
-    AssetProtection,                   // Prioritize protected assets
-    MigrationAware,                    // Group migration-related txs
-    Hybrid,                            // Combine multiple strategies
-}
-
 /// Phala TEE Sequencer
 pub struct PhalaTeeSequencer {
     // Encrypted mempool (only decrypted inside TEE)
     encrypted_mempool: VecDeque<EncryptedTransaction>,
+    max_mempool: usize,
     
     // Asset registry
     asset_registry: HashMap<String, AssetProtection>,
@@ -179,6 +100,7 @@ impl PhalaTeeSequencer {
     pub fn new(worker_id: String, enclave_id: String, tee_platform: String) -> Self {
         Self {
             encrypted_mempool: VecDeque::with_capacity(10000),
+            max_mempool: DEFAULT_MAX_MEMPOOL,
             asset_registry: HashMap::new(),
             migration_state: None,
             migration_in_progress: false,
@@ -199,8 +121,23 @@ impl PhalaTeeSequencer {
     }
 
     /// Submit encrypted transaction (from outside TEE)
-    pub fn submit_encrypted(&mut self, encrypted_tx: EncryptedTransaction) {
+    /// Refused once the mempool holds `max_mempool` entries
+    pub fn submit_encrypted(&mut self, encrypted_tx: EncryptedTransaction) -> Result<(), SubmitError> {
+        if self.encrypted_mempool.len() >= self.max_mempool {
+            return Err(SubmitError::MempoolFull { capacity: self.max_mempool });
+        }
         self.encrypted_mempool.push_back(encrypted_tx);
+        Ok(())
+    }
+
+    /// Set mempool capacity (minimum 1)
+    pub fn set_max_mempool(&mut self, max_mempool: usize) {
+        self.max_mempool = max_mempool.max(1);
+    }
+
+    /// Mempool capacity
+    pub fn mempool_capacity(&self) -> usize {
+        self.max_mempool
     }
 
     /// Decrypt and order transactions (inside TEE only)
@@ -214,7 +151,7 @@ impl PhalaTeeSequencer {
         }
 
         // Decrypt transactions (simulated - real implementation uses TEE key)
-        let mut decrypted: Vec<(DecryptedTransaction, u32, Vec<String>)> = Vec::new();
+        let mut decrypted: Vec<OrderingCandidate> = Vec::new();
         
         for enc_tx in self.encrypted_mempool.iter() {
             // In real TEE: decrypt with tee_key
@@ -239,128 +176,21 @@ impl PhalaTeeSequencer {
         self.encrypted_mempool.clear();
 
         // Intelligence-based ordering
-        let ordered = match self.intelligence_mode {
-            IntelligenceOrdering::RiskAware => {
-                self.order_by_risk(decrypted)
-            }
-            IntelligenceOrdering::AssetProtection => {
-                self.order_by_asset_protection(decrypted)
-            }
-            IntelligenceOrdering::MigrationAware => {
-                self.order_by_migration(decrypted)
-            }
-            IntelligenceOrdering::Hybrid => {
-                self.order_hybrid(decrypted)
-            }
+        let ctx = OrderingContext {
+            asset_registry: &self.asset_registry,
+            migration_in_progress: self.migration_in_progress,
         };
+        let ordered = ordering::order(self.intelligence_mode, decrypted, ctx);
 
         ordered.into_iter().take(self.batch_size).collect()
     }
 
-    /// Order by risk level (high risk first for faster protection)
-    fn order_by_risk(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        txs.sort_by(|a, b| b.1.cmp(&a.1)); // Descending risk
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Order by asset protection priority
-    fn order_by_asset_protection(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        txs.sort_by(|a, b| {
-            let a_protected = a.2.iter()
-                .any(|asset_id| self.asset_registry.contains_key(asset_id));
-            let b_protected = b.2.iter()
-                .any(|asset_id| self.asset_registry.contains_key(asset_id));
-            
-            match (a_protected, b_protected) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.1.cmp(&b.1), // Fallback to risk
-            }
-        });
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Order by migration requirements
-    fn order_by_migration(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        if self.migration_in_progress {
-            // Group migration-related transactions
-            txs.sort_by(|a, b| {
-                let a_migration = a.0.tx_id.contains("migration");
-                let b_migration = b.0.tx_id.contains("migration");
-                
-                match (a_migration, b_migration) {
-                    (true, false) => std::cmp::Ordering::Less,
-                    (false, true) => std::cmp::Ordering::Greater,
-                    _ => a.1.cmp(&b.1),
-                }
-            });
-        } else {
-            txs.sort_by(|a, b| b.1.cmp(&a.1));
-        }
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Hybrid ordering: combines risk, asset protection, and migration
-    fn order_hybrid(
-        &self,
-        mut txs: Vec<(DecryptedTransaction, u32, Vec<String>)>,
-    ) -> Vec<DecryptedTransaction> {
-        // Score each transaction
-        txs.sort_by(|a, b| {
-            let a_score = self.calculate_priority_score(&a.0, a.1, &a.2);
-            let b_score = self.calculate_priority_score(&b.0, b.1, &b.2);
-            b_score.cmp(&a_score)
-        });
-        txs.into_iter().map(|(tx, _, _)| tx).collect()
-    }
-
-    /// Calculate priority score for hybrid ordering
-    fn calculate_priority_score(
-        &self,
-        tx: &DecryptedTransaction,
-        risk: u32,
-        asset_refs: &[String],
-    ) -> u64 {
-        let mut score = risk as u64 * 100;
-        
-        // Asset protection bonus
-        for asset_id in asset_refs {
-            if let Some(asset) = self.asset_registry.get(asset_id) {
-                if asset.access_policy.requires_pqc {
-                    score += 1000;
-                }
-                if asset.access_policy.requires_tee {
-                    score += 2000;
-                }
-            }
-        }
-        
-        // Migration bonus
-        if self.migration_in_progress && tx.tx_id.contains("migration") {
-            score += 5000;
-        }
-        
-        // Priority fee bonus
-        score += tx.priority_fee as u64;
-        
-        score
-    }
-
     /// Create quantum-resistant batch with intelligence
     pub async fn create_quantum_batch(
         &mut self,
         apqc: &mut AdaptivePqcLayer,
         tee_key: &[u8],
-    ) -> Option<QuantumResistantBatch> {
+    ) -> Result<Option<QuantumResistantBatch>, CryptoError> {
         // Get current risk assessment
         let risk = self.qrm.calculate_risk();
         
@@ -368,7 +198,7 @@ impl PhalaTeeSequencer {
         let ordered_txs = self.decrypt_and_order_intelligent(tee_key);
         
         if ordered_txs.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         // Collect asset protections for this batch
@@ -392,14 +222,14 @@ impl PhalaTeeSequencer {
         let batch_id = hex::encode(&hasher.finalize());
 
         // Sign with dual PQC
-        let signatures = apqc.sign_dual(&batch_data).await;
+        let signatures = apqc.sign_dual(&batch_data).await?;
 
         // Generate Phala attestation
         let attestation = self.generate_phala_attestation(&batch_id);
 
         // Create migration checkpoint if needed
         let checkpoint = if self.migration_in_progress {
-            Some(self.create_migration_checkpoint(&ordered_txs, apqc).await)
+            Some(self.create_migration_checkpoint(&ordered_txs, apqc).await?)
         } else {
             None
         };
@@ -420,7 +250,7 @@ impl PhalaTeeSequencer {
         self.batches.push(batch.clone());
         self.current_block += 1;
 
-        Some(batch)
+        Ok(Some(batch))
     }
 
     /// Generate Phala TEE attestation
@@ -461,7 +291,7 @@ impl PhalaTeeSequencer {
         &self,
         txs: &[DecryptedTransaction],
         apqc: &mut AdaptivePqcLayer,
-    ) -> MigrationCheckpoint {
+    ) -> Result<MigrationCheckpoint, CryptoError> {
         // Snapshot asset states
         let mut snapshots = Vec::new();
         for tx in txs {
@@ -487,16 +317,16 @@ impl PhalaTeeSequencer {
         let state_hash = hex::encode(&hasher.finalize());
 
         // Sign checkpoint with PQC
-        let sig = apqc.sign_dual(&checkpoint_data).await;
+        let sig = apqc.sign_dual(&checkpoint_data).await?;
 
-        MigrationCheckpoint {
+        Ok(MigrationCheckpoint {
             checkpoint_id: format!("checkpoint_{}", self.current_block),
             block_number: self.current_block,
             state_hash,
             asset_snapshots: snapshots,
             timestamp: Utc::now(),
             pqc_signature: sig.ml_dsa.signature,
-        }
+        })
     }
 
     /// Start migration process
@@ -524,6 +354,16 @@ impl PhalaTeeSequencer {
     pub fn get_recent_batches(&self, count: usize) -> Vec<QuantumResistantBatch> {
         self.batches.iter().rev().take(count).cloned().collect()
     }
+
+    /// Get active intelligence ordering mode
+    pub fn intelligence_mode(&self) -> IntelligenceOrdering {
+        self.intelligence_mode
+    }
+
+    /// Switch intelligence ordering mode
+    pub fn set_intelligence_mode(&mut self, mode: IntelligenceOrdering) {
+        self.intelligence_mode = mode;
+    }
 }
 
 impl Default for PhalaTeeSequencer {
@@ -535,3 +375,26 @@ impl Default for PhalaTeeSequencer {
         )
     }
 }
+
+#[async_trait::async_trait]
+impl Sequencer for PhalaTeeSequencer {
+    type Tx = EncryptedTransaction;
+    type Ordered = DecryptedTransaction;
+    type Batch = QuantumResistantBatch;
+
+    fn submit(&mut self, tx: EncryptedTransaction) -> Result<(), SubmitError> {
+        self.submit_encrypted(tx)
+    }
+
+    fn order(&mut self, tee_key: &[u8]) -> Vec<DecryptedTransaction> {
+        self.decrypt_and_order_intelligent(tee_key)
+    }
+
+    async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer, tee_key: &[u8]) -> Result<Option<QuantumResistantBatch>, CryptoError> {
+        self.create_quantum_batch(apqc, tee_key).await
+    }
+
+    fn recent_batches(&self, count: usize) -> Vec<QuantumResistantBatch> {
+        self.get_recent_batches(count)
+    }
+}
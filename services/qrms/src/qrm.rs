@@ -16,9 +16,14 @@
 //! 12. Migration/Agility
 
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
 use chrono::{DateTime, Utc};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::qvm::ThreatLevel;
 
 /// Expanded threat indicator categories (12 total)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -153,8 +158,7 @@ impl ThreatCategory {
     }
 
     /// Random category for simulation (weighted by importance)
-    pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+    pub fn random(rng: &mut impl Rng) -> Self {
         let roll: f64 = rng.gen();
         
         // Weighted random selection
@@ -187,8 +191,19 @@ impl ThreatCategory {
     }
 }
 
+impl std::str::FromStr for ThreatCategory {
+    type Err = String;
+
+    /// Parse the snake_case name used by the API (e.g. "mev_ordering")
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use serde::de::{value::{Error, StrDeserializer}, IntoDeserializer};
+        let deserializer: StrDeserializer<Error> = s.into_deserializer();
+        Self::deserialize(deserializer).map_err(|_| format!("Unknown threat category: {}", s))
+    }
+}
+
 /// Quantum computing era for severity scaling
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum QuantumEra {
     PreQuantum,     // Current: no fault-tolerant QC
@@ -208,6 +223,16 @@ pub struct ThreatIndicator {
     pub description: String,
     pub era_relevance: QuantumEra,  // When this threat becomes critical
     pub references: Vec<String>,    // arXiv, CVE, etc.
+    #[serde(default)]
+    pub tags: Vec<String>,          // Analyst labels, e.g. "production", "theoretical"
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,  // Time-bounded alerts stop scoring after this
+}
+
+impl ThreatIndicator {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expiry| expiry <= now)
+    }
 }
 
 /// Risk recommendation based on score
@@ -225,14 +250,23 @@ pub enum RiskRecommendation {
 pub struct CategoryRisk {
     pub category: ThreatCategory,
     pub score: u32,
+    /// `score` in the QVM oracle's vocabulary
+    #[serde(default)]
+    pub threat_level: ThreatLevel,
     pub indicator_count: usize,
     pub top_threats: Vec<String>,
+    /// Change in `score` since the previous recorded assessment; positive is worsening
+    #[serde(default)]
+    pub delta: i32,
 }
 
 /// Risk assessment result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskAssessment {
     pub score: u32,  // 0-10000 basis points
+    /// EWMA of `score` that drives `recommendation`
+    #[serde(default)]
+    pub smoothed_score: u32,
     pub recommendation: RiskRecommendation,
     pub category_breakdown: Vec<CategoryRisk>,
     pub indicators: Vec<ThreatIndicator>,
@@ -240,6 +274,20 @@ pub struct RiskAssessment {
     pub timestamp: DateTime<Utc>,
 }
 
+/// A single point in a per-category score series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryHistoryPoint {
+    pub timestamp: DateTime<Utc>,
+    pub score: u32,
+}
+
+/// On-disk form of the monitor's accumulated intelligence
+#[derive(Serialize, Deserialize)]
+struct MonitorSnapshot {
+    indicators: Vec<ThreatIndicator>,
+    risk_history: Vec<RiskAssessment>,
+}
+
 /// Quantum Resistance Monitor
 pub struct QuantumResistanceMonitor {
     indicators: VecDeque<ThreatIndicator>,
@@ -247,8 +295,22 @@ pub struct QuantumResistanceMonitor {
     pub threshold_scheduled: u32,
     pub threshold_emergency: u32,
     pub current_era: QuantumEra,
+    weights: HashMap<ThreatCategory, f64>,
+    /// Operator overrides of `ThreatCategory::era_multiplier`
+    era_multipliers: HashMap<(ThreatCategory, QuantumEra), f64>,
     max_indicators: usize,
     max_history: usize,
+    /// Merge indicators repeating (category, sub_category, source) within this window
+    pub dedup_window: Option<Duration>,
+    /// EWMA weight of the newest score, in (0, 1]; 1.0 disables smoothing
+    pub smoothing_alpha: f64,
+    /// Indicators below this severity are ignored when scoring
+    pub severity_floor: f64,
+    /// Indicators below this confidence are ignored when scoring
+    pub confidence_floor: f64,
+    smoothed_score: f64,
+    /// Indicators arrived since the last smoothing step
+    unsmoothed_indicators: bool,
 }
 
 impl QuantumResistanceMonitor {
@@ -259,19 +321,112 @@ impl QuantumResistanceMonitor {
             threshold_scheduled: 6000,
             threshold_emergency: 9000,
             current_era: QuantumEra::PreQuantum,
+            weights: HashMap::new(),
+            era_multipliers: HashMap::new(),
             max_indicators: 200,
             max_history: 500,
+            dedup_window: None,
+            smoothing_alpha: 1.0,
+            severity_floor: 0.0,
+            confidence_floor: 0.0,
+            smoothed_score: 0.0,
+            unsmoothed_indicators: false,
+        }
+    }
+
+    /// Effective weight for a category (override if set, otherwise the default)
+    pub fn weight(&self, category: ThreatCategory) -> f64 {
+        self.weights.get(&category).copied().unwrap_or_else(|| category.weight())
+    }
+
+    /// Override category weights; categories not listed keep their default weight.
+    /// Rejects negative weights or an effective total that does not sum to 1.0.
+    pub fn set_weights(&mut self, weights: HashMap<ThreatCategory, f64>) -> Result<(), String> {
+        if let Some((category, w)) = weights.iter().find(|(_, w)| !w.is_finite() || **w < 0.0) {
+            return Err(format!("Invalid weight {} for {:?}", w, category));
+        }
+
+        let sum: f64 = ThreatCategory::all().iter()
+            .map(|c| weights.get(c).copied().unwrap_or_else(|| c.weight()))
+            .sum();
+        if (sum - 1.0).abs() > 0.001 {
+            return Err(format!("Category weights must sum to 1.0, got {:.4}", sum));
+        }
+
+        self.weights = weights;
+        Ok(())
+    }
+
+    /// Era multiplier for a category in the current era (override if set, otherwise the default)
+    pub fn era_multiplier(&self, category: ThreatCategory) -> f64 {
+        self.era_multiplier_in(category, self.current_era)
+    }
+
+    /// Era multiplier for a category in `era` (override if set, otherwise the default)
+    pub fn era_multiplier_in(&self, category: ThreatCategory, era: QuantumEra) -> f64 {
+        self.era_multipliers.get(&(category, era))
+            .copied()
+            .unwrap_or_else(|| category.era_multiplier(era))
+    }
+
+    /// Factor an override scales `category`'s aggregate weight by in the current era
+    /// 1.0 without an override, so default scores match the plain category weights.
+    /// (Inside one category every indicator shares the multiplier, so it cancels
+    /// out of the category score and only the aggregate can reflect it.)
+    fn era_override_factor(&self, category: ThreatCategory) -> f64 {
+        match self.era_multipliers.get(&(category, self.current_era)) {
+            Some(value) => value / category.era_multiplier(self.current_era),
+            None => 1.0,
+        }
+    }
+
+    /// Override the multiplier for `category` during `era`; must lie in [0, 1]
+    pub fn set_era_multiplier(&mut self, category: ThreatCategory, era: QuantumEra, value: f64) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(format!("Era multiplier {} for {:?} in {:?} is outside [0, 1]", value, category, era));
         }
+        self.era_multipliers.insert((category, era), value);
+        Ok(())
     }
 
     /// Add a new threat indicator
+    /// With `dedup_window` set, a repeat within the window of a live indicator
+    /// is merged into it, keeping the higher severity and the later timestamp and expiry.
     pub fn add_indicator(&mut self, indicator: ThreatIndicator) {
+        if let Some(window) = self.dedup_window.and_then(|w| chrono::Duration::from_std(w).ok()) {
+            let now = Utc::now();
+            let duplicate = self.indicators.iter_mut().rev().find(|existing| {
+                existing.category == indicator.category
+                    && existing.sub_category == indicator.sub_category
+                    && existing.source == indicator.source
+                    && !existing.is_expired(now)
+                    && (indicator.timestamp - existing.timestamp).abs() <= window
+            });
+            if let Some(existing) = duplicate {
+                existing.severity = existing.severity.max(indicator.severity);
+                existing.timestamp = existing.timestamp.max(indicator.timestamp);
+                // A repeat without expiry outlasts any deadline
+                existing.expires_at = existing.expires_at.zip(indicator.expires_at).map(|(a, b)| a.max(b));
+                self.unsmoothed_indicators = true;
+                return;
+            }
+        }
+
         self.indicators.push_back(indicator);
+        self.unsmoothed_indicators = true;
         while self.indicators.len() > self.max_indicators {
             self.indicators.pop_front();
         }
     }
 
+    /// Drop expired indicators to reclaim buffer space, returning how many were removed
+    pub fn evict_expired(&mut self) -> usize {
+        let now = Utc::now();
+        let before = self.indicators.len();
+        self.indicators.retain(|i| !i.is_expired(now));
+        before - self.indicators.len()
+    }
+
     /// Get recent indicators
     pub fn get_indicators(&self) -> Vec<ThreatIndicator> {
         self.indicators.iter().cloned().collect()
@@ -282,23 +437,74 @@ impl QuantumResistanceMonitor {
         self.risk_history.iter().cloned().collect()
     }
 
+    /// Get the last `points` scores for a category, oldest first
+    pub fn get_category_history(&self, category: ThreatCategory, points: usize) -> Vec<CategoryHistoryPoint> {
+        let mut series: Vec<CategoryHistoryPoint> = self.risk_history.iter()
+            .rev()
+            .filter_map(|assessment| {
+                assessment.category_breakdown.iter()
+                    .find(|c| c.category == category)
+                    .map(|c| CategoryHistoryPoint {
+                        timestamp: assessment.timestamp,
+                        score: c.score,
+                    })
+            })
+            .take(points)
+            .collect();
+        series.reverse();
+        series
+    }
+
     /// Get indicator count
     pub fn indicator_count(&self) -> usize {
         self.indicators.len()
     }
 
+    /// Persist indicators and risk history as JSON
+    pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
+        let snapshot = MonitorSnapshot {
+            indicators: self.get_indicators(),
+            risk_history: self.get_risk_history(),
+        };
+        let json = serde_json::to_vec(&snapshot)?;
+
+        // Write then rename so a crash mid-write never leaves a truncated file
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Restore a monitor from a file written by `save_to_file`
+    /// Oldest entries are dropped if the file exceeds the in-memory caps.
+    pub fn load_from_file(path: &Path) -> io::Result<Self> {
+        let snapshot: MonitorSnapshot = serde_json::from_slice(&std::fs::read(path)?)?;
+
+        let mut monitor = Self::new();
+        for indicator in snapshot.indicators {
+            monitor.add_indicator(indicator);
+        }
+        monitor.risk_history.extend(snapshot.risk_history);
+        while monitor.risk_history.len() > monitor.max_history {
+            monitor.risk_history.pop_front();
+        }
+        Ok(monitor)
+    }
+
     /// Calculate category-specific risk
     fn calculate_category_risk(&self, category: ThreatCategory, recent: &[ThreatIndicator]) -> CategoryRisk {
         let cat_indicators: Vec<_> = recent.iter()
             .filter(|i| i.category == category)
+            .filter(|i| i.severity >= self.severity_floor && i.confidence >= self.confidence_floor)
             .collect();
         
         if cat_indicators.is_empty() {
             return CategoryRisk {
                 category,
                 score: 0,
+                threat_level: ThreatLevel::None,
                 indicator_count: 0,
                 top_threats: vec![],
+                delta: 0,
             };
         }
 
@@ -323,16 +529,102 @@ impl QuantumResistanceMonitor {
         CategoryRisk {
             category,
             score,
+            threat_level: ThreatLevel::from_severity(score as f64 / 10000.0),
             indicator_count: cat_indicators.len(),
             top_threats: threats.into_iter().take(3).collect(),
+            delta: 0,
         }
     }
 
     /// Calculate current risk score
+    /// The recommendation follows the smoothed score, which advances one EWMA
+    /// step per call that follows new indicators; `score` stays the raw value.
     pub fn calculate_risk(&mut self) -> RiskAssessment {
-        if self.indicators.is_empty() {
+        let assessment = self.current_risk();
+        self.smoothed_score = self.next_smoothed_score(assessment.score);
+        self.unsmoothed_indicators = false;
+
+        if !self.indicators.is_empty() {
+            self.risk_history.push_back(assessment.clone());
+            while self.risk_history.len() > self.max_history {
+                self.risk_history.pop_front();
+            }
+        }
+
+        assessment
+    }
+
+    /// What `calculate_risk` would return now, without advancing the
+    /// smoothed score or recording history (for status and metrics reads)
+    pub fn current_risk(&self) -> RiskAssessment {
+        // Use recent indicators (last 50), ignoring expired alerts
+        let now = Utc::now();
+        let recent: Vec<_> = self.indicators.iter()
+            .rev()
+            .filter(|i| !i.is_expired(now))
+            .take(50)
+            .cloned()
+            .collect();
+        let mut assessment = self.assess(recent);
+
+        if let Some(previous) = self.risk_history.back() {
+            for category in &mut assessment.category_breakdown {
+                let before = previous.category_breakdown.iter()
+                    .find(|c| c.category == category.category)
+                    .map_or(0, |c| c.score);
+                category.delta = category.score as i32 - before as i32;
+            }
+        }
+
+        assessment.smoothed_score = self.next_smoothed_score(assessment.score).round() as u32;
+        assessment.recommendation = self.recommendation_for(assessment.smoothed_score);
+        assessment
+    }
+
+    /// Smoothed score after folding in `score`, if indicators arrived since the last step
+    fn next_smoothed_score(&self, score: u32) -> f64 {
+        if self.unsmoothed_indicators {
+            let alpha = self.smoothing_alpha.clamp(f64::EPSILON, 1.0);
+            alpha * score as f64 + (1.0 - alpha) * self.smoothed_score
+        } else {
+            self.smoothed_score
+        }
+    }
+
+    /// Calculate risk over indicators carrying any of the given tags
+    /// An empty tag list scores all indicators; the result is not recorded in history
+    pub fn calculate_risk_filtered(&self, include_tags: &[String]) -> RiskAssessment {
+        let now = Utc::now();
+        let recent: Vec<_> = self.indicators.iter()
+            .rev()
+            .filter(|i| !i.is_expired(now))
+            .filter(|i| include_tags.is_empty() || i.tags.iter().any(|t| include_tags.contains(t)))
+            .take(50)
+            .cloned()
+            .collect();
+
+        self.assess(recent)
+    }
+
+    /// Map a score onto the rotation thresholds
+    fn recommendation_for(&self, score: u32) -> RiskRecommendation {
+        if score >= self.threshold_emergency {
+            RiskRecommendation::EmergencyRotation
+        } else if score >= self.threshold_scheduled {
+            RiskRecommendation::ScheduleRotation
+        } else if score >= self.threshold_scheduled / 2 {
+            RiskRecommendation::MonitorClosely
+        } else {
+            RiskRecommendation::Continue
+        }
+    }
+
+    /// Score a set of recent indicators (newest first)
+    fn assess(&self, recent: Vec<ThreatIndicator>) -> RiskAssessment {
+        if recent.is_empty() {
             return RiskAssessment {
                 score: 0,
+                smoothed_score: 0,
                 recommendation: RiskRecommendation::Continue,
                 category_breakdown: vec![],
                 indicators: vec![],
@@ -341,9 +633,6 @@ impl QuantumResistanceMonitor {
             };
         }
 
-        // Use recent indicators (last 50)
-        let recent: Vec<_> = self.indicators.iter().rev().take(50).cloned().collect();
-
         // Calculate per-category risk
         let category_risks: Vec<CategoryRisk> = ThreatCategory::all()
             .iter()
@@ -355,7 +644,7 @@ impl QuantumResistanceMonitor {
         let mut weight_total = 0.0;
 
         for cat_risk in &category_risks {
-            let w = cat_risk.category.weight();
+            let w = self.weight(cat_risk.category) * self.era_override_factor(cat_risk.category);
             weighted_sum += (cat_risk.score as f64) * w;
             weight_total += w;
         }
@@ -366,43 +655,36 @@ impl QuantumResistanceMonitor {
             0
         };
 
-        let recommendation = if score >= self.threshold_emergency {
-            RiskRecommendation::EmergencyRotation
-        } else if score >= self.threshold_scheduled {
-            RiskRecommendation::ScheduleRotation
-        } else if score >= self.threshold_scheduled / 2 {
-            RiskRecommendation::MonitorClosely
-        } else {
-            RiskRecommendation::Continue
-        };
-
-        let assessment = RiskAssessment {
+        RiskAssessment {
             score,
-            recommendation,
+            smoothed_score: score,
+            recommendation: self.recommendation_for(score),
             category_breakdown: category_risks,
             indicators: recent.into_iter().take(10).collect(),
             current_era: self.current_era,
             timestamp: Utc::now(),
-        };
-
-        self.risk_history.push_back(assessment.clone());
-        while self.risk_history.len() > self.max_history {
-            self.risk_history.pop_front();
         }
+    }
 
-        assessment
+    /// Simulated severity centred on the effective era multiplier, scaled by category weight
+    /// The heaviest category keeps the full multiplier, weight-0 categories half of it;
+    /// jitter is triangular in ±0.15.
+    fn sample_severity(&self, rng: &mut impl Rng, category: ThreatCategory, era: QuantumEra) -> f64 {
+        let max_weight = ThreatCategory::all().iter().map(|c| self.weight(*c)).fold(0.0, f64::max);
+        let mean = self.era_multiplier_in(category, era) * (0.5 + 0.5 * self.weight(category) / max_weight);
+        let jitter = rng.gen_range(-0.075..0.075) + rng.gen_range(-0.075..0.075);
+        (mean + jitter).clamp(0.1, 1.0)
     }
 
-    /// Simulate a threat feed update
-    pub fn simulate_threat_feed(&mut self) -> ThreatIndicator {
-        let mut rng = rand::thread_rng();
-        
+    /// Draw a random indicator from the built-in threat catalog
+    /// Severities follow this monitor's effective category weights and era multipliers.
+    fn simulated_indicator(&self, rng: &mut impl Rng) -> ThreatIndicator {
         let sources = [
             "arXiv", "NIST", "IACR", "IBM Quantum", "Google AI", 
             "CVE Database", "GitHub Security", "Industry Report"
         ];
         
-        let category = ThreatCategory::random();
+        let category = ThreatCategory::random(rng);
 
         let (sub_category, descriptions) = match category {
             ThreatCategory::DigitalSignatures => {
@@ -699,16 +981,10 @@ impl QuantumResistanceMonitor {
             },
         };
 
-        let base_severity = category.weight() * 2.0;
-        let severity = (base_severity + rng.gen_range(-0.2..0.4)).clamp(0.1, 1.0);
+        let era_relevance = sample_era(rng);
+        let severity = self.sample_severity(rng, category, era_relevance);
 
-        let era_relevance = match rng.gen_range(0..10) {
-            0..=2 => QuantumEra::PreQuantum,
-            3..=6 => QuantumEra::Nisq,
-            _ => QuantumEra::FaultTolerant,
-        };
-
-        let indicator = ThreatIndicator {
+        ThreatIndicator {
             category,
             sub_category,
             severity,
@@ -718,13 +994,63 @@ impl QuantumResistanceMonitor {
             description: descriptions[rng.gen_range(0..descriptions.len())].to_string(),
             era_relevance,
             references: vec![],
-        };
+            tags: vec![],
+            expires_at: None,
+        }
+    }
 
+    /// Simulate a threat feed update drawn from `rng`
+    pub fn simulate_threat_feed(&mut self, rng: &mut impl Rng) -> ThreatIndicator {
+        let indicator = self.simulated_indicator(rng);
         self.add_indicator(indicator.clone());
         indicator
     }
 }
 
+/// Source of threat intelligence pulled by the simulation loop
+/// Implement this to plug in a real feed (arXiv scraper, CVE API, ...).
+#[async_trait::async_trait]
+pub trait ThreatFeed: Send {
+    /// Next indicator, or None if the feed has nothing new right now
+    /// `scoring` is the live monitor, for feeds that grade severity by its weights.
+    async fn next_indicator(&mut self, scoring: &QuantumResistanceMonitor) -> Option<ThreatIndicator>;
+}
+
+/// Random indicators drawn from the built-in threat catalog
+#[derive(Debug)]
+pub struct SimulatedFeed {
+    rng: StdRng,
+}
+
+impl SimulatedFeed {
+    /// Feed whose indicator sequence is fixed by `seed`
+    pub fn seeded(seed: u64) -> Self {
+        Self { rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Default for SimulatedFeed {
+    fn default() -> Self {
+        Self { rng: StdRng::from_entropy() }
+    }
+}
+
+#[async_trait::async_trait]
+impl ThreatFeed for SimulatedFeed {
+    async fn next_indicator(&mut self, scoring: &QuantumResistanceMonitor) -> Option<ThreatIndicator> {
+        Some(scoring.simulated_indicator(&mut self.rng))
+    }
+}
+
+/// Era distribution of simulated indicators (30% pre-quantum, 40% NISQ, 30% fault-tolerant)
+fn sample_era(rng: &mut impl Rng) -> QuantumEra {
+    match rng.gen_range(0..10) {
+        0..=2 => QuantumEra::PreQuantum,
+        3..=6 => QuantumEra::Nisq,
+        _ => QuantumEra::FaultTolerant,
+    }
+}
+
 impl Default for QuantumResistanceMonitor {
     fn default() -> Self {
         Self::new()
@@ -735,6 +1061,68 @@ impl Default for QuantumResistanceMonitor {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_simulated_severity_tracks_category_and_era() {
+        let mut rng = rand::thread_rng();
+        let qrm = QuantumResistanceMonitor::new();
+        let mut mean_severity = |category: ThreatCategory| {
+            (0..1000)
+                .map(|_| {
+                    let era = sample_era(&mut rng);
+                    qrm.sample_severity(&mut rng, category, era)
+                })
+                .sum::<f64>() / 1000.0
+        };
+
+        let hash = mean_severity(ThreatCategory::HashReversal);
+        let signatures = mean_severity(ThreatCategory::DigitalSignatures);
+        assert!(hash < signatures, "hash {} >= signatures {}", hash, signatures);
+
+        // HNDL is already active before fault tolerance
+        let hndl_pre = qrm.sample_severity(&mut rng, ThreatCategory::DecryptionHndl, QuantumEra::PreQuantum);
+        assert!(hndl_pre > 0.6);
+    }
+
+    #[test]
+    fn test_simulated_severity_follows_monitor_overrides() {
+        let mut rng = rand::thread_rng();
+        let mut qrm = QuantumResistanceMonitor::new();
+        let mean_severity = |qrm: &QuantumResistanceMonitor, rng: &mut rand::rngs::ThreadRng| {
+            (0..1000)
+                .map(|_| qrm.sample_severity(rng, ThreatCategory::HashReversal, QuantumEra::PreQuantum))
+                .sum::<f64>() / 1000.0
+        };
+
+        let default_mean = mean_severity(&qrm, &mut rng);
+        qrm.set_era_multiplier(ThreatCategory::HashReversal, QuantumEra::PreQuantum, 1.0).unwrap();
+        let overridden_mean = mean_severity(&qrm, &mut rng);
+        assert!(overridden_mean > default_mean + 0.2, "{} vs {}", overridden_mean, default_mean);
+    }
+
+    #[test]
+    fn test_smoothing_absorbs_lone_spike() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        assert_eq!(qrm.smoothing_alpha, 1.0, "smoothing is opt-in");
+        qrm.smoothing_alpha = 0.3;
+        let spike = |qrm: &mut QuantumResistanceMonitor| {
+            for category in ThreatCategory::all() {
+                qrm.add_indicator(indicator(*category, 1.0, &[]));
+            }
+            qrm.calculate_risk()
+        };
+
+        let first = spike(&mut qrm);
+        assert!(first.score >= qrm.threshold_emergency);
+        assert!(first.smoothed_score < qrm.threshold_emergency);
+        assert_ne!(first.recommendation, RiskRecommendation::EmergencyRotation);
+
+        // Polling without new indicators does not advance the average
+        assert_eq!(qrm.calculate_risk().smoothed_score, first.smoothed_score);
+
+        let sustained = (0..10).map(|_| spike(&mut qrm)).last().unwrap();
+        assert_eq!(sustained.recommendation, RiskRecommendation::EmergencyRotation);
+    }
+
     #[test]
     fn test_weights_sum_to_one() {
         let sum: f64 = ThreatCategory::all().iter().map(|c| c.weight()).sum();
@@ -745,4 +1133,259 @@ mod tests {
     fn test_category_count() {
         assert_eq!(ThreatCategory::all().len(), 12);
     }
+
+    fn indicator(category: ThreatCategory, severity: f64, tags: &[&str]) -> ThreatIndicator {
+        ThreatIndicator {
+            category,
+            sub_category: "Test".to_string(),
+            severity,
+            confidence: 0.9,
+            source: "Test".to_string(),
+            timestamp: Utc::now(),
+            description: "Test indicator".to_string(),
+            era_relevance: QuantumEra::Nisq,
+            references: vec![],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_severity_floor_suppresses_background_noise() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        for _ in 0..20 {
+            qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.15, &[]));
+        }
+        let signatures = |risk: &RiskAssessment| risk.category_breakdown.iter()
+            .find(|c| c.category == ThreatCategory::DigitalSignatures)
+            .map_or(0, |c| c.score);
+
+        let unfloored = qrm.calculate_risk_filtered(&[]);
+        assert!((1499..=1500).contains(&signatures(&unfloored)));
+        assert!(unfloored.score > 0);
+
+        qrm.severity_floor = 0.2;
+        let floored = qrm.calculate_risk_filtered(&[]);
+        assert_eq!(signatures(&floored), 0);
+        assert_eq!(floored.score, 0);
+
+        qrm.severity_floor = 0.0;
+        qrm.confidence_floor = 0.95;
+        assert_eq!(qrm.calculate_risk_filtered(&[]).score, 0);
+    }
+
+    #[test]
+    fn test_dedup_window_merges_repeated_indicators() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.dedup_window = Some(Duration::from_secs(60));
+        for severity in [0.5, 0.6, 0.7, 0.8, 0.9] {
+            qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, severity, &[]));
+        }
+        assert_eq!(qrm.indicator_count(), 1);
+        assert_eq!(qrm.get_indicators()[0].severity, 0.9);
+
+        // A different source is a separate report
+        let mut other = indicator(ThreatCategory::DigitalSignatures, 0.5, &[]);
+        other.source = "NIST".to_string();
+        qrm.add_indicator(other);
+        assert_eq!(qrm.indicator_count(), 2);
+
+        qrm.dedup_window = None;
+        qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.5, &[]));
+        assert_eq!(qrm.indicator_count(), 3);
+    }
+
+    #[test]
+    fn test_dedup_keeps_peak_severity_and_latest_timestamp() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.dedup_window = Some(Duration::from_secs(60));
+        let first = indicator(ThreatCategory::DigitalSignatures, 0.9, &[]);
+        let seen_at = first.timestamp;
+        qrm.add_indicator(first);
+
+        // A lower, backdated repeat neither lowers the alert nor moves it back in time
+        let mut repeat = indicator(ThreatCategory::DigitalSignatures, 0.4, &[]);
+        repeat.timestamp = seen_at - chrono::Duration::seconds(30);
+        qrm.add_indicator(repeat);
+        assert_eq!(qrm.indicator_count(), 1);
+        assert_eq!(qrm.get_indicators()[0].severity, 0.9);
+        assert_eq!(qrm.get_indicators()[0].timestamp, seen_at);
+
+        // Far outside the window in either direction is a separate report
+        let mut stale = indicator(ThreatCategory::DigitalSignatures, 0.4, &[]);
+        stale.timestamp = seen_at - chrono::Duration::hours(1);
+        qrm.add_indicator(stale);
+        assert_eq!(qrm.indicator_count(), 2);
+    }
+
+    #[test]
+    fn test_dedup_skips_expired_entries() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.dedup_window = Some(Duration::from_secs(60));
+        let mut expired = indicator(ThreatCategory::DigitalSignatures, 0.5, &[]);
+        expired.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        qrm.add_indicator(expired);
+
+        qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.5, &[]));
+        assert_eq!(qrm.indicator_count(), 2);
+        assert!(qrm.calculate_risk_filtered(&[]).score > 0);
+    }
+
+    #[test]
+    fn test_category_delta_tracks_escalation() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.3, &[]));
+        qrm.add_indicator(indicator(ThreatCategory::DecryptionHndl, 0.6, &[]));
+        let first = qrm.calculate_risk();
+        assert!(first.category_breakdown.iter().all(|c| c.delta == 0));
+
+        for severity in [0.7, 0.8, 0.9] {
+            qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, severity, &[]));
+        }
+        let risk = qrm.calculate_risk();
+        let delta = |category| risk.category_breakdown.iter().find(|c| c.category == category).unwrap().delta;
+
+        assert!(delta(ThreatCategory::DigitalSignatures) > 0);
+        assert!(delta(ThreatCategory::DecryptionHndl) <= 0);
+        assert_eq!(delta(ThreatCategory::SideChannel), 0);
+    }
+
+    #[test]
+    fn test_category_risk_carries_threat_level() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.add_indicator(indicator(ThreatCategory::DecryptionHndl, 0.8, &[]));
+
+        let risk = qrm.calculate_risk();
+        for category in &risk.category_breakdown {
+            assert_eq!(category.threat_level, ThreatLevel::from_severity(category.score as f64 / 10000.0));
+        }
+        let hndl = risk.category_breakdown.iter()
+            .find(|c| c.category == ThreatCategory::DecryptionHndl)
+            .unwrap();
+        assert_eq!(hndl.threat_level, ThreatLevel::NearTerm);
+    }
+
+    #[test]
+    fn test_expired_indicator_scores_zero() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        let mut expired = indicator(ThreatCategory::DecryptionHndl, 0.95, &[]);
+        expired.expires_at = Some(Utc::now() - chrono::Duration::minutes(1));
+        qrm.add_indicator(expired);
+
+        let risk = qrm.calculate_risk();
+        assert_eq!(risk.score, 0);
+        assert!(risk.indicators.is_empty());
+
+        let mut live = indicator(ThreatCategory::DecryptionHndl, 0.95, &[]);
+        live.expires_at = Some(Utc::now() + chrono::Duration::hours(1));
+        qrm.add_indicator(live);
+        assert!(qrm.calculate_risk().score > 0);
+
+        assert_eq!(qrm.evict_expired(), 1);
+        assert_eq!(qrm.indicator_count(), 1);
+    }
+
+    #[test]
+    fn test_filtered_risk_excludes_untagged() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.3, &["production"]));
+        qrm.add_indicator(indicator(ThreatCategory::DecryptionHndl, 0.95, &[]));
+        qrm.add_indicator(indicator(ThreatCategory::KeyManagement, 0.9, &["theoretical"]));
+
+        let unfiltered = qrm.calculate_risk();
+        let history_len = qrm.get_risk_history().len();
+        let production = qrm.calculate_risk_filtered(&["production".to_string()]);
+
+        assert!(production.score < unfiltered.score);
+        assert_eq!(production.indicators.len(), 1);
+        assert_eq!(qrm.calculate_risk_filtered(&[]).score, unfiltered.score);
+        assert_eq!(qrm.get_risk_history().len(), history_len);
+    }
+
+    #[test]
+    fn test_custom_weights_change_score() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.add_indicator(indicator(ThreatCategory::CrossChainBridge, 0.9, &[]));
+        let default_score = qrm.calculate_risk().score;
+
+        // Bridge-heavy deployment: shift MEV weight onto cross-chain bridges
+        let shifted = ThreatCategory::CrossChainBridge.weight() + ThreatCategory::MevOrdering.weight();
+        let weights = HashMap::from([
+            (ThreatCategory::CrossChainBridge, shifted),
+            (ThreatCategory::MevOrdering, 0.0),
+        ]);
+        qrm.set_weights(weights).unwrap();
+
+        assert!((qrm.weight(ThreatCategory::CrossChainBridge) - shifted).abs() < 1e-12);
+        assert!(qrm.calculate_risk().score > default_score);
+    }
+
+    #[test]
+    fn test_era_multiplier_override_raises_contribution() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        qrm.add_indicator(indicator(ThreatCategory::SideChannel, 0.9, &[]));
+        qrm.add_indicator(indicator(ThreatCategory::DigitalSignatures, 0.2, &[]));
+        assert_eq!(qrm.era_multiplier(ThreatCategory::SideChannel), 0.5);
+        let default_score = qrm.calculate_risk_filtered(&[]).score;
+
+        qrm.set_era_multiplier(ThreatCategory::SideChannel, QuantumEra::PreQuantum, 1.0).unwrap();
+        assert_eq!(qrm.era_multiplier(ThreatCategory::SideChannel), 1.0);
+        assert!(qrm.calculate_risk_filtered(&[]).score > default_score);
+
+        // Overrides are per era and bounded
+        qrm.current_era = QuantumEra::Nisq;
+        assert_eq!(qrm.era_multiplier(ThreatCategory::SideChannel), 0.75);
+        assert!(qrm.set_era_multiplier(ThreatCategory::SideChannel, QuantumEra::Nisq, 1.5).is_err());
+        assert!(qrm.set_era_multiplier(ThreatCategory::SideChannel, QuantumEra::Nisq, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_default_aggregate_uses_plain_category_weights() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        for (category, severity) in [
+            (ThreatCategory::SideChannel, 0.9),
+            (ThreatCategory::DigitalSignatures, 0.2),
+            (ThreatCategory::DecryptionHndl, 0.6),
+        ] {
+            qrm.add_indicator(indicator(category, severity, &[]));
+        }
+
+        let assessment = qrm.calculate_risk_filtered(&[]);
+        let (sum, total) = assessment.category_breakdown.iter()
+            .fold((0.0, 0.0), |(sum, total), c| (sum + c.score as f64 * c.category.weight(), total + c.category.weight()));
+        assert_eq!(assessment.score, (sum / total) as u32);
+    }
+
+    #[test]
+    fn test_non_normalized_weights_rejected() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        let weights = HashMap::from([(ThreatCategory::DigitalSignatures, 0.5)]);
+
+        assert!(qrm.set_weights(weights).is_err());
+        assert_eq!(qrm.weight(ThreatCategory::DigitalSignatures), ThreatCategory::DigitalSignatures.weight());
+        assert!(qrm.set_weights(HashMap::from([(ThreatCategory::HashReversal, -0.1)])).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut qrm = QuantumResistanceMonitor::new();
+        for _ in 0..5 {
+            qrm.simulate_threat_feed(&mut rand::thread_rng());
+            qrm.calculate_risk();
+        }
+
+        let path = std::env::temp_dir().join(format!("qrms-state-{}.json", uuid::Uuid::new_v4()));
+        qrm.save_to_file(&path).unwrap();
+        let restored = QuantumResistanceMonitor::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(restored.indicator_count(), qrm.indicator_count());
+        assert_eq!(restored.get_risk_history().len(), qrm.get_risk_history().len());
+    }
+
+    #[test]
+    fn test_threat_category_from_str() {
+        assert_eq!("mev_ordering".parse::<ThreatCategory>(), Ok(ThreatCategory::MevOrdering));
+        assert!("garbage".parse::<ThreatCategory>().is_err());
+    }
 }
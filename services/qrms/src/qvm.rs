@@ -37,8 +37,12 @@
 //! └─────────────────────────────────────────────────────────────────┘
 //! ```
 
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use chrono::{DateTime, Utc};
 
 use crate::qrm::{QuantumResistanceMonitor, ThreatCategory, QuantumEra, RiskAssessment, ThreatIndicator};
@@ -50,7 +54,7 @@ use crate::apqc::AdaptivePqcLayer;
 // ============================================================================
 
 /// Supported Google quantum processor types for virtualization
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum QuantumProcessor {
     /// 105-qubit Willow processor (2024)
     WillowPink,
@@ -122,10 +126,52 @@ impl QuantumProcessor {
             Self::Custom { .. } => 15.0,
         }
     }
+
+    /// Qubit coupling topology (Google processors are 2D grids)
+    pub fn connectivity(&self) -> ConnectivityType {
+        match self {
+            Self::Custom { connectivity, .. } => *connectivity,
+            _ => ConnectivityType::Grid,
+        }
+    }
+
+    /// Simplified quantum volume: 2^n for the widest n×n random circuit whose
+    /// estimated success probability stays above the 2/3 heavy-output threshold
+    pub fn quantum_volume(&self) -> u64 {
+        let (e1, e2, ro) = (
+            self.single_qubit_error_rate(),
+            self.two_qubit_error_rate(),
+            self.readout_error_rate(),
+        );
+
+        let mut width = 0;
+        for n in 1..=self.qubit_count().min(63) {
+            let nf = n as f64;
+            // Each of n layers pairs up the qubits; routing adds SWAPs (3 gates each)
+            // over the mean distance between random partners
+            let mean_distance = match self.connectivity() {
+                ConnectivityType::AllToAll => 1.0,
+                ConnectivityType::Grid => 2.0 / 3.0 * nf.sqrt(),
+                ConnectivityType::HeavyHex => nf.sqrt(),
+                ConnectivityType::Linear => nf / 3.0,
+            };
+            let routed_gates_per_pair = 1.0 + 3.0 * (mean_distance - 1.0).max(0.0);
+            let two_qubit_gates = nf * (n / 2) as f64 * routed_gates_per_pair;
+
+            let success = (1.0 - e2).powf(two_qubit_gates)
+                * (1.0 - e1).powf(nf * nf)
+                * (1.0 - ro).powf(nf);
+            if success <= 2.0 / 3.0 {
+                break;
+            }
+            width = n;
+        }
+        1u64 << width
+    }
 }
 
 /// Qubit connectivity topology
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ConnectivityType {
     /// 2D grid/lattice (typical superconducting)
     Grid,
@@ -156,9 +202,13 @@ pub enum QuantumGate {
     CNOT(usize, usize),
     ISWAP(usize, usize),
     SqrtISWAP(usize, usize),
+    CPhase(usize, usize, f64),  // Phase e^(iθ) on |11⟩ (symmetric in its qubits)
     
     // Measurement
     Measure(usize, String),  // qubit index, measurement key
+
+    // Feed-forward: apply `gate` only if the bit recorded under `condition_key` equals `expected`
+    ClassicalControl { condition_key: String, expected: u8, gate: Box<QuantumGate> },
 }
 
 /// Quantum circuit representation
@@ -168,9 +218,172 @@ pub struct QuantumCircuit {
     pub name: String,
     pub qubits: Vec<GridQubit>,
     pub gates: Vec<Vec<QuantumGate>>,  // Moments (parallel gate layers)
+    #[serde(default)]
     pub metadata: HashMap<String, String>,
 }
 
+/// Largest circuit the state-vector simulator will accept (2^n amplitudes)
+pub const MAX_SIMULATED_QUBITS: usize = 20;
+
+/// Structural problems that make a circuit unrunnable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    NoQubits,
+    TooWide { qubits: usize, limit: usize },
+    /// A two-qubit gate names the same qubit twice (e.g. `CZ(2, 2)`)
+    SelfLoop { gate: String, qubit: usize },
+    QubitOutOfRange { gate: String, qubit: usize, qubits: usize },
+    DuplicateMeasurementKey(String),
+    /// A classically-controlled gate reads a key no earlier gate measures
+    UnmeasuredCondition(String),
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoQubits => write!(f, "Circuit declares no qubits"),
+            Self::TooWide { qubits, limit } => {
+                write!(f, "Circuit uses {} qubits, limit is {}", qubits, limit)
+            }
+            Self::SelfLoop { gate, qubit } => write!(f, "{} acts twice on qubit {}", gate, qubit),
+            Self::QubitOutOfRange { gate, qubit, qubits } => {
+                write!(f, "{} addresses qubit {} but circuit has {}", gate, qubit, qubits)
+            }
+            Self::DuplicateMeasurementKey(key) => write!(f, "Measurement key '{}' is used more than once", key),
+            Self::UnmeasuredCondition(key) => {
+                write!(f, "Classical control reads '{}' before it is measured", key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+impl QuantumCircuit {
+    /// Check every gate addresses declared, distinct qubits and measurement keys are unique
+    pub fn validate(&self) -> Result<(), CircuitError> {
+        let n = self.qubits.len();
+        if n == 0 {
+            return Err(CircuitError::NoQubits);
+        }
+
+        let mut keys = std::collections::HashSet::new();
+        for gate in self.gates.iter().flatten() {
+            if let Some((a, b)) = two_qubit_operands(gate) {
+                if a == b {
+                    return Err(CircuitError::SelfLoop { gate: format!("{:?}", gate), qubit: a });
+                }
+            }
+            if let Some(q) = gate_qubits(gate).into_iter().find(|q| *q >= n) {
+                return Err(CircuitError::QubitOutOfRange { gate: format!("{:?}", gate), qubit: q, qubits: n });
+            }
+            if let QuantumGate::ClassicalControl { condition_key, .. } = gate {
+                if !keys.contains(condition_key.as_str()) {
+                    return Err(CircuitError::UnmeasuredCondition(condition_key.clone()));
+                }
+            }
+            if let QuantumGate::Measure(_, key) = gate {
+                if !keys.insert(key.as_str()) {
+                    return Err(CircuitError::DuplicateMeasurementKey(key.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `validate`, and also check the circuit fits `max_qubits`
+    pub fn validate_width(&self, max_qubits: usize) -> Result<(), CircuitError> {
+        self.validate()?;
+        if self.qubits.len() > max_qubits {
+            return Err(CircuitError::TooWide { qubits: self.qubits.len(), limit: max_qubits });
+        }
+        Ok(())
+    }
+
+    /// Rebuild moments so each gate runs as early as its qubits allow
+    ///
+    /// Gates keep their relative order on every qubit, and measurements keep
+    /// their relative order so outcome bit positions are unchanged. Classically
+    /// controlled gates stay after every measurement that preceded them.
+    pub fn repack_moments(&self) -> QuantumCircuit {
+        let mut moments: Vec<Vec<QuantumGate>> = Vec::new();
+        // Index of the next free moment per qubit
+        let mut qubit_free: HashMap<usize, usize> = HashMap::new();
+        let mut measure_floor = 0;
+        let mut control_floor = 0;
+
+        for gate in self.gates.iter().flatten() {
+            let qubits = gate_qubits(gate);
+            let mut slot = qubits.iter()
+                .map(|q| qubit_free.get(q).copied().unwrap_or(0))
+                .max()
+                .unwrap_or(0);
+            if matches!(gate, QuantumGate::Measure(..)) {
+                slot = slot.max(measure_floor);
+                measure_floor = slot;
+                control_floor = slot + 1;
+            }
+            if matches!(gate, QuantumGate::ClassicalControl { .. }) {
+                slot = slot.max(control_floor);
+            }
+
+            if slot == moments.len() {
+                moments.push(Vec::new());
+            }
+            moments[slot].push(gate.clone());
+            for q in qubits {
+                qubit_free.insert(q, slot + 1);
+            }
+        }
+
+        QuantumCircuit {
+            gates: moments,
+            ..self.clone()
+        }
+    }
+
+    /// SHA-256 over the serialized qubits and gates, as hex
+    /// Structurally identical circuits share a hash regardless of id, name or metadata.
+    pub fn content_hash(&self) -> String {
+        let structure = serde_json::to_vec(&(&self.qubits, &self.gates))
+            .expect("circuit structure serializes");
+        hex::encode(Sha256::digest(&structure))
+    }
+
+    /// Number of moments
+    pub fn depth(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Total gate count (measurements excluded)
+    pub fn gate_count(&self) -> usize {
+        self.gates.iter()
+            .flatten()
+            .filter(|g| !matches!(g, QuantumGate::Measure(..)))
+            .count()
+    }
+
+    /// Number of two-qubit gates
+    pub fn two_qubit_gate_count(&self) -> usize {
+        self.gates.iter()
+            .flatten()
+            .filter(|g| matches!(
+                g,
+                QuantumGate::CZ(..) | QuantumGate::CNOT(..) | QuantumGate::ISWAP(..)
+                    | QuantumGate::SqrtISWAP(..) | QuantumGate::CPhase(..)
+            ))
+            .count()
+    }
+
+    /// Number of T gates (dominant cost under fault tolerance)
+    pub fn t_gate_count(&self) -> usize {
+        self.gates.iter()
+            .flatten()
+            .filter(|g| matches!(g, QuantumGate::T(_)))
+            .count()
+    }
+}
+
 /// Grid qubit addressing (Cirq-compatible)
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct GridQubit {
@@ -192,11 +405,24 @@ pub struct NoiseModel {
     pub amplitude_damping_rate: f64,
     pub phase_damping_rate: f64,
     pub readout_errors: HashMap<String, (f64, f64)>,  // qubit -> (p0|1, p1|0)
+    /// Mean FSim error norm for the native iSWAP family; CZ rate applies when absent
+    #[serde(default)]
+    pub iswap_error_rate: Option<f64>,
     pub gate_durations_ns: HashMap<String, f64>,
     pub calibration_timestamp: DateTime<Utc>,
 }
 
 impl NoiseModel {
+    /// Serialize the model (including calibration timestamp) for later replay
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Load a model previously saved with `to_json`
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
     /// Create noise model from processor calibration data
     pub fn from_processor(processor: QuantumProcessor) -> Self {
         let two_q_err = processor.two_qubit_error_rate();
@@ -212,13 +438,27 @@ impl NoiseModel {
         gate_durations.insert("single".to_string(), 25.0);   // 25 ns typical
         gate_durations.insert("cz".to_string(), 32.0);       // 32 ns for CZ
         gate_durations.insert("measure".to_string(), 1000.0); // 1 μs readout
+
+        // Simulator qubit i maps to the i-th calibrated qubit in (row, col) order
+        let picker = QubitPicker::new(processor);
+        let mut calibrated: Vec<&QubitErrorData> = picker.qubit_errors.values().collect();
+        calibrated.sort_by_key(|e| (e.qubit.row, e.qubit.col));
+        let readout_errors = calibrated.iter()
+            .enumerate()
+            .map(|(i, e)| (i.to_string(), (e.readout_error_1_to_0, e.readout_error_0_to_1)))
+            .collect();
+        let iswap_error_rate = (!picker.two_qubit_errors.is_empty()).then(|| {
+            picker.two_qubit_errors.values().map(|e| e.fsim_error_norm).sum::<f64>()
+                / picker.two_qubit_errors.len() as f64
+        });
         
         Self {
             processor,
             depolarizing_rate,
             amplitude_damping_rate,
             phase_damping_rate,
-            readout_errors: HashMap::new(),
+            readout_errors,
+            iswap_error_rate,
             gate_durations_ns: gate_durations,
             calibration_timestamp: Utc::now(),
         }
@@ -230,6 +470,25 @@ impl NoiseModel {
         let noisy_prob = ideal_prob * (1.0 - total_depolarizing) + 0.5 * total_depolarizing;
         noisy_prob.clamp(0.0, 1.0)
     }
+
+    /// Error rate of a two-qubit gate of the given family
+    pub fn two_qubit_error(&self, family: TwoQubitGateFamily) -> f64 {
+        match (family, self.iswap_error_rate) {
+            (TwoQubitGateFamily::ISwap, Some(rate)) => rate,
+            _ => self.processor.two_qubit_error_rate(),
+        }
+    }
+
+    /// Readout error (p0|1, p1|0) for a simulator qubit, falling back to the device average
+    pub fn readout_error(&self, qubit: usize) -> (f64, f64) {
+        self.readout_errors
+            .get(&qubit.to_string())
+            .copied()
+            .unwrap_or_else(|| {
+                let rate = self.processor.readout_error_rate();
+                (rate, rate)
+            })
+    }
 }
 
 /// Circuit execution result
@@ -242,6 +501,80 @@ pub struct CircuitResult {
     pub execution_time_ms: f64,
     pub fidelity_estimate: f64,
     pub noise_applied: bool,
+    /// Outcome with the highest count (lowest value on ties)
+    #[serde(default)]
+    pub most_probable_outcome: Option<u64>,
+    /// Shannon entropy of the histogram in bits
+    #[serde(default)]
+    pub entropy_bits: f64,
+    /// Set when the circuit was rejected instead of simulated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CircuitResult {
+    /// Empty result for a circuit the simulator refused to run
+    fn rejected(circuit: &QuantumCircuit, repetitions: usize, error: String) -> Self {
+        Self {
+            circuit_id: circuit.id.clone(),
+            repetitions,
+            measurements: HashMap::new(),
+            histogram: HashMap::new(),
+            execution_time_ms: 0.0,
+            fidelity_estimate: 0.0,
+            noise_applied: false,
+            most_probable_outcome: None,
+            entropy_bits: 0.0,
+            error: Some(error),
+        }
+    }
+
+    /// Fill the derived statistics from the histogram
+    fn with_statistics(mut self) -> Self {
+        self.most_probable_outcome = self.most_probable();
+        self.entropy_bits = self.entropy();
+        self
+    }
+
+    /// Histogram normalized to probabilities
+    pub fn distribution(&self) -> HashMap<u64, f64> {
+        let total: usize = self.histogram.values().sum();
+        if total == 0 {
+            return HashMap::new();
+        }
+        self.histogram.iter()
+            .map(|(&outcome, &count)| (outcome, count as f64 / total as f64))
+            .collect()
+    }
+
+    /// Outcome with the highest count (lowest value on ties)
+    pub fn most_probable(&self) -> Option<u64> {
+        self.histogram.iter()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(&outcome, _)| outcome)
+    }
+
+    /// Shannon entropy of the measured distribution in bits
+    pub fn entropy(&self) -> f64 {
+        self.distribution().values()
+            .filter(|&&p| p > 0.0)
+            .map(|&p| -p * p.log2())
+            .sum()
+    }
+
+    /// Total variation distance from an ideal outcome distribution (0 = identical, 1 = disjoint)
+    pub fn tvd_from(&self, ideal: &HashMap<u64, f64>) -> f64 {
+        let measured = self.distribution();
+        let outcomes: std::collections::HashSet<u64> = measured.keys().chain(ideal.keys()).copied().collect();
+        outcomes.into_iter()
+            .map(|outcome| {
+                let p = measured.get(&outcome).copied().unwrap_or(0.0);
+                let q = ideal.get(&outcome).copied().unwrap_or(0.0);
+                (p - q).abs()
+            })
+            .sum::<f64>()
+            / 2.0
+    }
 }
 
 // ============================================================================
@@ -284,6 +617,39 @@ pub struct TwoQubitErrorData {
     pub quality_score: f64,
 }
 
+impl TwoQubitErrorData {
+    /// Error for a gate of `family` on this pair: Pauli error for CZ-like
+    /// gates, the FSim error norm for the native iSWAP family
+    pub fn gate_error(&self, family: TwoQubitGateFamily) -> f64 {
+        match family {
+            TwoQubitGateFamily::Cz => self.pauli_error,
+            TwoQubitGateFamily::ISwap => self.fsim_error_norm,
+        }
+    }
+}
+
+/// Calibration family of a two-qubit gate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TwoQubitGateFamily {
+    /// CZ, CNOT and controlled phases (compiled to CZ)
+    Cz,
+    /// iSWAP and √iSWAP (native FSim gates on Sycamore-family devices)
+    ISwap,
+}
+
+impl TwoQubitGateFamily {
+    /// Family of a two-qubit gate; None for everything else
+    pub fn of(gate: &QuantumGate) -> Option<Self> {
+        match gate {
+            QuantumGate::CZ(..) | QuantumGate::CNOT(..) | QuantumGate::CPhase(..) => Some(Self::Cz),
+            QuantumGate::ISWAP(..) | QuantumGate::SqrtISWAP(..) => Some(Self::ISwap),
+            QuantumGate::ClassicalControl { gate, .. } => Self::of(gate),
+            _ => None,
+        }
+    }
+}
+
 /// Qubit picking strategy
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum QubitPickingStrategy {
@@ -298,7 +664,31 @@ pub enum QubitPickingStrategy {
     /// Maximize coherence time (for deep circuits)
     MaximizeCoherence,
     /// Custom weighted combination
-    Custom { single_weight: f64, two_qubit_weight: f64, readout_weight: f64 },
+    Custom {
+        single_weight: f64,
+        two_qubit_weight: f64,
+        readout_weight: f64,
+        /// Scales the penalty for simultaneous two-qubit gates on neighbouring pairs
+        #[serde(default = "default_crosstalk_weight")]
+        crosstalk_weight: f64,
+    },
+}
+
+/// Crosstalk weight used by every strategy except `Custom`
+pub const DEFAULT_CROSSTALK_WEIGHT: f64 = 0.5;
+
+fn default_crosstalk_weight() -> f64 {
+    DEFAULT_CROSSTALK_WEIGHT
+}
+
+impl QubitPickingStrategy {
+    /// Weight applied to the crosstalk penalty in fidelity estimates
+    pub fn crosstalk_weight(&self) -> f64 {
+        match self {
+            Self::Custom { crosstalk_weight, .. } => *crosstalk_weight,
+            _ => DEFAULT_CROSSTALK_WEIGHT,
+        }
+    }
 }
 
 /// Result of qubit picking analysis
@@ -318,6 +708,10 @@ pub struct QubitPickingResult {
     pub strategy: QubitPickingStrategy,
     /// Detailed quality scores for each selected qubit
     pub quality_details: Vec<QubitErrorData>,
+    /// Whether estimated fidelity clears the picker's floor
+    pub viable: bool,
+    /// Specific concerns about the selection (bad qubits, bad pairs, low fidelity)
+    pub warnings: Vec<String>,
 }
 
 /// Qubit picker for optimal hardware qubit selection
@@ -331,8 +725,19 @@ pub struct QubitPicker {
     connectivity: HashMap<GridQubit, Vec<GridQubit>>,
     /// Calibration timestamp
     calibration_time: DateTime<Utc>,
+    /// Estimated fidelity below which a selection is reported as not viable
+    pub fidelity_floor: f64,
 }
 
+/// Single-qubit error above which a qubit is avoided (readout decay uses 5x)
+const BAD_QUBIT_THRESHOLD: f64 = 0.1;
+
+/// Two-qubit Pauli error above which a pair is avoided, and a required pair warned about
+const BAD_PAIR_THRESHOLD: f64 = 0.05;
+
+/// Stricter single-qubit error above which a selected qubit is warned about
+const WARN_QUBIT_THRESHOLD: f64 = 0.02;
+
 impl QubitPicker {
     /// Create a new qubit picker with simulated calibration data
     pub fn new(processor: QuantumProcessor) -> Self {
@@ -342,11 +747,30 @@ impl QubitPicker {
             two_qubit_errors: HashMap::new(),
             connectivity: HashMap::new(),
             calibration_time: Utc::now(),
+            fidelity_floor: 0.5,
         };
         picker.load_calibration_data();
         picker
     }
 
+    /// Create a qubit picker from externally supplied calibration
+    /// (e.g. parsed from a Cirq device JSON) instead of simulated data
+    pub fn from_calibration(
+        processor: QuantumProcessor,
+        qubit_errors: Vec<QubitErrorData>,
+        two_qubit_errors: Vec<TwoQubitErrorData>,
+        connectivity: HashMap<GridQubit, Vec<GridQubit>>,
+    ) -> Self {
+        Self {
+            processor,
+            qubit_errors: qubit_errors.into_iter().map(|e| (e.qubit, e)).collect(),
+            two_qubit_errors: two_qubit_errors.into_iter().map(|e| (e.qubit_pair, e)).collect(),
+            connectivity,
+            calibration_time: Utc::now(),
+            fidelity_floor: 0.5,
+        }
+    }
+
     /// Load calibration data for the processor
     /// In production, this would load from cirq_google.engine.load_device_noise_properties()
     fn load_calibration_data(&mut self) {
@@ -535,10 +959,15 @@ impl QubitPicker {
         let side = (qubits as f64).sqrt().ceil() as i32;
         let mut qubit_coords: Vec<(i32, i32)> = Vec::new();
         
-        for row in 0..side {
-            for col in 0..side {
-                if qubit_coords.len() < qubits {
-                    qubit_coords.push((row, col));
+        if connectivity == ConnectivityType::Linear {
+            // Single row, so grid connectivity yields a chain
+            qubit_coords.extend((0..qubits as i32).map(|col| (0, col)));
+        } else {
+            for row in 0..side {
+                for col in 0..side {
+                    if qubit_coords.len() < qubits {
+                        qubit_coords.push((row, col));
+                    }
                 }
             }
         }
@@ -562,16 +991,63 @@ impl QubitPicker {
             ConnectivityType::AllToAll => 0.015,
             ConnectivityType::Linear => 0.012,
         };
-        
-        self.build_grid_connectivity(&qubit_coords, base_error, base_error * 1.2);
+
+        match connectivity {
+            ConnectivityType::Grid => {
+                self.build_grid_connectivity(&qubit_coords, base_error, base_error * 1.2)
+            }
+            ConnectivityType::Linear => self.build_connectivity(
+                &qubit_coords,
+                |row, col| vec![(row, col + 1), (row, col - 1)],
+                base_error,
+                base_error * 1.2,
+            ),
+            ConnectivityType::HeavyHex => self.build_connectivity(
+                &qubit_coords,
+                |row, col| {
+                    // Brick-wall embedding: full rows, with vertical links on
+                    // alternating columns so no qubit exceeds degree 3
+                    let mut neighbors = vec![(row, col + 1), (row, col - 1)];
+                    if (row + col) % 2 == 0 {
+                        neighbors.push((row + 1, col));
+                    } else {
+                        neighbors.push((row - 1, col));
+                    }
+                    neighbors
+                },
+                base_error,
+                base_error * 1.2,
+            ),
+            ConnectivityType::AllToAll => {
+                let all = qubit_coords.clone();
+                self.build_connectivity(&qubit_coords, |_, _| all.clone(), base_error, base_error * 1.2)
+            }
+        }
     }
 
-    /// Build connectivity graph and two-qubit error data
+    /// Build 4-neighbor grid connectivity and two-qubit error data
     fn build_grid_connectivity(
         &mut self,
         qubit_coords: &[(i32, i32)],
         base_pauli_error: f64,
         base_fsim_error: f64,
+    ) {
+        self.build_connectivity(
+            qubit_coords,
+            |row, col| vec![(row, col + 1), (row, col - 1), (row + 1, col), (row - 1, col)],
+            base_pauli_error,
+            base_fsim_error,
+        );
+    }
+
+    /// Build connectivity graph and two-qubit error data, linking each qubit
+    /// to whichever candidate coordinates `neighbors_of` yields that exist
+    fn build_connectivity(
+        &mut self,
+        qubit_coords: &[(i32, i32)],
+        neighbors_of: impl Fn(i32, i32) -> Vec<(i32, i32)>,
+        base_pauli_error: f64,
+        base_fsim_error: f64,
     ) {
         let qubit_set: std::collections::HashSet<(i32, i32)> = qubit_coords.iter().cloned().collect();
         
@@ -579,15 +1055,12 @@ impl QubitPicker {
             let qubit = GridQubit::new(row, col);
             let mut neighbors = Vec::new();
             
-            // Check all four neighbors (grid connectivity)
-            for (dr, dc) in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                let nr = row + dr;
-                let nc = col + dc;
-                if qubit_set.contains(&(nr, nc)) {
+            for (nr, nc) in neighbors_of(row, col) {
+                if (nr, nc) != (row, col) && qubit_set.contains(&(nr, nc)) {
                     neighbors.push(GridQubit::new(nr, nc));
                     
                     // Add two-qubit error data (only for one direction to avoid duplicates)
-                    if *dr > 0 || (*dr == 0 && *dc > 0) {
+                    if (nr, nc) > (row, col) {
                         let neighbor = GridQubit::new(nr, nc);
                         let pair = (qubit, neighbor);
                         
@@ -679,7 +1152,7 @@ impl QubitPicker {
             QubitPickingStrategy::Balanced => {
                 qubit_data.quality_score
             }
-            QubitPickingStrategy::Custom { single_weight, two_qubit_weight, readout_weight } => {
+            QubitPickingStrategy::Custom { single_weight, two_qubit_weight, readout_weight, .. } => {
                 let single_score = qubit_data.single_qubit_pauli_error * single_weight;
                 let readout_score = qubit_data.readout_error_1_to_0 * readout_weight;
                 let two_qubit_score = if let Some(neighbors) = self.connectivity.get(&qubit_data.qubit) {
@@ -724,15 +1197,18 @@ impl QubitPicker {
                 .collect();
             
             let fidelity = self.estimate_fidelity(&selected, &[]);
+            let warnings = self.selection_warnings(&mapping, &[], fidelity);
             
             return QubitPickingResult {
                 selected_qubits: selected,
                 qubit_mapping: mapping,
                 estimated_fidelity: fidelity,
-                avoid_qubits: self.get_bad_qubits(0.1),
-                avoid_pairs: self.get_bad_pairs(0.05),
+                avoid_qubits: self.get_bad_qubits(BAD_QUBIT_THRESHOLD),
+                avoid_pairs: self.get_bad_pairs(BAD_PAIR_THRESHOLD),
                 strategy,
                 quality_details,
+                viable: fidelity >= self.fidelity_floor,
+                warnings,
             };
         }
 
@@ -770,15 +1246,57 @@ impl QubitPicker {
             .filter_map(|q| self.qubit_errors.get(q).cloned())
             .collect();
 
+        let warnings = self.selection_warnings(&mapping, required_connectivity, best_fidelity);
+
         QubitPickingResult {
             selected_qubits: selected.clone(),
             qubit_mapping: mapping,
             estimated_fidelity: best_fidelity,
-            avoid_qubits: self.get_bad_qubits(0.1),
-            avoid_pairs: self.get_bad_pairs(0.05),
+            avoid_qubits: self.get_bad_qubits(BAD_QUBIT_THRESHOLD),
+            avoid_pairs: self.get_bad_pairs(BAD_PAIR_THRESHOLD),
             strategy,
             quality_details,
+            viable: best_fidelity >= self.fidelity_floor,
+            warnings,
+        }
+    }
+
+    /// Cross-reference a selection against the warning limits and fidelity floor
+    fn selection_warnings(
+        &self,
+        mapping: &HashMap<usize, GridQubit>,
+        required_connectivity: &[(usize, usize)],
+        fidelity: f64,
+    ) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let flagged_qubits = self.get_bad_qubits(WARN_QUBIT_THRESHOLD);
+        let flagged_pairs = self.get_bad_pairs(BAD_PAIR_THRESHOLD);
+
+        let mut selected: Vec<GridQubit> = mapping.values().copied().collect();
+        selected.sort_by_key(|q| (q.row, q.col));
+        for q in selected.iter().filter(|q| flagged_qubits.contains(q)) {
+            warnings.push(format!("selected qubit ({},{}) is a known bad qubit", q.row, q.col));
+        }
+
+        for (a, b) in required_connectivity {
+            if let (Some(&qa), Some(&qb)) = (mapping.get(a), mapping.get(b)) {
+                if flagged_pairs.contains(&(qa, qb)) || flagged_pairs.contains(&(qb, qa)) {
+                    let error = self.get_pair_error(qa, qb).map(|e| e.pauli_error).unwrap_or_default();
+                    warnings.push(format!(
+                        "required pair ({},{})-({},{}) has pauli_error {:.4} > {}",
+                        qa.row, qa.col, qb.row, qb.col, error, BAD_PAIR_THRESHOLD,
+                    ));
+                }
+            }
+        }
+
+        if fidelity < self.fidelity_floor {
+            warnings.push(format!(
+                "estimated fidelity {:.3} is below floor {:.3}", fidelity, self.fidelity_floor,
+            ));
         }
+
+        warnings
     }
 
     /// Find a connected mapping using BFS
@@ -870,11 +1388,24 @@ impl QubitPicker {
         }
     }
 
-    /// Estimate circuit fidelity with given qubit selection
+    /// Estimate circuit fidelity with given qubit selection, treating every
+    /// two-qubit operation as a CZ
     fn estimate_fidelity(
         &self,
         qubits: &[GridQubit],
         two_qubit_ops: &[(usize, usize)],
+    ) -> f64 {
+        let ops: Vec<((usize, usize), TwoQubitGateFamily)> = two_qubit_ops.iter()
+            .map(|&pair| (pair, TwoQubitGateFamily::Cz))
+            .collect();
+        self.estimate_fidelity_by_family(qubits, &ops)
+    }
+
+    /// Estimate fidelity, charging each two-qubit operation its family's error
+    fn estimate_fidelity_by_family(
+        &self,
+        qubits: &[GridQubit],
+        two_qubit_ops: &[((usize, usize), TwoQubitGateFamily)],
     ) -> f64 {
         if qubits.is_empty() {
             return 0.0;
@@ -888,10 +1419,10 @@ impl QubitPicker {
         
         // Two-qubit fidelity
         let two_qubit_fidelity: f64 = two_qubit_ops.iter()
-            .filter_map(|(a, b)| {
-                if *a < qubits.len() && *b < qubits.len() {
-                    let pair = (qubits[*a], qubits[*b]);
-                    self.two_qubit_errors.get(&pair).map(|e| 1.0 - e.pauli_error)
+            .filter_map(|&((a, b), family)| {
+                if a < qubits.len() && b < qubits.len() {
+                    let pair = (qubits[a], qubits[b]);
+                    self.two_qubit_errors.get(&pair).map(|e| 1.0 - e.gate_error(family))
                 } else {
                     Some(1.0)
                 }
@@ -907,6 +1438,65 @@ impl QubitPicker {
         single_fidelity * two_qubit_fidelity * readout_fidelity
     }
 
+    /// Estimate fidelity of `circuit` on `qubits` (indexed by circuit qubit),
+    /// penalising two-qubit gates that run in the same moment on neighbouring pairs
+    pub fn estimate_circuit_fidelity(
+        &self,
+        qubits: &[GridQubit],
+        circuit: &QuantumCircuit,
+        strategy: QubitPickingStrategy,
+    ) -> f64 {
+        let moments: Vec<Vec<(usize, usize)>> = circuit.gates.iter()
+            .map(|moment| moment.iter().filter_map(two_qubit_operands).collect())
+            .collect();
+        let two_qubit_ops: Vec<((usize, usize), TwoQubitGateFamily)> = circuit.gates.iter()
+            .flatten()
+            .filter_map(|gate| Some((two_qubit_operands(gate)?, TwoQubitGateFamily::of(gate)?)))
+            .collect();
+
+        self.estimate_fidelity_by_family(qubits, &two_qubit_ops)
+            * self.crosstalk_factor(qubits, &moments, strategy.crosstalk_weight())
+    }
+
+    /// Fidelity factor for simultaneous two-qubit gates whose pairs are disjoint
+    /// but coupled through a device edge; each such pair of gates costs
+    /// `weight` times the sum of their Pauli errors
+    fn crosstalk_factor(
+        &self,
+        qubits: &[GridQubit],
+        moments: &[Vec<(usize, usize)>],
+        weight: f64,
+    ) -> f64 {
+        let hardware_pair = |&(a, b): &(usize, usize)| Some((*qubits.get(a)?, *qubits.get(b)?));
+        let pair_error = |(a, b): (GridQubit, GridQubit)| {
+            self.get_pair_error(a, b)
+                .map(|e| e.pauli_error)
+                .unwrap_or_else(|| self.processor.two_qubit_error_rate())
+        };
+        let coupled = |(a, b): (GridQubit, GridQubit), (c, d): (GridQubit, GridQubit)| {
+            [a, b].iter().any(|q| {
+                self.connectivity.get(q).is_some_and(|neighbors| {
+                    neighbors.contains(&c) || neighbors.contains(&d)
+                })
+            })
+        };
+
+        let mut factor = 1.0;
+        for moment in moments {
+            let pairs: Vec<(GridQubit, GridQubit)> = moment.iter().filter_map(hardware_pair).collect();
+            for (i, &first) in pairs.iter().enumerate() {
+                for &second in &pairs[i + 1..] {
+                    let shares_qubit = [first.0, first.1].iter().any(|q| *q == second.0 || *q == second.1);
+                    if !shares_qubit && coupled(first, second) {
+                        let penalty = weight * (pair_error(first) + pair_error(second));
+                        factor *= (1.0 - penalty).clamp(0.0, 1.0);
+                    }
+                }
+            }
+        }
+        factor
+    }
+
     /// Get list of qubits with error above threshold
     fn get_bad_qubits(&self, threshold: f64) -> Vec<GridQubit> {
         self.qubit_errors.iter()
@@ -934,48 +1524,204 @@ impl QubitPicker {
             .or_else(|| self.two_qubit_errors.get(&(q2, q1)))
     }
 
+    /// Mean per-qubit fidelity across the calibrated device
+    /// Combines gate and readout error with amplitude damping (T1 decay) over one readout window
+    pub fn device_fidelity_score(&self) -> f64 {
+        if self.qubit_errors.is_empty() {
+            return 0.0;
+        }
+
+        let readout_window_us = 1.0; // Matches NoiseModel "measure" duration
+        let total: f64 = self.qubit_errors.values()
+            .map(|e| {
+                let damping = (-readout_window_us / e.t1_us.max(f64::EPSILON)).exp();
+                (1.0 - e.single_qubit_pauli_error) * (1.0 - e.readout_error_1_to_0) * damping
+            })
+            .sum();
+
+        total / self.qubit_errors.len() as f64
+    }
+
     /// Get neighbors of a qubit
     pub fn get_neighbors(&self, qubit: GridQubit) -> Option<&Vec<GridQubit>> {
         self.connectivity.get(&qubit)
     }
 
     /// Transform circuit to use selected hardware qubits
+    ///
+    /// Gate indices in the result refer to `qubits` of the returned circuit (physical
+    /// wires). Two-qubit gates on non-adjacent hardware qubits are routed by moving
+    /// the first operand along a shortest path with SWAPs, each decomposed into three
+    /// CNOTs in their own moments; the logical-to-physical permutation is tracked as
+    /// qubits move, and hardware qubits used only for routing are appended as wires.
     pub fn transform_circuit(
         &self,
         circuit: &QuantumCircuit,
         mapping: &HashMap<usize, GridQubit>,
     ) -> QuantumCircuit {
-        let new_qubits: Vec<GridQubit> = (0..circuit.qubits.len())
-            .filter_map(|i| mapping.get(&i).copied())
-            .collect();
-        
-        // Transform gate indices
-        let new_gates: Vec<Vec<QuantumGate>> = circuit.gates.iter()
-            .map(|moment| {
-                moment.iter()
-                    .map(|gate| self.remap_gate(gate, mapping))
-                    .collect()
-            })
-            .collect();
+        let mut wires: Vec<GridQubit> = Vec::new();
+        let mut wire_of: HashMap<GridQubit, usize> = HashMap::new();
+        let mut physical_of: HashMap<usize, GridQubit> = HashMap::new();
+        let mut logical_at: HashMap<GridQubit, usize> = HashMap::new();
+
+        for logical in 0..circuit.qubits.len() {
+            if let Some(&physical) = mapping.get(&logical) {
+                wire_of.insert(physical, wires.len());
+                wires.push(physical);
+                physical_of.insert(logical, physical);
+                logical_at.insert(physical, logical);
+            }
+        }
+
+        let mut new_gates: Vec<Vec<QuantumGate>> = Vec::new();
+        let mut swaps_inserted = 0;
+
+        for moment in &circuit.gates {
+            let mut current: Vec<QuantumGate> = Vec::new();
+
+            for gate in moment {
+                if let Some((a, b)) = two_qubit_operands(gate) {
+                    if let (Some(&pa), Some(&pb)) = (physical_of.get(&a), physical_of.get(&b)) {
+                        let path = self.shortest_path(pa, pb).unwrap_or_default();
+
+                        if path.len() > 2 {
+                            // SWAPs change positions, so close the moment before moving qubits
+                            if !current.is_empty() {
+                                new_gates.push(std::mem::take(&mut current));
+                            }
+
+                            for step in path.windows(2).take(path.len() - 2) {
+                                let (from, to) = (step[0], step[1]);
+                                for q in [from, to] {
+                                    if let std::collections::hash_map::Entry::Vacant(e) = wire_of.entry(q) {
+                                        e.insert(wires.len());
+                                        wires.push(q);
+                                    }
+                                }
+                                let (wf, wt) = (wire_of[&from], wire_of[&to]);
+                                new_gates.push(vec![QuantumGate::CNOT(wf, wt)]);
+                                new_gates.push(vec![QuantumGate::CNOT(wt, wf)]);
+                                new_gates.push(vec![QuantumGate::CNOT(wf, wt)]);
+                                swaps_inserted += 1;
+
+                                // Exchange whichever logical qubits occupy the two sites
+                                let at_from = logical_at.remove(&from);
+                                let at_to = logical_at.remove(&to);
+                                if let Some(l) = at_from {
+                                    physical_of.insert(l, to);
+                                    logical_at.insert(to, l);
+                                }
+                                if let Some(l) = at_to {
+                                    physical_of.insert(l, from);
+                                    logical_at.insert(from, l);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                current.push(Self::remap_gate(gate, |logical| {
+                    physical_of.get(&logical).map(|p| wire_of[p]).unwrap_or(logical)
+                }));
+            }
+
+            if !current.is_empty() {
+                new_gates.push(current);
+            }
+        }
         
         let mut metadata = circuit.metadata.clone();
         metadata.insert("qubit_mapping".to_string(), format!("{:?}", mapping));
         metadata.insert("transformed".to_string(), "true".to_string());
+        metadata.insert("swaps_inserted".to_string(), swaps_inserted.to_string());
+        metadata.insert("source_circuit".to_string(), circuit.id.clone());
         
-        QuantumCircuit {
-            id: format!("{}_mapped", circuit.id),
+        let mut mapped = QuantumCircuit {
+            id: String::new(),
             name: format!("{} (Hardware Mapped)", circuit.name),
-            qubits: new_qubits,
+            qubits: wires,
             gates: new_gates,
             metadata,
+        };
+        mapped.id = mapped.content_hash();
+        mapped
+    }
+
+    /// Shortest hardware path between two qubits (inclusive), by BFS over connectivity
+    fn shortest_path(&self, from: GridQubit, to: GridQubit) -> Option<Vec<GridQubit>> {
+        let mut previous: HashMap<GridQubit, GridQubit> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(q) = queue.pop_front() {
+            if q == to {
+                let mut path = vec![to];
+                while let Some(&p) = previous.get(path.last().unwrap()) {
+                    path.push(p);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &n in self.connectivity.get(&q).into_iter().flatten() {
+                if n != from && !previous.contains_key(&n) {
+                    previous.insert(n, q);
+                    queue.push_back(n);
+                }
+            }
         }
+        None
     }
 
     /// Remap a single gate's qubit indices
-    fn remap_gate(&self, gate: &QuantumGate, mapping: &HashMap<usize, GridQubit>) -> QuantumGate {
-        // For now, gates use indices, so we just need to validate
-        // In a full implementation, we'd convert to GridQubit addressing
-        gate.clone()
+    fn remap_gate(gate: &QuantumGate, wire: impl Fn(usize) -> usize) -> QuantumGate {
+        match gate {
+            QuantumGate::X(q) => QuantumGate::X(wire(*q)),
+            QuantumGate::Y(q) => QuantumGate::Y(wire(*q)),
+            QuantumGate::Z(q) => QuantumGate::Z(wire(*q)),
+            QuantumGate::H(q) => QuantumGate::H(wire(*q)),
+            QuantumGate::S(q) => QuantumGate::S(wire(*q)),
+            QuantumGate::T(q) => QuantumGate::T(wire(*q)),
+            QuantumGate::Rx(q, theta) => QuantumGate::Rx(wire(*q), *theta),
+            QuantumGate::Ry(q, theta) => QuantumGate::Ry(wire(*q), *theta),
+            QuantumGate::Rz(q, theta) => QuantumGate::Rz(wire(*q), *theta),
+            QuantumGate::CZ(a, b) => QuantumGate::CZ(wire(*a), wire(*b)),
+            QuantumGate::CNOT(a, b) => QuantumGate::CNOT(wire(*a), wire(*b)),
+            QuantumGate::ISWAP(a, b) => QuantumGate::ISWAP(wire(*a), wire(*b)),
+            QuantumGate::SqrtISWAP(a, b) => QuantumGate::SqrtISWAP(wire(*a), wire(*b)),
+            QuantumGate::CPhase(a, b, theta) => QuantumGate::CPhase(wire(*a), wire(*b), *theta),
+            QuantumGate::Measure(q, key) => QuantumGate::Measure(wire(*q), key.clone()),
+            QuantumGate::ClassicalControl { condition_key, expected, gate } => QuantumGate::ClassicalControl {
+                condition_key: condition_key.clone(),
+                expected: *expected,
+                gate: Box::new(Self::remap_gate(gate, wire)),
+            },
+        }
+    }
+}
+
+/// All qubits a gate acts on
+fn gate_qubits(gate: &QuantumGate) -> Vec<usize> {
+    match gate {
+        QuantumGate::X(q) | QuantumGate::Y(q) | QuantumGate::Z(q) | QuantumGate::H(q)
+        | QuantumGate::S(q) | QuantumGate::T(q)
+        | QuantumGate::Rx(q, _) | QuantumGate::Ry(q, _) | QuantumGate::Rz(q, _)
+        | QuantumGate::Measure(q, _) => vec![*q],
+        QuantumGate::CZ(a, b) | QuantumGate::CNOT(a, b)
+        | QuantumGate::ISWAP(a, b) | QuantumGate::SqrtISWAP(a, b)
+        | QuantumGate::CPhase(a, b, _) => vec![*a, *b],
+        QuantumGate::ClassicalControl { gate, .. } => gate_qubits(gate),
+    }
+}
+
+/// Operands of a two-qubit gate
+fn two_qubit_operands(gate: &QuantumGate) -> Option<(usize, usize)> {
+    match gate {
+        QuantumGate::CZ(a, b)
+        | QuantumGate::CNOT(a, b)
+        | QuantumGate::ISWAP(a, b)
+        | QuantumGate::SqrtISWAP(a, b)
+        | QuantumGate::CPhase(a, b, _) => Some((*a, *b)),
+        QuantumGate::ClassicalControl { gate, .. } => two_qubit_operands(gate),
+        _ => None,
     }
 }
 
@@ -988,9 +1734,25 @@ pub struct QvmSimulator {
     processor: QuantumProcessor,
     noise_model: NoiseModel,
     state_vector: Option<Vec<Complex>>,
-    random_seed: u64,
+    /// Fixed seed making runs reproducible; `None` draws from OS entropy
+    seed: Option<u64>,
+    rng: StdRng,
+    /// Circuits wider than this are rejected before the state vector is allocated
+    pub max_qubits: usize,
+    /// Results of seeded runs, least recently used first
+    result_cache: VecDeque<(ResultCacheKey, CircuitResult)>,
+    cache_hits: u64,
 }
 
+/// (content hash, repetitions, seed, Kraus noise) identifying a reproducible run
+type ResultCacheKey = (String, usize, u64, bool);
+
+/// Seeded results kept per simulator
+const RESULT_CACHE_CAPACITY: usize = 64;
+
+/// Probability below which a measurement branch is treated as impossible
+const MEASUREMENT_EPSILON: f64 = 1e-12;
+
 /// Complex number for state vector simulation
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Complex {
@@ -1037,6 +1799,27 @@ impl Complex {
     }
 }
 
+/// Whether two state vectors are equal up to a global phase
+/// Every amplitude must agree within `tol` after aligning the phase.
+pub fn states_equal(a: &[Complex], b: &[Complex], tol: f64) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // ⟨a|b⟩ carries the relative global phase
+    let inner = a.iter().zip(b).fold(Complex::zero(), |acc, (x, y)| {
+        acc.add(&Complex::new(x.real, -x.imag).mul(y))
+    });
+    let magnitude = inner.norm_squared().sqrt();
+    if magnitude < tol {
+        return a.iter().chain(b).all(|x| x.norm_squared().sqrt() <= tol);
+    }
+    let phase = inner.scale(1.0 / magnitude);
+    a.iter().zip(b).all(|(x, y)| {
+        let diff = y.add(&phase.mul(x).scale(-1.0));
+        diff.norm_squared().sqrt() <= tol
+    })
+}
+
 impl QvmSimulator {
     /// Create new QVM simulator with specified processor
     pub fn new(processor: QuantumProcessor) -> Self {
@@ -1045,34 +1828,302 @@ impl QvmSimulator {
             processor,
             noise_model,
             state_vector: None,
-            random_seed: rand::random(),
+            seed: None,
+            rng: StdRng::from_entropy(),
+            max_qubits: MAX_SIMULATED_QUBITS,
+            result_cache: VecDeque::new(),
+            cache_hits: 0,
         }
     }
 
-    /// Get processor info
-    pub fn processor(&self) -> QuantumProcessor {
-        self.processor
+    /// Create simulator from a saved noise model instead of deriving one
+    pub fn new_with_noise_model(processor: QuantumProcessor, noise_model: NoiseModel) -> Self {
+        Self {
+            processor,
+            noise_model,
+            state_vector: None,
+            seed: None,
+            rng: StdRng::from_entropy(),
+            max_qubits: MAX_SIMULATED_QUBITS,
+            result_cache: VecDeque::new(),
+            cache_hits: 0,
+        }
     }
 
-    /// Get noise model
-    pub fn noise_model(&self) -> &NoiseModel {
-        &self.noise_model
+    /// Fix the RNG seed so every run replays the same samples (`None` restores entropy)
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+        self.rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
     }
 
-    /// Initialize state vector for n qubits
-    fn initialize_state(&mut self, n_qubits: usize) {
-        let size = 1 << n_qubits;
+    /// Seeded runs answered from the result cache
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits
+    }
+
+    /// Serve a seeded run from the cache, or execute and remember it
+    /// Unseeded runs are not reproducible and always execute.
+    fn run_cached(
+        &mut self,
+        circuit: &QuantumCircuit,
+        repetitions: usize,
+        kraus_noise: bool,
+        execute: impl FnOnce(&mut Self) -> CircuitResult,
+    ) -> CircuitResult {
+        let Some(seed) = self.seed else {
+            return execute(self);
+        };
+        let key = (circuit.content_hash(), repetitions, seed, kraus_noise);
+
+        if let Some(pos) = self.result_cache.iter().position(|(k, _)| *k == key) {
+            let entry = self.result_cache.remove(pos).expect("position is in range");
+            let mut result = entry.1.clone();
+            self.result_cache.push_back(entry);
+            self.cache_hits += 1;
+            result.circuit_id = circuit.id.clone();
+            return result;
+        }
+
+        // Every seeded run starts from the seed so the cached result is the one it would produce
+        self.rng = StdRng::seed_from_u64(seed);
+        let result = execute(self);
+        if result.error.is_none() {
+            if self.result_cache.len() >= RESULT_CACHE_CAPACITY {
+                self.result_cache.pop_front();
+            }
+            self.result_cache.push_back((key, result.clone()));
+        }
+        result
+    }
+
+    /// Get processor info
+    pub fn processor(&self) -> QuantumProcessor {
+        self.processor
+    }
+
+    /// Get noise model
+    pub fn noise_model(&self) -> &NoiseModel {
+        &self.noise_model
+    }
+
+    /// Initialize state vector for n qubits, refusing widths above `max_qubits`
+    fn initialize_state(&mut self, n_qubits: usize) -> Result<(), String> {
+        if n_qubits > self.max_qubits {
+            self.state_vector = None;
+            return Err(format!(
+                "Circuit uses {} qubits, simulator limit is {}", n_qubits, self.max_qubits,
+            ));
+        }
+        let size = 1 << n_qubits;
         let mut state = vec![Complex::zero(); size];
         state[0] = Complex::one();  // |00...0⟩ state
         self.state_vector = Some(state);
+        Ok(())
+    }
+
+    /// Reset the state vector to |00...0⟩ over n qubits
+    pub fn prepare_state(&mut self, n_qubits: usize) -> Result<(), String> {
+        self.initialize_state(n_qubits)
+    }
+
+    /// Current state vector amplitudes, if initialized
+    pub fn state_vector(&self) -> Option<&[Complex]> {
+        self.state_vector.as_deref()
+    }
+
+    /// Apply an arbitrary unitary to the listed qubits
+    ///
+    /// `matrix` is row-major with dimension 2^k x 2^k for k qubits; `qubits[0]`
+    /// maps to the least significant bit of the matrix index.
+    pub fn apply_unitary(&mut self, qubits: &[usize], matrix: &[Complex]) -> Result<(), String> {
+        let state = self.state_vector.as_mut().ok_or("State not initialized")?;
+        let n = (state.len() as f64).log2() as usize;
+        let k = qubits.len();
+
+        if k == 0 || k > n {
+            return Err(format!("Unitary must target between 1 and {} qubits, got {}", n, k));
+        }
+        let dim = 1usize << k;
+        if matrix.len() != dim * dim {
+            return Err(format!("Expected {}x{} matrix for {} qubits, got {} entries", dim, dim, k, matrix.len()));
+        }
+        for (i, q) in qubits.iter().enumerate() {
+            if *q >= n {
+                return Err(format!("Qubit {} out of range for {}-qubit state", q, n));
+            }
+            if qubits[..i].contains(q) {
+                return Err(format!("Qubit {} listed more than once", q));
+            }
+        }
+
+        let target_mask: usize = qubits.iter().map(|q| 1 << q).sum();
+        let offsets: Vec<usize> = (0..dim)
+            .map(|local| {
+                qubits.iter().enumerate()
+                    .filter(|(bit, _)| local & (1 << bit) != 0)
+                    .map(|(_, q)| 1 << q)
+                    .sum()
+            })
+            .collect();
+
+        let mut amplitudes = vec![Complex::zero(); dim];
+        for base in 0..state.len() {
+            if base & target_mask != 0 {
+                continue;
+            }
+            for (local, offset) in offsets.iter().enumerate() {
+                amplitudes[local] = state[base | offset];
+            }
+            for (row, offset) in offsets.iter().enumerate() {
+                state[base | offset] = (0..dim).fold(Complex::zero(), |acc, col| {
+                    acc.add(&matrix[row * dim + col].mul(&amplitudes[col]))
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exact noiseless output distribution of a circuit (measurements ignored)
+    pub fn ideal_probabilities(&mut self, circuit: &QuantumCircuit) -> Vec<f64> {
+        self.statevector_after(circuit)
+            .iter()
+            .map(|a| a.norm_squared())
+            .collect()
+    }
+
+    /// Noiseless final state vector of a circuit, with measurements skipped
+    /// (empty if the circuit exceeds `max_qubits`)
+    pub fn statevector_after(&mut self, circuit: &QuantumCircuit) -> Vec<Complex> {
+        if self.initialize_state(circuit.qubits.len()).is_err() {
+            return Vec::new();
+        }
+        for gate in circuit.gates.iter().flatten() {
+            if !matches!(gate, QuantumGate::Measure(..)) {
+                self.apply_gate(gate);
+            }
+        }
+        self.state_vector.clone().unwrap_or_default()
     }
 
     /// Run quantum circuit simulation with noise
+    /// Seeded runs are cached by circuit content hash.
     pub fn run(&mut self, circuit: &QuantumCircuit, repetitions: usize) -> CircuitResult {
+        self.run_cached(circuit, repetitions, false, |sim| sim.run_streaming(circuit, repetitions, |_, _| {}))
+    }
+
+    /// Run circuit simulation, reporting progress after each moment
+    ///
+    /// `on_moment` receives the moment index and the cumulative fraction of
+    /// moment executions completed, as each moment finishes. Moments ahead of
+    /// the first measurement are evolved once and shared by every repetition;
+    /// the rest are reported once per repetition.
+    pub fn run_streaming(
+        &mut self,
+        circuit: &QuantumCircuit,
+        repetitions: usize,
+        mut on_moment: impl FnMut(usize, f64),
+    ) -> CircuitResult {
         let start = std::time::Instant::now();
         let n_qubits = circuit.qubits.len();
+        let total_moments = circuit.gates.len();
+        let prefix = circuit.gates.iter()
+            .take_while(|moment| !moment.iter().any(|g| matches!(g, QuantumGate::Measure(..))))
+            .count();
+        let total_executions = prefix + (total_moments - prefix) * repetitions;
+        let mut executed = 0;
+        let mut report = |moment: usize| {
+            executed += 1;
+            on_moment(moment, executed as f64 / total_executions as f64);
+        };
+
+        // Evolve the deterministic prefix once
+        if let Err(error) = self.initialize_state(n_qubits) {
+            return CircuitResult::rejected(circuit, repetitions, error);
+        }
+        for (index, moment) in circuit.gates[..prefix].iter().enumerate() {
+            for gate in moment {
+                self.apply_gate(gate);
+            }
+            report(index);
+        }
+
+        let (histogram, all_measurements) = self.sample(circuit, repetitions, false, prefix, &mut report);
+
+        // Apply noise model to histogram (approximation)
+        let noisy_histogram = self.apply_noise_to_histogram(&histogram, total_moments);
         
-        self.initialize_state(n_qubits);
+        // Estimate fidelity
+        let fidelity = self.estimate_fidelity(total_moments, n_qubits);
+
+        CircuitResult {
+            circuit_id: circuit.id.clone(),
+            repetitions,
+            measurements: all_measurements,
+            histogram: noisy_histogram,
+            execution_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+            fidelity_estimate: fidelity,
+            noise_applied: true,
+            most_probable_outcome: None,
+            entropy_bits: 0.0,
+            error: None,
+        }.with_statistics()
+    }
+
+    /// Run circuit with stochastic depolarizing Kraus noise
+    ///
+    /// Each gate is followed, with the processor's gate error rate, by a random
+    /// non-identity Pauli on the qubits it touched. Averaged over repetitions this
+    /// samples the depolarizing channel, so entanglement degrades physically
+    /// instead of the histogram being rescaled after the fact. Every moment
+    /// also idles its qubits for the moment's gate duration under the noise
+    /// model's T1 amplitude damping and T2 dephasing.
+    pub fn run_with_kraus(&mut self, circuit: &QuantumCircuit, repetitions: usize) -> CircuitResult {
+        self.run_cached(circuit, repetitions, true, |sim| sim.execute_with_kraus(circuit, repetitions))
+    }
+
+    fn execute_with_kraus(&mut self, circuit: &QuantumCircuit, repetitions: usize) -> CircuitResult {
+        let start = std::time::Instant::now();
+        let n_qubits = circuit.qubits.len();
+
+        if let Err(error) = self.initialize_state(n_qubits) {
+            return CircuitResult::rejected(circuit, repetitions, error);
+        }
+        let (histogram, all_measurements) = self.sample(circuit, repetitions, true, 0, &mut |_| {});
+        let fidelity = self.estimate_fidelity(circuit.gates.len(), n_qubits);
+
+        CircuitResult {
+            circuit_id: circuit.id.clone(),
+            repetitions,
+            measurements: all_measurements,
+            histogram,
+            execution_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+            fidelity_estimate: fidelity,
+            noise_applied: true,
+            most_probable_outcome: None,
+            entropy_bits: 0.0,
+            error: None,
+        }.with_statistics()
+    }
+
+    /// Sample measurement outcomes over repetitions, optionally injecting Pauli errors
+    ///
+    /// The current state vector is taken as the state after `start_moment`
+    /// moments and restored before every repetition. `on_moment` is called
+    /// with each moment's index as a repetition finishes it.
+    fn sample(
+        &mut self,
+        circuit: &QuantumCircuit,
+        repetitions: usize,
+        kraus_noise: bool,
+        start_moment: usize,
+        on_moment: &mut dyn FnMut(usize),
+    ) -> (HashMap<u64, usize>, HashMap<String, Vec<u64>>) {
+        let n_qubits = circuit.qubits.len();
+        let prepared_state = self.state_vector.clone();
         
         // Track measurement outcomes
         let mut histogram: HashMap<u64, usize> = HashMap::new();
@@ -1081,21 +2132,20 @@ impl QvmSimulator {
         // Run simulation for each repetition
         for _ in 0..repetitions {
             // Reset state
-            self.initialize_state(n_qubits);
+            self.state_vector.clone_from(&prepared_state);
             
             // Apply gates moment by moment
             let mut measurement_results: Vec<(String, u64)> = Vec::new();
             
-            for moment in &circuit.gates {
+            for (index, moment) in circuit.gates.iter().enumerate().skip(start_moment) {
                 for gate in moment {
-                    match gate {
-                        QuantumGate::Measure(qubit, key) => {
-                            let result = self.measure_qubit(*qubit);
-                            measurement_results.push((key.clone(), result as u64));
-                        }
-                        _ => self.apply_gate(gate),
-                    }
+                    self.execute_gate(gate, &mut measurement_results, kraus_noise, n_qubits);
                 }
+                if kraus_noise {
+                    let duration_ns = self.moment_duration_ns(moment);
+                    self.apply_decoherence(duration_ns, n_qubits);
+                }
+                on_moment(index);
             }
             
             // Record measurements
@@ -1111,21 +2161,150 @@ impl QvmSimulator {
             }
         }
 
-        // Apply noise model to histogram (approximation)
-        let circuit_depth = circuit.gates.len();
-        let noisy_histogram = self.apply_noise_to_histogram(&histogram, circuit_depth);
-        
-        // Estimate fidelity
-        let fidelity = self.estimate_fidelity(circuit_depth, n_qubits);
+        (histogram, all_measurements)
+    }
 
-        CircuitResult {
-            circuit_id: circuit.id.clone(),
-            repetitions,
-            measurements: all_measurements,
-            histogram: noisy_histogram,
-            execution_time_ms: start.elapsed().as_secs_f64() * 1000.0,
-            fidelity_estimate: fidelity,
-            noise_applied: true,
+    /// Apply one gate within a repetition, recording measurements and resolving
+    /// classical control against the bits measured so far
+    fn execute_gate(
+        &mut self,
+        gate: &QuantumGate,
+        measurement_results: &mut Vec<(String, u64)>,
+        kraus_noise: bool,
+        n_qubits: usize,
+    ) {
+        match gate {
+            QuantumGate::Measure(qubit, key) => {
+                let result = self.measure_qubit(*qubit);
+                measurement_results.push((key.clone(), result as u64));
+            }
+            QuantumGate::ClassicalControl { condition_key, expected, gate } => {
+                let fired = measurement_results.iter()
+                    .rev()
+                    .find(|(key, _)| key == condition_key)
+                    .is_some_and(|(_, bit)| *bit == *expected as u64);
+                if fired {
+                    self.execute_gate(gate, measurement_results, kraus_noise, n_qubits);
+                }
+            }
+            _ => {
+                self.apply_gate(gate);
+                if kraus_noise {
+                    self.apply_depolarizing_error(gate, n_qubits);
+                }
+            }
+        }
+    }
+
+    /// Stochastically apply a depolarizing Kraus operator after a gate
+    /// With the gate's error rate, a uniformly random non-identity Pauli
+    /// (3 single-qubit or 15 two-qubit choices) hits the gate's qubits.
+    /// iSWAP-family gates use the calibrated FSim error instead of the CZ rate.
+    fn apply_depolarizing_error(&mut self, gate: &QuantumGate, n_qubits: usize) {
+        match gate {
+            QuantumGate::CZ(q1, q2)
+            | QuantumGate::CNOT(q1, q2)
+            | QuantumGate::ISWAP(q1, q2)
+            | QuantumGate::SqrtISWAP(q1, q2)
+            | QuantumGate::CPhase(q1, q2, _) => {
+                let family = TwoQubitGateFamily::of(gate).unwrap_or(TwoQubitGateFamily::Cz);
+                if self.rng.gen::<f64>() < self.noise_model.two_qubit_error(family) {
+                    // Index 1..16 encodes (pauli_q1, pauli_q2) in base 4, skipping I⊗I
+                    let pauli = self.rng.gen_range(1..16);
+                    self.apply_pauli(*q1, pauli % 4, n_qubits);
+                    self.apply_pauli(*q2, pauli / 4, n_qubits);
+                }
+            }
+            QuantumGate::Measure(..) | QuantumGate::ClassicalControl { .. } => {}
+            QuantumGate::X(q)
+            | QuantumGate::Y(q)
+            | QuantumGate::Z(q)
+            | QuantumGate::H(q)
+            | QuantumGate::S(q)
+            | QuantumGate::T(q)
+            | QuantumGate::Rx(q, _)
+            | QuantumGate::Ry(q, _)
+            | QuantumGate::Rz(q, _) => {
+                if self.rng.gen::<f64>() < self.processor.single_qubit_error_rate() {
+                    let pauli = self.rng.gen_range(1..4);
+                    self.apply_pauli(*q, pauli, n_qubits);
+                }
+            }
+        }
+    }
+
+    /// Wall-clock length of a moment: its slowest gate per `gate_durations_ns`
+    fn moment_duration_ns(&self, moment: &[QuantumGate]) -> f64 {
+        let durations = &self.noise_model.gate_durations_ns;
+        moment.iter()
+            .map(|gate| {
+                let kind = match gate {
+                    QuantumGate::Measure(..) => "measure",
+                    _ if two_qubit_operands(gate).is_some() => "cz",
+                    _ => "single",
+                };
+                durations.get(kind).copied().unwrap_or(0.0)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Stochastically apply T1/T2 decoherence for `duration_ns` to every qubit
+    ///
+    /// Amplitude damping is unravelled as a quantum jump: with probability
+    /// γ·P(|1⟩) the qubit decays to |0⟩, otherwise the no-jump operator
+    /// diag(1, √(1-γ)) shrinks the excited amplitudes. Dephasing beyond the
+    /// T1 limit is a Z flip with probability (1 - e^(-t/Tφ))/2.
+    fn apply_decoherence(&mut self, duration_ns: f64, n_qubits: usize) {
+        if duration_ns <= 0.0 {
+            return;
+        }
+        let t_us = duration_ns / 1000.0;
+        let gamma = 1.0 - (-self.noise_model.amplitude_damping_rate * t_us).exp();
+        let dephasing_rate = (self.noise_model.phase_damping_rate
+            - self.noise_model.amplitude_damping_rate / 2.0).max(0.0);
+        let p_phase_flip = (1.0 - (-dephasing_rate * t_us).exp()) / 2.0;
+
+        for qubit in 0..n_qubits {
+            let state = self.state_vector.as_mut().unwrap();
+            let mask = 1 << qubit;
+            let prob_one: f64 = state.iter()
+                .enumerate()
+                .filter(|(i, _)| i & mask != 0)
+                .map(|(_, a)| a.norm_squared())
+                .sum();
+
+            let p_jump = gamma * prob_one;
+            if p_jump > MEASUREMENT_EPSILON && self.rng.gen::<f64>() < p_jump {
+                // Decay: |..1..⟩ amplitudes move onto |..0..⟩
+                let norm = 1.0 / prob_one.sqrt();
+                for i in 0..state.len() {
+                    if i & mask == 0 {
+                        state[i] = state[i | mask].scale(norm);
+                        state[i | mask] = Complex::zero();
+                    }
+                }
+            } else {
+                let damp = (1.0 - gamma).sqrt();
+                let norm = 1.0 / (1.0 - p_jump).sqrt();
+                for (i, amp) in state.iter_mut().enumerate() {
+                    let factor = if i & mask != 0 { damp * norm } else { norm };
+                    *amp = amp.scale(factor);
+                }
+            }
+
+            if self.rng.gen::<f64>() < p_phase_flip {
+                self.apply_z(qubit, n_qubits);
+            }
+        }
+    }
+
+    /// Apply Pauli by index: 0 = I, 1 = X, 2 = Y, 3 = Z
+    fn apply_pauli(&mut self, qubit: usize, pauli: usize, n_qubits: usize) {
+        match pauli {
+            1 => self.apply_x(qubit, n_qubits),
+            2 => self.apply_y(qubit, n_qubits),
+            3 => self.apply_z(qubit, n_qubits),
+            _ => {}
         }
     }
 
@@ -1139,9 +2318,84 @@ impl QvmSimulator {
             QuantumGate::Y(q) => self.apply_y(*q, n),
             QuantumGate::Z(q) => self.apply_z(*q, n),
             QuantumGate::H(q) => self.apply_h(*q, n),
+            QuantumGate::S(q) => self.apply_phase(*q, n, std::f64::consts::FRAC_PI_2),
+            QuantumGate::T(q) => self.apply_phase(*q, n, std::f64::consts::FRAC_PI_4),
+            QuantumGate::Rx(q, theta) => {
+                let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+                self.apply_single_qubit_matrix(*q, n, [
+                    Complex::new(c, 0.0), Complex::new(0.0, -s),
+                    Complex::new(0.0, -s), Complex::new(c, 0.0),
+                ]);
+            }
+            QuantumGate::Ry(q, theta) => {
+                let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+                self.apply_single_qubit_matrix(*q, n, [
+                    Complex::new(c, 0.0), Complex::new(-s, 0.0),
+                    Complex::new(s, 0.0), Complex::new(c, 0.0),
+                ]);
+            }
+            QuantumGate::Rz(q, theta) => {
+                let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+                self.apply_single_qubit_matrix(*q, n, [
+                    Complex::new(c, -s), Complex::zero(),
+                    Complex::zero(), Complex::new(c, s),
+                ]);
+            }
             QuantumGate::CZ(q1, q2) => self.apply_cz(*q1, *q2, n),
             QuantumGate::CNOT(q1, q2) => self.apply_cnot(*q1, *q2, n),
-            _ => {} // Other gates simplified for prototype
+            QuantumGate::ISWAP(q1, q2) => self.apply_iswap_power(*q1, *q2, n, std::f64::consts::FRAC_PI_2),
+            QuantumGate::SqrtISWAP(q1, q2) => self.apply_iswap_power(*q1, *q2, n, std::f64::consts::FRAC_PI_4),
+            QuantumGate::CPhase(q1, q2, theta) => self.apply_cphase(*q1, *q2, *theta),
+            QuantumGate::Measure(..) | QuantumGate::ClassicalControl { .. } => {} // Handled by run()
+        }
+    }
+
+    /// Apply a 2x2 unitary [m00, m01, m10, m11] to one qubit
+    fn apply_single_qubit_matrix(&mut self, qubit: usize, n_qubits: usize, m: [Complex; 4]) {
+        let state = self.state_vector.as_mut().unwrap();
+        let mask = 1 << qubit;
+
+        for i in 0..(1 << n_qubits) {
+            if i & mask == 0 {
+                let j = i | mask;
+                let a = state[i];
+                let b = state[j];
+                state[i] = m[0].mul(&a).add(&m[1].mul(&b));
+                state[j] = m[2].mul(&a).add(&m[3].mul(&b));
+            }
+        }
+    }
+
+    /// Apply phase gate diag(1, e^{iφ}) (S for φ=π/2, T for φ=π/4)
+    fn apply_phase(&mut self, qubit: usize, n_qubits: usize, phi: f64) {
+        let state = self.state_vector.as_mut().unwrap();
+        let mask = 1 << qubit;
+        let phase = Complex::new(phi.cos(), phi.sin());
+
+        for i in 0..(1 << n_qubits) {
+            if i & mask != 0 {
+                state[i] = state[i].mul(&phase);
+            }
+        }
+    }
+
+    /// Apply iSWAP-family gate: rotates |01⟩/|10⟩ by [[cos θ, i sin θ], [i sin θ, cos θ]]
+    /// θ = π/2 gives iSWAP, θ = π/4 gives √iSWAP
+    fn apply_iswap_power(&mut self, q1: usize, q2: usize, n_qubits: usize, theta: f64) {
+        let state = self.state_vector.as_mut().unwrap();
+        let mask1 = 1 << q1;
+        let mask2 = 1 << q2;
+        let (c, s) = (theta.cos(), theta.sin());
+
+        for i in 0..(1 << n_qubits) {
+            // Visit each |..0_q1..1_q2..⟩ / |..1_q1..0_q2..⟩ pair once
+            if i & mask1 != 0 && i & mask2 == 0 {
+                let j = (i & !mask1) | mask2;
+                let a = state[i];
+                let b = state[j];
+                state[i] = a.scale(c).add(&Complex::new(-b.imag * s, b.real * s));
+                state[j] = b.scale(c).add(&Complex::new(-a.imag * s, a.real * s));
+            }
         }
     }
 
@@ -1198,7 +2452,15 @@ impl QvmSimulator {
                 let a = state[i];
                 let b = state[j];
                 state[i] = a.add(&b).scale(inv_sqrt2);
-                state[j] = a.add(&b.scale(-1.0)).scale(inv_sqrt2);
+                #[cfg(not(feature = "qvm-fault-injection"))]
+                {
+                    state[j] = a.add(&b.scale(-1.0)).scale(inv_sqrt2);
+                }
+                // Deliberately wrong sign so the golden self-test can be shown to fail
+                #[cfg(feature = "qvm-fault-injection")]
+                {
+                    state[j] = a.add(&b).scale(inv_sqrt2);
+                }
             }
         }
     }
@@ -1216,6 +2478,19 @@ impl QvmSimulator {
         }
     }
 
+    /// Apply controlled-phase gate: |11⟩ picks up e^(iθ)
+    fn apply_cphase(&mut self, q1: usize, q2: usize, theta: f64) {
+        let state = self.state_vector.as_mut().unwrap();
+        let mask = (1 << q1) | (1 << q2);
+        let phase = Complex::new(theta.cos(), theta.sin());
+
+        for (i, amp) in state.iter_mut().enumerate() {
+            if i & mask == mask {
+                *amp = amp.mul(&phase);
+            }
+        }
+    }
+
     /// Apply CNOT gate
     fn apply_cnot(&mut self, control: usize, target: usize, n_qubits: usize) {
         let state = self.state_vector.as_mut().unwrap();
@@ -1244,28 +2519,41 @@ impl QvmSimulator {
             }
         }
         
-        // Apply readout noise
-        let noisy_prob = self.noise_model.apply_noise(prob_one, 1);
-        
-        // Random measurement outcome
-        let outcome = if rand::random::<f64>() < noisy_prob { 1 } else { 0 };
-        
-        // Collapse state
-        let norm_factor = if outcome == 1 { 
-            1.0 / prob_one.sqrt() 
-        } else { 
-            1.0 / (1.0 - prob_one).sqrt() 
+        let prob_one = prob_one.clamp(0.0, 1.0);
+
+        // Physical collapse: sample from the ideal distribution. Branches with
+        // near-zero probability are never selected, so renormalization is safe.
+        let physical = if prob_one < MEASUREMENT_EPSILON {
+            0
+        } else if prob_one > 1.0 - MEASUREMENT_EPSILON || self.rng.gen::<f64>() < prob_one {
+            1
+        } else {
+            0
         };
-        
+
+        let branch_prob = if physical == 1 { prob_one } else { 1.0 - prob_one };
+        let norm_factor = if branch_prob > 1.0 - MEASUREMENT_EPSILON {
+            1.0
+        } else {
+            1.0 / branch_prob.sqrt()
+        };
+
         for i in 0..(1 << n) {
-            if (i & mask != 0) != (outcome == 1) {
+            if (i & mask != 0) != (physical == 1) {
                 state[i] = Complex::zero();
             } else {
                 state[i] = state[i].scale(norm_factor);
             }
         }
-        
-        outcome
+
+        // Readout noise flips the reported bit only, never the collapsed state
+        let (decay, excitation) = self.noise_model.readout_error(qubit);
+        let flip_prob = if physical == 1 { decay } else { excitation };
+        if self.rng.gen::<f64>() < flip_prob {
+            1 - physical
+        } else {
+            physical
+        }
     }
 
     /// Apply noise to histogram
@@ -1299,6 +2587,13 @@ impl QvmSimulator {
         
         single_q_fidelity * two_q_fidelity * readout_fidelity
     }
+
+    /// Estimated fidelity at each depth in 1..=max_depth for an n-qubit circuit
+    pub fn fidelity_curve(&self, n_qubits: usize, max_depth: usize) -> Vec<(usize, f64)> {
+        (1..=max_depth)
+            .map(|depth| (depth, self.estimate_fidelity(depth, n_qubits)))
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -1319,8 +2614,9 @@ pub struct GroverThreatAssessment {
     pub noise_adjusted: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ThreatLevel {
+    #[default]
     None,           // No realistic threat
     Theoretical,    // Possible in theory
     LongTerm,       // Possible with future QC (>10 years)
@@ -1329,6 +2625,30 @@ pub enum ThreatLevel {
     Imminent,       // Possible with current technology
 }
 
+impl ThreatLevel {
+    /// Risk score in basis points (0-10000), the scale QRM scores use
+    pub fn as_score(&self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Theoretical => 1000,
+            Self::LongTerm => 3000,
+            Self::MediumTerm => 5000,
+            Self::NearTerm => 7500,
+            Self::Imminent => 10000,
+        }
+    }
+
+    /// Highest level whose score does not exceed `severity` (0.0 - 1.0),
+    /// so `from_severity(level.as_score() as f64 / 10000.0) == level`
+    pub fn from_severity(severity: f64) -> Self {
+        let score = (severity.clamp(0.0, 1.0) * 10000.0).round() as u32;
+        [Self::Imminent, Self::NearTerm, Self::MediumTerm, Self::LongTerm, Self::Theoretical]
+            .into_iter()
+            .find(|level| score >= level.as_score())
+            .unwrap_or(Self::None)
+    }
+}
+
 /// Shor's algorithm threat assessment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShorThreatAssessment {
@@ -1340,15 +2660,118 @@ pub struct ShorThreatAssessment {
     pub error_correction_overhead: f64,
     pub estimated_time_hours: f64,         // With fault-tolerant QC
     pub threat_level: ThreatLevel,
+    /// Explanation when no resource estimate applies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Threat level of one assessed primitive in two assessments
+/// `None` means the primitive was not assessed on that side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreatLevelChange {
+    pub algorithm: String,
+    /// "grover" or "shor"
+    pub attack: String,
+    pub from: Option<ThreatLevel>,
+    pub to: Option<ThreatLevel>,
+}
+
+/// What changed between two oracle assessments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssessmentDiff {
+    pub from_timestamp: DateTime<Utc>,
+    pub to_timestamp: DateTime<Utc>,
+    /// Composite risk change in basis points (positive = worse)
+    pub composite_risk_delta: i64,
+    /// Recommended era (from, to) when it changed
+    pub era_change: Option<(QuantumEra, QuantumEra)>,
+    /// Primitives whose threat level rose
+    pub escalated: Vec<ThreatLevelChange>,
+    /// Primitives whose threat level fell, appeared or disappeared
+    pub relaxed: Vec<ThreatLevelChange>,
 }
 
+/// Default number of oracle assessments retained in history
+pub const DEFAULT_ORACLE_HISTORY: usize = 256;
+
 /// QVM Oracle for cryptographic threat analysis
 pub struct QvmOracle {
     simulator: QvmSimulator,
-    threat_history: Vec<OracleAssessment>,
+    /// Bounded ring buffer of past assessments, oldest first
+    threat_history: VecDeque<OracleAssessment>,
+    max_history: usize,
     last_calibration: DateTime<Utc>,
+    /// Symmetric primitives (name, security bits); empty uses the defaults
+    grover_targets: Vec<(String, usize)>,
+    /// Public key primitives (name, key bits); empty uses the defaults
+    shor_targets: Vec<(String, usize)>,
+    recommendation_policy: RecommendationPolicy,
+}
+
+/// Algorithms recommended once composite risk exceeds `above`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskBand {
+    pub above: u32,
+    pub algorithms: Vec<String>,
+}
+
+/// Maps composite risk bands to recommended algorithm sets
+/// Lets a deployment encode its own migration playbook; one policy covers the whole stack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendationPolicy {
+    /// Bands in any order; the highest `above` below the risk wins
+    pub bands: Vec<RiskBand>,
+    /// Recommendation when risk does not exceed any band
+    pub baseline: Vec<String>,
+}
+
+impl Default for RecommendationPolicy {
+    fn default() -> Self {
+        let names = |algos: &[&str]| algos.iter().map(|a| a.to_string()).collect();
+        Self {
+            bands: vec![RiskBand {
+                above: 5000,
+                algorithms: names(&["ML-DSA-87", "SLH-DSA-256s", "ML-KEM-1024", "Hybrid-ECDSA-ML-DSA"]),
+            }],
+            baseline: names(&["ECDSA-secp256k1", "Ed25519", "BLS12-381"]),
+        }
+    }
 }
 
+impl RecommendationPolicy {
+    /// Algorithms recommended at the given composite risk (basis points)
+    pub fn recommend(&self, composite_risk: u32) -> Vec<String> {
+        self.bands.iter()
+            .filter(|band| composite_risk > band.above)
+            .max_by_key(|band| band.above)
+            .map(|band| band.algorithms.clone())
+            .unwrap_or_else(|| self.baseline.clone())
+    }
+}
+
+/// Default symmetric primitives assessed for Grover threat
+const DEFAULT_GROVER_TARGETS: &[(&str, usize)] = &[
+    ("AES-128", 128),
+    ("AES-256", 256),
+    ("SHA-256", 256),
+    ("Keccak-256", 256),
+];
+
+/// Default public key primitives assessed for Shor threat
+const DEFAULT_SHOR_TARGETS: &[(&str, usize)] = &[
+    ("RSA-2048", 2048),
+    ("RSA-4096", 4096),
+    ("ECDSA-secp256k1", 256),
+    ("ECDSA-P384", 384),
+    ("Ed25519", 256),
+    ("BLS12-381", 381),
+];
+
+/// NIST PQC families (and their pre-standard names) that Shor's algorithm does not break
+const PQC_ALGORITHM_FAMILIES: &[&str] = &[
+    "ML-DSA", "ML-KEM", "SLH-DSA", "HQC", "FALCON", "FN-DSA", "DILITHIUM", "KYBER", "SPHINCS",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OracleAssessment {
     pub timestamp: DateTime<Utc>,
@@ -1359,13 +2782,100 @@ pub struct OracleAssessment {
     pub recommended_algorithms: Vec<String>,
 }
 
+impl OracleAssessment {
+    /// Threat levels keyed by (attack, algorithm)
+    fn threat_levels(&self) -> Vec<((&'static str, &str), ThreatLevel)> {
+        let grover = self.grover_assessments.iter()
+            .map(|g| (("grover", g.target_algorithm.as_str()), g.threat_level));
+        let shor = self.shor_assessments.iter()
+            .map(|s| (("shor", s.target_algorithm.as_str()), s.threat_level));
+        grover.chain(shor).collect()
+    }
+
+    /// Changes from this assessment to a later one
+    pub fn diff(&self, other: &OracleAssessment) -> AssessmentDiff {
+        let before: HashMap<_, _> = self.threat_levels().into_iter().collect();
+        let after = other.threat_levels();
+
+        let mut keys: Vec<(&str, &str)> = after.iter().map(|(key, _)| *key).collect();
+        keys.extend(self.threat_levels().into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| !after.iter().any(|(k, _)| k == key)));
+        let after: HashMap<_, _> = after.into_iter().collect();
+
+        let mut escalated = Vec::new();
+        let mut relaxed = Vec::new();
+        for (attack, algorithm) in keys {
+            let from = before.get(&(attack, algorithm)).copied();
+            let to = after.get(&(attack, algorithm)).copied();
+            if from == to {
+                continue;
+            }
+            let change = ThreatLevelChange {
+                algorithm: algorithm.to_string(),
+                attack: attack.to_string(),
+                from,
+                to,
+            };
+            match (from, to) {
+                (Some(f), Some(t)) if t.as_score() > f.as_score() => escalated.push(change),
+                _ => relaxed.push(change),
+            }
+        }
+
+        AssessmentDiff {
+            from_timestamp: self.timestamp,
+            to_timestamp: other.timestamp,
+            composite_risk_delta: other.composite_risk as i64 - self.composite_risk as i64,
+            era_change: (self.recommended_era != other.recommended_era)
+                .then_some((self.recommended_era, other.recommended_era)),
+            escalated,
+            relaxed,
+        }
+    }
+}
+
 impl QvmOracle {
     /// Create QVM Oracle with specified processor
     pub fn new(processor: QuantumProcessor) -> Self {
         Self {
             simulator: QvmSimulator::new(processor),
-            threat_history: Vec::new(),
+            threat_history: VecDeque::new(),
+            max_history: DEFAULT_ORACLE_HISTORY,
             last_calibration: Utc::now(),
+            grover_targets: Vec::new(),
+            shor_targets: Vec::new(),
+            recommendation_policy: RecommendationPolicy::default(),
+        }
+    }
+
+    /// Replace the risk-to-algorithm recommendation policy
+    pub fn set_recommendation_policy(&mut self, policy: RecommendationPolicy) {
+        self.recommendation_policy = policy;
+    }
+
+    /// Configure the primitives assessed by `perform_assessment`
+    /// An empty list falls back to the built-in defaults for that family
+    pub fn set_assessment_targets(&mut self, grover: Vec<(String, usize)>, shor: Vec<(String, usize)>) {
+        self.grover_targets = grover;
+        self.shor_targets = shor;
+    }
+
+    /// Effective Grover targets, resolving defaults
+    fn grover_targets(&self) -> Vec<(String, usize)> {
+        if self.grover_targets.is_empty() {
+            DEFAULT_GROVER_TARGETS.iter().map(|(name, bits)| (name.to_string(), *bits)).collect()
+        } else {
+            self.grover_targets.clone()
+        }
+    }
+
+    /// Effective Shor targets, resolving defaults
+    fn shor_targets(&self) -> Vec<(String, usize)> {
+        if self.shor_targets.is_empty() {
+            DEFAULT_SHOR_TARGETS.iter().map(|(name, bits)| (name.to_string(), *bits)).collect()
+        } else {
+            self.shor_targets.clone()
         }
     }
 
@@ -1407,7 +2917,10 @@ impl QvmOracle {
         
         // Time estimation (assuming 1000 gates/second with error correction)
         let gates_per_second = 1000.0 / (self.simulator.noise_model().gate_durations_ns["cz"] * 1e-9);
-        let total_gates_f64 = grover_iterations_f64 * (logical_qubits * 10) as f64; // Rough estimate
+        // Gates per Grover iteration, taken from the constructed circuit
+        let gates_per_iteration = build_grover_circuit(logical_qubits, 1).gate_count()
+            - build_grover_circuit(logical_qubits, 0).gate_count();
+        let total_gates_f64 = grover_iterations_f64 * gates_per_iteration as f64;
         let time_seconds = total_gates_f64 / gates_per_second;
         let time_years = time_seconds / (365.25 * 24.0 * 3600.0);
         
@@ -1445,6 +2958,21 @@ impl QvmOracle {
         algorithm: &str,
         key_bits: usize,
     ) -> ShorThreatAssessment {
+        let upper = algorithm.to_ascii_uppercase();
+        if PQC_ALGORITHM_FAMILIES.iter().any(|family| upper.contains(family)) {
+            return ShorThreatAssessment {
+                target_algorithm: algorithm.to_string(),
+                key_bits,
+                required_logical_qubits: 0,
+                required_t_gates: 0,
+                required_physical_qubits: 0,
+                error_correction_overhead: 0.0,
+                estimated_time_hours: 0.0,
+                threat_level: ThreatLevel::None,
+                note: Some("post-quantum algorithm: Shor's algorithm does not apply".to_string()),
+            };
+        }
+
         // Shor's algorithm qubit requirements
         let (logical_qubits, t_gates) = match algorithm {
             algo if algo.contains("RSA") => {
@@ -1498,36 +3026,31 @@ impl QvmOracle {
             error_correction_overhead: physical_per_logical as f64,
             estimated_time_hours: total_time_hours,
             threat_level,
+            note: None,
         }
     }
 
     /// Perform full oracle assessment
     pub fn perform_assessment(&mut self) -> OracleAssessment {
-        let mut grover_assessments = Vec::new();
-        let mut shor_assessments = Vec::new();
-        
-        // Assess common cryptographic primitives
         // Symmetric algorithms (Grover threat)
-        grover_assessments.push(self.assess_grover_threat("AES-128", 128));
-        grover_assessments.push(self.assess_grover_threat("AES-256", 256));
-        grover_assessments.push(self.assess_grover_threat("SHA-256", 256));
-        grover_assessments.push(self.assess_grover_threat("Keccak-256", 256));
+        let grover_assessments: Vec<GroverThreatAssessment> = self.grover_targets()
+            .iter()
+            .map(|(algorithm, bits)| self.assess_grover_threat(algorithm, *bits))
+            .collect();
         
         // Public key algorithms (Shor threat)
-        shor_assessments.push(self.assess_shor_threat("RSA-2048", 2048));
-        shor_assessments.push(self.assess_shor_threat("RSA-4096", 4096));
-        shor_assessments.push(self.assess_shor_threat("ECDSA-secp256k1", 256));
-        shor_assessments.push(self.assess_shor_threat("ECDSA-P384", 384));
-        shor_assessments.push(self.assess_shor_threat("Ed25519", 256));
-        shor_assessments.push(self.assess_shor_threat("BLS12-381", 381));
+        let shor_assessments: Vec<ShorThreatAssessment> = self.shor_targets()
+            .iter()
+            .map(|(algorithm, bits)| self.assess_shor_threat(algorithm, *bits))
+            .collect();
         
         // Calculate composite risk
         let max_shor_threat = shor_assessments.iter()
-            .map(|a| threat_level_to_score(a.threat_level))
+            .map(|a| a.threat_level.as_score())
             .max()
             .unwrap_or(0);
         let max_grover_threat = grover_assessments.iter()
-            .map(|a| threat_level_to_score(a.threat_level))
+            .map(|a| a.threat_level.as_score())
             .max()
             .unwrap_or(0);
         
@@ -1544,20 +3067,7 @@ impl QvmOracle {
         };
         
         // Recommend algorithms based on threat level
-        let recommended_algorithms = if composite_risk > 5000 {
-            vec![
-                "ML-DSA-87".to_string(),
-                "SLH-DSA-256s".to_string(),
-                "ML-KEM-1024".to_string(),
-                "Hybrid-ECDSA-ML-DSA".to_string(),
-            ]
-        } else {
-            vec![
-                "ECDSA-secp256k1".to_string(),
-                "Ed25519".to_string(),
-                "BLS12-381".to_string(),
-            ]
-        };
+        let recommended_algorithms = self.recommendation_policy.recommend(composite_risk);
         
         let assessment = OracleAssessment {
             timestamp: Utc::now(),
@@ -1568,25 +3078,56 @@ impl QvmOracle {
             recommended_algorithms,
         };
         
-        self.threat_history.push(assessment.clone());
+        self.threat_history.push_back(assessment.clone());
+        while self.threat_history.len() > self.max_history {
+            self.threat_history.pop_front();
+        }
         assessment
     }
 
-    /// Get threat history
-    pub fn get_threat_history(&self) -> &[OracleAssessment] {
+    /// Get threat history, oldest first
+    pub fn get_threat_history(&self) -> &VecDeque<OracleAssessment> {
         &self.threat_history
     }
-}
 
-fn threat_level_to_score(level: ThreatLevel) -> u32 {
-    match level {
-        ThreatLevel::None => 0,
-        ThreatLevel::Theoretical => 1000,
-        ThreatLevel::LongTerm => 3000,
-        ThreatLevel::MediumTerm => 5000,
-        ThreatLevel::NearTerm => 7500,
-        ThreatLevel::Imminent => 10000,
+    /// Maximum number of assessments retained
+    pub fn max_history(&self) -> usize {
+        self.max_history
+    }
+
+    /// Change the history cap (minimum 1), evicting the oldest entries if needed
+    pub fn set_max_history(&mut self, max_history: usize) {
+        self.max_history = max_history.max(1);
+        while self.threat_history.len() > self.max_history {
+            self.threat_history.pop_front();
+        }
     }
+
+    /// Page through history newest first, skipping `offset` assessments
+    pub fn get_history_page(&self, offset: usize, limit: usize) -> Vec<OracleAssessment> {
+        self.threat_history.iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Run the same oracle assessment on each processor for side-by-side comparison
+/// `algos` are the public key primitives assessed for Shor threat (empty uses
+/// the defaults); Grover targets keep their defaults.
+pub fn compare_processors(
+    algos: &[(String, usize)],
+    processors: &[QuantumProcessor],
+) -> HashMap<QuantumProcessor, OracleAssessment> {
+    processors.iter()
+        .map(|&processor| {
+            let mut oracle = QvmOracle::new(processor);
+            oracle.set_assessment_targets(Vec::new(), algos.to_vec());
+            (processor, oracle.perform_assessment())
+        })
+        .collect()
 }
 
 // ============================================================================
@@ -1598,6 +3139,7 @@ fn threat_level_to_score(level: ThreatLevel) -> u32 {
 pub struct QvmProtocolStack {
     // Core components
     pub oracle: QvmOracle,
+    pub qubit_picker: QubitPicker,
     pub qrm: QuantumResistanceMonitor,
     pub apqc: AdaptivePqcLayer,
     
@@ -1605,6 +3147,8 @@ pub struct QvmProtocolStack {
     pub current_era: QuantumEra,
     pub threat_indicators: Vec<ThreatIndicator>,
     pub last_assessment: Option<OracleAssessment>,
+    /// Candidate era awaiting confirmation and how many assessments it has held
+    pending_era: Option<(QuantumEra, u32)>,
     
     // Configuration
     pub config: QvmConfig,
@@ -1624,6 +3168,15 @@ pub struct QvmConfig {
     pub risk_threshold_scheduled: u32,
     pub enable_quantum_circuits: bool,
     pub simulation_repetitions: usize,
+    /// Confidence assigned to indicators derived from Shor assessments (0.0 - 1.0)
+    pub shor_indicator_confidence: f64,
+    /// Confidence assigned to indicators derived from Grover assessments (0.0 - 1.0)
+    pub grover_indicator_confidence: f64,
+    /// Consecutive assessments a new recommended era must hold before it is committed
+    pub confirmation_blocks: u32,
+    /// Risk bands mapped to the algorithms each assessment recommends
+    #[serde(default)]
+    pub recommendation_policy: RecommendationPolicy,
 }
 
 impl Default for QvmConfig {
@@ -1636,6 +3189,10 @@ impl Default for QvmConfig {
             risk_threshold_scheduled: 6000,
             enable_quantum_circuits: true,
             simulation_repetitions: 3000,
+            shor_indicator_confidence: 0.85,
+            grover_indicator_confidence: 0.75,
+            confirmation_blocks: 3,
+            recommendation_policy: RecommendationPolicy::default(),
         }
     }
 }
@@ -1643,34 +3200,63 @@ impl Default for QvmConfig {
 impl QvmProtocolStack {
     /// Create new QVM Protocol Stack
     pub fn new(config: QvmConfig) -> Self {
-        let oracle = QvmOracle::new(config.processor);
+        let mut oracle = QvmOracle::new(config.processor);
+        oracle.set_recommendation_policy(config.recommendation_policy.clone());
         
         Self {
             oracle,
+            qubit_picker: QubitPicker::new(config.processor),
             qrm: QuantumResistanceMonitor::new(),
             apqc: AdaptivePqcLayer::new(),
             current_era: QuantumEra::PreQuantum,
             threat_indicators: Vec::new(),
             last_assessment: None,
+            pending_era: None,
             config,
             assessments_count: 0,
             era_transitions: Vec::new(),
         }
     }
 
+    /// Track a recommended era, committing it once it holds for `confirmation_blocks` assessments
+    fn observe_recommended_era(&mut self, recommended: QuantumEra) {
+        if recommended == self.current_era {
+            self.pending_era = None;
+            return;
+        }
+
+        let held = match self.pending_era {
+            Some((era, count)) if era == recommended => count + 1,
+            _ => 1,
+        };
+
+        if held >= self.config.confirmation_blocks.max(1) {
+            let old_era = self.current_era;
+            self.current_era = recommended;
+            self.era_transitions.push((Utc::now(), old_era, self.current_era));
+            self.pending_era = None;
+            
+            // Update QRM era
+            self.qrm.current_era = self.current_era;
+        } else {
+            self.pending_era = Some((recommended, held));
+        }
+    }
+
+    /// Replace the recommendation policy consulted by future assessments
+    pub fn set_recommendation_policy(&mut self, policy: RecommendationPolicy) {
+        self.oracle.set_recommendation_policy(policy.clone());
+        self.config.recommendation_policy = policy;
+    }
+
     /// Perform quantum oracle assessment and update QRMS
     pub fn assess_and_update(&mut self) -> RiskAssessment {
         // Perform QVM oracle assessment
         let oracle_assessment = self.oracle.perform_assessment();
         
         // Check for era transition
-        if self.config.auto_era_transition && oracle_assessment.recommended_era != self.current_era {
-            let old_era = self.current_era;
-            self.current_era = oracle_assessment.recommended_era;
-            self.era_transitions.push((Utc::now(), old_era, self.current_era));
-            
-            // Update QRM era
-            self.qrm.current_era = self.current_era;
+        if self.config.auto_era_transition {
+            self.observe_recommended_era(oracle_assessment.recommended_era);
         }
         
         // Generate threat indicators from oracle assessment
@@ -1687,6 +3273,9 @@ impl QvmProtocolStack {
 
     /// Generate threat indicators from oracle assessment
     fn generate_threat_indicators(&mut self, assessment: &OracleAssessment) {
+        let shor_confidence = self.config.shor_indicator_confidence.clamp(0.0, 1.0);
+        let grover_confidence = self.config.grover_indicator_confidence.clamp(0.0, 1.0);
+
         // Convert Shor assessments to threat indicators
         for shor in &assessment.shor_assessments {
             if shor.threat_level != ThreatLevel::None && shor.threat_level != ThreatLevel::Theoretical {
@@ -1699,8 +3288,8 @@ impl QvmProtocolStack {
                         ThreatCategory::KeyManagement
                     },
                     sub_category: shor.target_algorithm.clone(),
-                    severity: threat_level_to_score(shor.threat_level) as f64 / 10000.0,
-                    confidence: 0.85,
+                    severity: shor.threat_level.as_score() as f64 / 10000.0,
+                    confidence: shor_confidence,
                     source: format!("QVM Oracle ({})", self.oracle.simulator().processor().processor_id()),
                     timestamp: Utc::now(),
                     description: format!(
@@ -1713,6 +3302,8 @@ impl QvmProtocolStack {
                         "https://arxiv.org/abs/quant-ph/9508027".to_string(),
                         "NIST PQC Standardization".to_string(),
                     ],
+                    tags: vec![],
+                    expires_at: None,
                 };
                 self.qrm.add_indicator(indicator.clone());
                 self.threat_indicators.push(indicator);
@@ -1729,8 +3320,8 @@ impl QvmProtocolStack {
                         ThreatCategory::DecryptionHndl
                     },
                     sub_category: grover.target_algorithm.clone(),
-                    severity: threat_level_to_score(grover.threat_level) as f64 / 10000.0,
-                    confidence: 0.75,
+                    severity: grover.threat_level.as_score() as f64 / 10000.0,
+                    confidence: grover_confidence,
                     source: format!("QVM Oracle ({})", self.oracle.simulator().processor().processor_id()),
                     timestamp: Utc::now(),
                     description: format!(
@@ -1742,6 +3333,8 @@ impl QvmProtocolStack {
                     references: vec![
                         "https://arxiv.org/abs/quant-ph/9605043".to_string(),
                     ],
+                    tags: vec![],
+                    expires_at: None,
                 };
                 self.qrm.add_indicator(indicator.clone());
                 self.threat_indicators.push(indicator);
@@ -1749,6 +3342,18 @@ impl QvmProtocolStack {
         }
     }
 
+    /// Validate and run a user-supplied circuit against the noise model
+    pub fn run_custom_circuit(&mut self, circuit: &QuantumCircuit, repetitions: usize) -> Result<CircuitResult, String> {
+        if !self.config.enable_quantum_circuits {
+            return Err("Quantum circuits are disabled".to_string());
+        }
+
+        let max_qubits = self.config.processor.qubit_count().min(self.oracle.simulator().max_qubits);
+        circuit.validate_width(max_qubits).map_err(|e| e.to_string())?;
+
+        Ok(self.oracle.simulator_mut().run(circuit, repetitions))
+    }
+
     /// Run a quantum circuit for custom threat simulation
     pub fn run_quantum_circuit(&mut self, circuit: &QuantumCircuit) -> Option<CircuitResult> {
         if !self.config.enable_quantum_circuits {
@@ -1760,8 +3365,13 @@ impl QvmProtocolStack {
 
     /// Get current protocol stack status
     pub fn get_status(&self) -> QvmStatus {
+        let processor = self.oracle.simulator().processor();
+
         QvmStatus {
-            processor: self.oracle.simulator().processor(),
+            processor,
+            processor_mean_fidelity: self.qubit_picker.device_fidelity_score(),
+            processor_qubit_count: processor.qubit_count(),
+            processor_quantum_volume: processor.quantum_volume(),
             current_era: self.current_era,
             qrm_risk_score: self.qrm.get_risk_history().last().map(|r| r.score).unwrap_or(0),
             oracle_risk_score: self.last_assessment.as_ref().map(|a| a.composite_risk).unwrap_or(0),
@@ -1787,6 +3397,9 @@ impl QvmProtocolStack {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QvmStatus {
     pub processor: QuantumProcessor,
+    pub processor_mean_fidelity: f64,
+    pub processor_qubit_count: usize,
+    pub processor_quantum_volume: u64,
     pub current_era: QuantumEra,
     pub qrm_risk_score: u32,
     pub oracle_risk_score: u32,
@@ -1934,10 +3547,189 @@ pub fn build_ghz_circuit(n_qubits: usize) -> QuantumCircuit {
     }
 }
 
+/// Quantum Fourier Transform over `register`, one gate per moment
+///
+/// Each qubit gets a Hadamard followed by controlled-phase rotations of
+/// π/2^k from the qubits after it, then SWAPs (as CNOT triples) reverse the
+/// bit order. The inverse negates the angles and runs the sequence backwards.
+fn qft_moments(register: &[usize], inverse: bool) -> Vec<Vec<QuantumGate>> {
+    let n = register.len();
+    let mut gates = Vec::new();
+
+    for j in 0..n {
+        gates.push(vec![QuantumGate::H(register[j])]);
+        for k in j + 1..n {
+            let angle = std::f64::consts::PI / 2f64.powi((k - j) as i32);
+            gates.push(vec![QuantumGate::CPhase(register[k], register[j], angle)]);
+        }
+    }
+    for j in 0..n / 2 {
+        let (a, b) = (register[j], register[n - 1 - j]);
+        gates.push(vec![QuantumGate::CNOT(a, b)]);
+        gates.push(vec![QuantumGate::CNOT(b, a)]);
+        gates.push(vec![QuantumGate::CNOT(a, b)]);
+    }
+
+    if inverse {
+        gates.reverse();
+        for moment in &mut gates {
+            for gate in moment.iter_mut() {
+                if let QuantumGate::CPhase(_, _, theta) = gate {
+                    *theta = -*theta;
+                }
+            }
+        }
+    }
+    gates
+}
+
+/// Build a Quantum Fourier Transform circuit (the core of Shor's period finding)
+pub fn build_qft_circuit(n_qubits: usize) -> QuantumCircuit {
+    let qubits: Vec<GridQubit> = (0..n_qubits)
+        .map(|i| GridQubit::new(i as i32, 0))
+        .collect();
+    let register: Vec<usize> = (0..n_qubits).collect();
+
+    let mut gates = qft_moments(&register, false);
+    gates.push(register.iter()
+        .map(|&i| QuantumGate::Measure(i, format!("m{}", i)))
+        .collect());
+
+    let mut metadata = HashMap::new();
+    metadata.insert("algorithm".to_string(), "qft".to_string());
+    metadata.insert("qubits".to_string(), n_qubits.to_string());
+
+    QuantumCircuit {
+        id: format!("qft_{}", n_qubits),
+        name: format!("Quantum Fourier Transform ({} qubits)", n_qubits),
+        qubits,
+        gates,
+        metadata,
+    }.repack_moments()
+}
+
+/// Build the period-finding skeleton of Shor's algorithm
+///
+/// The counting register is put in superposition, each counting qubit j
+/// controls a stand-in for U^(2^j) on the work register (initialised to |1⟩),
+/// and an inverse QFT over the counting register precedes measurement. The
+/// controlled modular multiplications are single CNOTs: the structure and
+/// depth profile are faithful, the arithmetic is not.
+pub fn build_modular_exponentiation_stub(counting_qubits: usize, work_qubits: usize) -> QuantumCircuit {
+    let total = counting_qubits + work_qubits;
+    let qubits: Vec<GridQubit> = (0..total)
+        .map(|i| GridQubit::new(i as i32, 0))
+        .collect();
+    let counting: Vec<usize> = (0..counting_qubits).collect();
+
+    let mut gates = Vec::new();
+    gates.push(counting.iter().map(|&q| QuantumGate::H(q)).collect::<Vec<_>>());
+    if work_qubits > 0 {
+        gates.push(vec![QuantumGate::X(counting_qubits)]);
+        for &control in &counting {
+            let target = counting_qubits + control % work_qubits;
+            gates.push(vec![QuantumGate::CNOT(control, target)]);
+        }
+    }
+    gates.extend(qft_moments(&counting, true));
+    gates.push(counting.iter()
+        .map(|&q| QuantumGate::Measure(q, format!("c{}", q)))
+        .collect());
+
+    let mut metadata = HashMap::new();
+    metadata.insert("algorithm".to_string(), "shor_period_finding_stub".to_string());
+    metadata.insert("counting_qubits".to_string(), counting_qubits.to_string());
+    metadata.insert("work_qubits".to_string(), work_qubits.to_string());
+
+    QuantumCircuit {
+        id: format!("shor_stub_{}_{}", counting_qubits, work_qubits),
+        name: format!("Shor Period Finding Stub ({} + {} qubits)", counting_qubits, work_qubits),
+        qubits,
+        gates,
+        metadata,
+    }.repack_moments()
+}
+
+// ============================================================================
+// Golden Circuit Self-Test
+// ============================================================================
+
+/// Allowed absolute deviation from the expected golden distribution
+const GOLDEN_TOLERANCE: f64 = 1e-9;
+
+/// Outcome of a single golden circuit check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenCircuitResult {
+    pub circuit: String,
+    pub passed: bool,
+    pub max_deviation: f64,
+    pub expected: Vec<f64>,
+    pub observed: Vec<f64>,
+}
+
+/// QVM simulator regression self-test report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QvmSelfTestReport {
+    pub passed: bool,
+    pub circuits: Vec<GoldenCircuitResult>,
+}
+
+/// Run the golden circuits noiselessly and compare against known-correct distributions
+pub fn run_golden_selftest() -> QvmSelfTestReport {
+    let golden = [
+        // |Φ+⟩: |00⟩ and |11⟩ with equal probability
+        (build_bell_state_circuit(), vec![0.5, 0.0, 0.0, 0.5]),
+        // GHZ-3: |000⟩ and |111⟩ with equal probability
+        (build_ghz_circuit(3), vec![0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.5]),
+        // Grover-2, one iteration: marked |11⟩ found with certainty
+        (build_grover_circuit(2, 1), vec![0.0, 0.0, 0.0, 1.0]),
+    ];
+
+    let mut simulator = QvmSimulator::new(QuantumProcessor::WillowPink);
+    let circuits: Vec<GoldenCircuitResult> = golden.into_iter()
+        .map(|(circuit, expected)| {
+            let observed = simulator.ideal_probabilities(&circuit);
+            let max_deviation = if observed.len() == expected.len() {
+                observed.iter().zip(&expected).map(|(o, e)| (o - e).abs()).fold(0.0, f64::max)
+            } else {
+                f64::INFINITY
+            };
+            GoldenCircuitResult {
+                circuit: circuit.id,
+                passed: max_deviation <= GOLDEN_TOLERANCE,
+                max_deviation,
+                expected,
+                observed,
+            }
+        })
+        .collect();
+
+    QvmSelfTestReport {
+        passed: circuits.iter().all(|c| c.passed),
+        circuits,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_noise_model_json_round_trip() {
+        let model = NoiseModel::from_processor(QuantumProcessor::WillowPink);
+        let loaded = NoiseModel::from_json(&model.to_json().unwrap()).expect("valid model json");
+
+        assert_eq!(loaded.depolarizing_rate.to_bits(), model.depolarizing_rate.to_bits());
+        assert_eq!(loaded.amplitude_damping_rate.to_bits(), model.amplitude_damping_rate.to_bits());
+        assert_eq!(loaded.phase_damping_rate.to_bits(), model.phase_damping_rate.to_bits());
+        assert_eq!(loaded.calibration_timestamp, model.calibration_timestamp);
+        assert_eq!(loaded.readout_errors, model.readout_errors);
+
+        let sim = QvmSimulator::new_with_noise_model(QuantumProcessor::WillowPink, loaded);
+        assert_eq!(sim.noise_model().calibration_timestamp, model.calibration_timestamp);
+        assert!(NoiseModel::from_json("{}").is_err());
+    }
+
     #[test]
     fn test_processor_properties() {
         let willow = QuantumProcessor::WillowPink;
@@ -2038,6 +3830,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_picker_from_external_calibration() {
+        let (a, b) = (GridQubit::new(0, 0), GridQubit::new(0, 1));
+        let qubit_error = |qubit: GridQubit, pauli: f64| QubitErrorData {
+            qubit,
+            single_qubit_pauli_error: pauli,
+            readout_error_0_to_1: 0.012,
+            readout_error_1_to_0: 0.034,
+            t1_us: 21.5,
+            t2_us: 17.25,
+            quality_score: pauli * 10.0,
+        };
+        let pair = TwoQubitErrorData {
+            qubit_pair: (a, b),
+            gate_type: "CZ".to_string(),
+            pauli_error: 0.0071,
+            fsim_theta_error: 0.001,
+            fsim_phi_error: 0.002,
+            fsim_error_norm: 0.0022,
+            quality_score: 0.071,
+        };
+        let connectivity = HashMap::from([(a, vec![b]), (b, vec![a])]);
+
+        let picker = QubitPicker::from_calibration(
+            QuantumProcessor::Custom { qubits: 2, connectivity: ConnectivityType::Linear },
+            vec![qubit_error(a, 0.0013), qubit_error(b, 0.0042)],
+            vec![pair],
+            connectivity,
+        );
+
+        let error = picker.get_qubit_error(b).expect("injected qubit");
+        assert_eq!(error.single_qubit_pauli_error, 0.0042);
+        assert_eq!(error.readout_error_0_to_1, 0.012);
+        assert_eq!(error.readout_error_1_to_0, 0.034);
+        assert_eq!(error.t1_us, 21.5);
+        assert_eq!(error.t2_us, 17.25);
+        assert_eq!(picker.get_pair_error(b, a).expect("injected pair").pauli_error, 0.0071);
+        assert!(picker.get_qubit_error(GridQubit::new(1, 0)).is_none());
+    }
+
     #[test]
     fn test_bad_qubits_identified() {
         let picker = QubitPicker::new(QuantumProcessor::Rainbow);
@@ -2060,7 +3892,7 @@ mod tests {
             QubitPickingStrategy::MinimizeReadoutError,
             QubitPickingStrategy::MaximizeCoherence,
             QubitPickingStrategy::Balanced,
-            QubitPickingStrategy::Custom { single_weight: 1.0, two_qubit_weight: 2.0, readout_weight: 1.5 },
+            QubitPickingStrategy::Custom { single_weight: 1.0, two_qubit_weight: 2.0, readout_weight: 1.5, crosstalk_weight: 0.0 },
         ];
         
         for strategy in strategies {
@@ -2069,6 +3901,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pick_qubits_warns_on_bad_qubit() {
+        let picker = QubitPicker::new(QuantumProcessor::Rainbow);
+        // Negative weights invert the ranking so the worst qubits are chosen
+        let strategy = QubitPickingStrategy::Custom { single_weight: -1.0, two_qubit_weight: 0.0, readout_weight: -1.0, crosstalk_weight: 0.0 };
+
+        let result = picker.pick_qubits(2, &[], strategy);
+
+        assert!(result.selected_qubits.contains(&GridQubit::new(7, 2)));
+        assert!(result.warnings.iter().any(|w| w == "selected qubit (7,2) is a known bad qubit"), "{:?}", result.warnings);
+        // Warnings are stricter than the avoid list, which keeps its 0.1 threshold
+        assert!(!result.avoid_qubits.contains(&GridQubit::new(7, 2)));
+
+        let good = picker.pick_qubits(2, &[], QubitPickingStrategy::Balanced);
+        assert!(good.viable);
+        assert!(good.warnings.is_empty(), "{:?}", good.warnings);
+    }
+
+    #[test]
+    fn test_parallel_adjacent_czs_pay_crosstalk() {
+        let picker = QubitPicker::new(QuantumProcessor::Custom { qubits: 4, connectivity: ConnectivityType::Grid });
+        // 2x2 grid: pairs (0,0)-(0,1) and (1,0)-(1,1) are coupled vertically
+        let qubits = vec![GridQubit::new(0, 0), GridQubit::new(0, 1), GridQubit::new(1, 0), GridQubit::new(1, 1)];
+        let circuit = |gates: Vec<Vec<QuantumGate>>| QuantumCircuit {
+            id: "crosstalk".to_string(),
+            name: "crosstalk".to_string(),
+            qubits: qubits.clone(),
+            gates,
+            metadata: HashMap::new(),
+        };
+        let parallel = circuit(vec![vec![QuantumGate::CZ(0, 1), QuantumGate::CZ(2, 3)]]);
+        let serialized = circuit(vec![vec![QuantumGate::CZ(0, 1)], vec![QuantumGate::CZ(2, 3)]]);
+
+        let strategy = QubitPickingStrategy::Balanced;
+        let parallel_fidelity = picker.estimate_circuit_fidelity(&qubits, &parallel, strategy);
+        let serialized_fidelity = picker.estimate_circuit_fidelity(&qubits, &serialized, strategy);
+        assert!(parallel_fidelity < serialized_fidelity, "{} vs {}", parallel_fidelity, serialized_fidelity);
+        assert_eq!(serialized_fidelity, picker.estimate_fidelity(&qubits, &[(0, 1), (2, 3)]));
+
+        let no_crosstalk = QubitPickingStrategy::Custom {
+            single_weight: 1.0, two_qubit_weight: 1.0, readout_weight: 1.0, crosstalk_weight: 0.0,
+        };
+        assert_eq!(
+            picker.estimate_circuit_fidelity(&qubits, &parallel, no_crosstalk),
+            serialized_fidelity,
+        );
+    }
+
     #[test]
     fn test_transform_circuit() {
         let picker = QubitPicker::new(QuantumProcessor::Rainbow);
@@ -2080,4 +3960,877 @@ mod tests {
         assert!(transformed.metadata.contains_key("transformed"));
         assert_eq!(transformed.metadata.get("transformed"), Some(&"true".to_string()));
     }
+
+    fn custom_degrees(connectivity: ConnectivityType) -> Vec<usize> {
+        let picker = QubitPicker::new(QuantumProcessor::Custom { qubits: 9, connectivity });
+        let mut degrees: Vec<usize> = picker.connectivity.values().map(|n| n.len()).collect();
+        degrees.sort();
+        degrees
+    }
+
+    #[test]
+    fn test_custom_connectivity_matches_topology() {
+        assert_eq!(custom_degrees(ConnectivityType::Grid), vec![2, 2, 2, 2, 3, 3, 3, 3, 4]);
+        assert_eq!(custom_degrees(ConnectivityType::Linear), vec![1, 1, 2, 2, 2, 2, 2, 2, 2]);
+        assert_eq!(custom_degrees(ConnectivityType::HeavyHex), vec![1, 1, 2, 2, 2, 2, 2, 3, 3]);
+        assert_eq!(custom_degrees(ConnectivityType::AllToAll), vec![8; 9]);
+    }
+
+    #[test]
+    fn test_all_to_all_routes_without_swaps() {
+        let picker = QubitPicker::new(QuantumProcessor::Custom {
+            qubits: 9,
+            connectivity: ConnectivityType::AllToAll,
+        });
+        let circuit = QuantumCircuit {
+            id: "far".to_string(),
+            name: "Far".to_string(),
+            qubits: (0..9).map(|i| GridQubit::new(i / 3, i % 3)).collect(),
+            gates: vec![vec![QuantumGate::CZ(0, 8)]],
+            metadata: HashMap::new(),
+        };
+        let mapping: HashMap<usize, GridQubit> = circuit.qubits.iter().cloned().enumerate().collect();
+
+        let routed = picker.transform_circuit(&circuit, &mapping);
+
+        assert_eq!(routed.metadata.get("swaps_inserted"), Some(&"0".to_string()));
+    }
+
+    #[test]
+    fn test_transform_circuit_routes_non_adjacent_gate() {
+        let picker = QubitPicker::new(QuantumProcessor::Custom {
+            qubits: 3,
+            connectivity: ConnectivityType::Linear,
+        });
+        let circuit = QuantumCircuit {
+            id: "route".to_string(),
+            name: "Route".to_string(),
+            qubits: (0..3).map(|i| GridQubit::new(0, i)).collect(),
+            gates: vec![
+                vec![QuantumGate::H(0)],
+                vec![QuantumGate::CZ(0, 2)],
+                vec![QuantumGate::Measure(0, "m0".to_string()), QuantumGate::Measure(2, "m2".to_string())],
+            ],
+            metadata: HashMap::new(),
+        };
+        let mapping: HashMap<usize, GridQubit> = (0..3).map(|i| (i, GridQubit::new(0, i as i32))).collect();
+
+        let routed = picker.transform_circuit(&circuit, &mapping);
+
+        assert_eq!(routed.metadata.get("swaps_inserted"), Some(&"1".to_string()));
+        assert_eq!(routed.two_qubit_gate_count(), 3 + 1);
+        assert!(routed.gates.iter().flatten().any(|g| matches!(g, QuantumGate::CNOT(..))));
+
+        // Every two-qubit gate now acts on adjacent hardware qubits
+        for gate in routed.gates.iter().flatten() {
+            if let Some((a, b)) = two_qubit_operands(gate) {
+                let (qa, qb) = (routed.qubits[a], routed.qubits[b]);
+                assert!(picker.get_neighbors(qa).unwrap().contains(&qb), "{:?} not adjacent", gate);
+            }
+        }
+
+        // Logical 0 was swapped onto (0,1), so the CZ and its measurement follow it
+        assert!(routed.gates.iter().flatten().any(|g| matches!(g, QuantumGate::CZ(1, 2))));
+        assert!(routed.gates.iter().flatten()
+            .any(|g| matches!(g, QuantumGate::Measure(1, key) if key == "m0")));
+    }
+
+    #[test]
+    fn test_status_reports_device_fidelity() {
+        let willow = QvmProtocolStack::new(QvmConfig::default()).get_status();
+        let rainbow = QvmProtocolStack::new(QvmConfig {
+            processor: QuantumProcessor::Rainbow,
+            ..QvmConfig::default()
+        }).get_status();
+
+        assert_eq!(willow.processor_qubit_count, 105);
+        assert_eq!(rainbow.processor_qubit_count, 53);
+        assert!(willow.processor_mean_fidelity > rainbow.processor_mean_fidelity);
+        assert!(willow.processor_mean_fidelity > 0.0 && willow.processor_mean_fidelity <= 1.0);
+    }
+
+    #[test]
+    fn test_quantum_volume_tracks_error_rates() {
+        let willow = QuantumProcessor::WillowPink.quantum_volume();
+        let rainbow = QuantumProcessor::Rainbow.quantum_volume();
+        assert!(willow > rainbow, "willow {} <= rainbow {}", willow, rainbow);
+        assert!(willow.is_power_of_two() && rainbow >= 2);
+
+        // Same qubits and error rates: only routing overhead differs
+        let custom = |connectivity| QuantumProcessor::Custom { qubits: 16, connectivity }.quantum_volume();
+        assert!(custom(ConnectivityType::AllToAll) >= custom(ConnectivityType::Grid));
+        assert!(custom(ConnectivityType::Grid) >= custom(ConnectivityType::Linear));
+
+        let status = QvmProtocolStack::new(QvmConfig::default()).get_status();
+        assert_eq!(status.processor_quantum_volume, willow);
+    }
+
+    #[test]
+    fn test_shor_skips_post_quantum_algorithms() {
+        let oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        let mlkem = oracle.assess_shor_threat("ML-KEM-1024", 1024);
+        assert_eq!(mlkem.threat_level, ThreatLevel::None);
+        assert_eq!(mlkem.required_physical_qubits, 0);
+        assert!(mlkem.note.is_some());
+
+        let rsa = oracle.assess_shor_threat("RSA-2048", 2048);
+        assert!(rsa.required_physical_qubits > 0);
+        assert!(rsa.note.is_none());
+    }
+
+    #[test]
+    fn test_shor_confidence_raises_signature_risk() {
+        let assessment = OracleAssessment {
+            timestamp: Utc::now(),
+            grover_assessments: vec![],
+            shor_assessments: vec![ShorThreatAssessment {
+                target_algorithm: "ECDSA-secp256k1".to_string(),
+                key_bits: 256,
+                required_logical_qubits: 2330,
+                required_t_gates: 1_000_000_000,
+                required_physical_qubits: 2_330_000,
+                error_correction_overhead: 1000.0,
+                estimated_time_hours: 8.0,
+                threat_level: ThreatLevel::NearTerm,
+                note: None,
+            }],
+            composite_risk: 7000,
+            recommended_era: QuantumEra::Nisq,
+            recommended_algorithms: vec![],
+        };
+
+        let signature_score = |shor_confidence: f64| {
+            let mut stack = QvmProtocolStack::new(QvmConfig {
+                shor_indicator_confidence: shor_confidence,
+                ..QvmConfig::default()
+            });
+            // Baseline low-severity indicator the oracle output is weighed against
+            stack.qrm.add_indicator(ThreatIndicator {
+                category: ThreatCategory::DigitalSignatures,
+                sub_category: "Baseline".to_string(),
+                severity: 0.1,
+                confidence: 0.5,
+                source: "Test".to_string(),
+                timestamp: Utc::now(),
+                description: "Baseline signature threat".to_string(),
+                era_relevance: QuantumEra::Nisq,
+                references: vec![],
+                tags: vec![],
+                expires_at: None,
+            });
+            stack.generate_threat_indicators(&assessment);
+            stack.qrm.calculate_risk().category_breakdown.iter()
+                .find(|c| c.category == ThreatCategory::DigitalSignatures)
+                .map(|c| c.score)
+                .unwrap_or(0)
+        };
+
+        assert!(signature_score(0.99) > signature_score(0.5));
+    }
+
+    #[cfg(not(feature = "qvm-fault-injection"))]
+    #[test]
+    fn test_statevector_equivalence() {
+        let mut sim = QvmSimulator::new(QuantumProcessor::WillowPink);
+        let bell = sim.statevector_after(&build_bell_state_circuit());
+        let rebuilt = sim.statevector_after(&build_ghz_circuit(2));
+        assert!(states_equal(&bell, &rebuilt, 1e-9));
+
+        // A global phase does not distinguish states
+        let phased: Vec<Complex> = bell.iter().map(|a| a.mul(&Complex::new(0.0, 1.0))).collect();
+        assert!(states_equal(&bell, &phased, 1e-9));
+
+        let mut swapped = build_bell_state_circuit();
+        swapped.gates[1] = vec![QuantumGate::CNOT(1, 0)];
+        let swapped = sim.statevector_after(&swapped);
+        assert!(!states_equal(&bell, &swapped, 1e-9));
+    }
+
+    #[cfg(not(feature = "qvm-fault-injection"))]
+    #[test]
+    fn test_apply_unitary_matches_builtin_hadamard() {
+        let inv_sqrt2 = 1.0 / 2.0_f64.sqrt();
+        let hadamard = [
+            Complex::new(inv_sqrt2, 0.0), Complex::new(inv_sqrt2, 0.0),
+            Complex::new(inv_sqrt2, 0.0), Complex::new(-inv_sqrt2, 0.0),
+        ];
+
+        let mut custom = QvmSimulator::new(QuantumProcessor::WillowPink);
+        custom.prepare_state(3).unwrap();
+        custom.apply_gate(&QuantumGate::X(2));
+        custom.apply_unitary(&[1], &hadamard).unwrap();
+        custom.apply_unitary(&[2], &hadamard).unwrap();
+
+        let mut builtin = QvmSimulator::new(QuantumProcessor::WillowPink);
+        builtin.prepare_state(3).unwrap();
+        builtin.apply_gate(&QuantumGate::X(2));
+        builtin.apply_gate(&QuantumGate::H(1));
+        builtin.apply_gate(&QuantumGate::H(2));
+
+        for (a, b) in custom.state_vector().unwrap().iter().zip(builtin.state_vector().unwrap()) {
+            assert!((a.real - b.real).abs() < 1e-12);
+            assert!((a.imag - b.imag).abs() < 1e-12);
+        }
+
+        // Dimension and qubit validation
+        assert!(custom.apply_unitary(&[0, 1], &hadamard).is_err());
+        assert!(custom.apply_unitary(&[3], &hadamard).is_err());
+        assert!(custom.apply_unitary(&[], &hadamard).is_err());
+    }
+
+    #[test]
+    fn test_measure_zero_state_never_produces_nan() {
+        let mut sim = QvmSimulator::new(QuantumProcessor::Rainbow);
+        // Exaggerated readout noise so the orthogonal outcome is reported often
+        sim.noise_model.readout_errors.insert("0".to_string(), (0.5, 0.5));
+
+        let mut flipped = 0;
+        for _ in 0..500 {
+            sim.prepare_state(2).unwrap();
+            if sim.measure_qubit(0) == 1 {
+                flipped += 1;
+            }
+            let state = sim.state_vector().unwrap();
+            assert!(state.iter().all(|a| a.real.is_finite() && a.imag.is_finite()));
+            assert!((state[0].real - 1.0).abs() < 1e-12);
+        }
+        assert!(flipped > 0);
+    }
+
+    #[test]
+    fn test_kraus_noise_degrades_deep_ghz() {
+        let n = 5;
+        let mut circuit = build_ghz_circuit(n);
+        let measure_layer = circuit.gates.pop().unwrap();
+        // Pairs of identical CNOTs cancel, deepening the circuit without changing the ideal state
+        for _ in 0..20 {
+            for i in 0..n - 1 {
+                circuit.gates.push(vec![QuantumGate::CNOT(i, i + 1)]);
+                circuit.gates.push(vec![QuantumGate::CNOT(i, i + 1)]);
+            }
+        }
+        circuit.gates.push(measure_layer);
+
+        let mut sim = QvmSimulator::new(QuantumProcessor::Custom {
+            qubits: n,
+            connectivity: ConnectivityType::Linear,
+        });
+        let ideal = sim.ideal_probabilities(&circuit);
+        let all_ones = (1 << n) - 1;
+        let ideal_ghz_mass = ideal[0] + ideal[all_ones];
+
+        let shots = 400;
+        let result = sim.run_with_kraus(&circuit, shots);
+        let ghz_counts = result.histogram.get(&0).copied().unwrap_or(0)
+            + result.histogram.get(&(all_ones as u64)).copied().unwrap_or(0);
+        let noisy_ghz_mass = ghz_counts as f64 / shots as f64;
+
+        assert!((ideal_ghz_mass - 1.0).abs() < 1e-9);
+        assert!(noisy_ghz_mass < 0.8 * ideal_ghz_mass, "GHZ mass {} not degraded", noisy_ghz_mass);
+    }
+
+    fn idle_excited_zero_fraction(t1_us: f64) -> f64 {
+        let processor = QuantumProcessor::Custom { qubits: 2, connectivity: ConnectivityType::Linear };
+        let mut model = NoiseModel::from_processor(processor);
+        model.amplitude_damping_rate = 1.0 / t1_us;
+        model.phase_damping_rate = 2.0 / t1_us;
+        model.readout_errors.clear();
+        let mut sim = QvmSimulator::new_with_noise_model(processor, model);
+
+        // Excite qubit 0, then let it idle for 200 single-qubit slots (5 μs at
+        // 25 ns each) padded by identity rotations on qubit 1
+        let mut gates = vec![vec![QuantumGate::X(0)]];
+        gates.extend((0..200).map(|_| vec![QuantumGate::Rz(1, 0.0)]));
+        gates.push(vec![QuantumGate::Measure(0, "m".to_string())]);
+        let circuit = QuantumCircuit {
+            id: "idle".to_string(),
+            name: "Idle".to_string(),
+            qubits: vec![GridQubit::new(0, 0), GridQubit::new(0, 1)],
+            gates,
+            metadata: HashMap::new(),
+        };
+
+        let shots = 400;
+        let result = sim.run_with_kraus(&circuit, shots);
+        result.histogram.get(&0).copied().unwrap_or(0) as f64 / shots as f64
+    }
+
+    #[test]
+    fn test_low_t1_idle_circuit_relaxes_to_ground() {
+        let low_t1 = idle_excited_zero_fraction(5.0);
+        let high_t1 = idle_excited_zero_fraction(500.0);
+
+        // Expected |0⟩ fractions: ~63% at T1 = 5 μs, ~1% at T1 = 500 μs
+        assert!(low_t1 > 0.45, "low-T1 |0⟩ fraction {}", low_t1);
+        assert!(high_t1 < 0.15, "high-T1 |0⟩ fraction {}", high_t1);
+        assert!(low_t1 > high_t1 + 0.3);
+    }
+
+    #[test]
+    fn test_run_streaming_reports_each_moment() {
+        let circuit = build_ghz_circuit(4);
+        let mut sim = QvmSimulator::new(QuantumProcessor::Custom {
+            qubits: 4,
+            connectivity: ConnectivityType::Linear,
+        });
+
+        let mut calls = Vec::new();
+        let result = sim.run_streaming(&circuit, 50, |moment, progress| calls.push((moment, progress)));
+
+        // The unitary prefix is reported once, the measurement moment once per repetition
+        let prefix = circuit.depth() - 1;
+        assert_eq!(calls.len(), prefix + 50);
+        assert!(calls[..prefix].iter().enumerate().all(|(i, (moment, _))| *moment == i));
+        assert!(calls[prefix..].iter().all(|(moment, _)| *moment == prefix));
+        assert!(calls.windows(2).all(|w| w[0].1 < w[1].1));
+        assert!((calls.last().unwrap().1 - 1.0).abs() < 1e-12);
+        assert!(result.measurements.values().all(|bits| bits.len() == 50));
+    }
+
+    #[test]
+    fn test_repack_moments_merges_disjoint_gates() {
+        let mut circuit = build_bell_state_circuit();
+        circuit.gates = vec![
+            vec![QuantumGate::H(0)],
+            vec![QuantumGate::X(1)],
+            vec![QuantumGate::Z(0)],
+        ];
+
+        let repacked = circuit.repack_moments();
+        assert_eq!(repacked.depth(), 2);
+        assert_eq!(repacked.gates[0].len(), 2);
+        // Z(0) depends on H(0) and stays in a later moment
+        assert!(matches!(repacked.gates[1][..], [QuantumGate::Z(0)]));
+
+        let mut sim = QvmSimulator::new(QuantumProcessor::WillowPink);
+        let before = sim.statevector_after(&circuit);
+        let after = sim.statevector_after(&repacked);
+        assert!(states_equal(&before, &after, 1e-12));
+    }
+
+    #[test]
+    fn test_circuit_size_metrics() {
+        // H layer, then per iteration: CZ chain, H, X, CZ chain, X, H; then measure
+        let grover = build_grover_circuit(3, 2);
+        assert_eq!(grover.depth(), 1 + 6 * 2 + 1);
+        assert_eq!(grover.gate_count(), 3 + 2 * (4 * 3 + 2 * 2));
+        assert_eq!(grover.two_qubit_gate_count(), 2 * 2 * 2);
+        assert_eq!(grover.t_gate_count(), 0);
+
+        // H, CNOT cascade, measure
+        let mut ghz = build_ghz_circuit(4);
+        assert_eq!(ghz.depth(), 5);
+        assert_eq!(ghz.gate_count(), 4);
+        assert_eq!(ghz.two_qubit_gate_count(), 3);
+
+        ghz.gates.push(vec![QuantumGate::T(0), QuantumGate::T(1)]);
+        assert_eq!(ghz.t_gate_count(), 2);
+    }
+
+    #[test]
+    fn test_era_transition_requires_confirmation() {
+        let mut stack = QvmProtocolStack::new(QvmConfig {
+            confirmation_blocks: 3,
+            ..QvmConfig::default()
+        });
+
+        // Flapping between two eras never holds long enough to commit
+        for i in 0..10 {
+            let era = if i % 2 == 0 { QuantumEra::Nisq } else { QuantumEra::FaultTolerant };
+            stack.observe_recommended_era(era);
+        }
+        assert_eq!(stack.current_era, QuantumEra::PreQuantum);
+        assert!(stack.era_transitions.is_empty());
+
+        // A stable run of N commits exactly once
+        for _ in 0..3 {
+            stack.observe_recommended_era(QuantumEra::FaultTolerant);
+        }
+        assert_eq!(stack.current_era, QuantumEra::FaultTolerant);
+        assert_eq!(stack.qrm.current_era, QuantumEra::FaultTolerant);
+        assert_eq!(stack.era_transitions.len(), 1);
+    }
+
+    #[test]
+    fn test_custom_assessment_targets() {
+        let mut oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        oracle.set_assessment_targets(vec![], vec![("BN254".to_string(), 254)]);
+
+        let assessment = oracle.perform_assessment();
+
+        assert_eq!(assessment.shor_assessments.len(), 1);
+        assert_eq!(assessment.shor_assessments[0].target_algorithm, "BN254");
+        assert_eq!(assessment.shor_assessments[0].key_bits, 254);
+        assert_eq!(assessment.grover_assessments.len(), DEFAULT_GROVER_TARGETS.len());
+    }
+
+    #[test]
+    fn test_fidelity_curve_decays_with_depth() {
+        let simulator = QvmSimulator::new(QuantumProcessor::WillowPink);
+
+        let curve = simulator.fidelity_curve(4, 50);
+
+        assert_eq!(curve.len(), 50);
+        assert_eq!(curve[0].0, 1);
+        assert!(curve[0].1 > 0.9 && curve[0].1 <= 1.0, "depth-1 fidelity {}", curve[0].1);
+        assert!(curve.windows(2).all(|w| w[1].1 <= w[0].1));
+        assert!(simulator.fidelity_curve(4, 0).is_empty());
+    }
+
+    #[test]
+    fn test_oracle_history_is_bounded_ring_buffer() {
+        let mut oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        oracle.set_max_history(8);
+
+        let mut newest = None;
+        for _ in 0..oracle.max_history() + 10 {
+            newest = Some(oracle.perform_assessment());
+        }
+        let newest = newest.unwrap();
+
+        assert_eq!(oracle.get_threat_history().len(), 8);
+        assert_eq!(oracle.get_threat_history().back().unwrap().timestamp, newest.timestamp);
+
+        let page = oracle.get_history_page(0, 3);
+        assert_eq!(page.len(), 3);
+        assert_eq!(page[0].timestamp, newest.timestamp);
+        assert_eq!(oracle.get_history_page(6, 10).len(), 2);
+        assert!(oracle.get_history_page(8, 10).is_empty());
+    }
+
+    #[test]
+    fn test_recommendation_policy_selects_band_for_risk() {
+        let names = |algos: &[&str]| algos.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        let policy = RecommendationPolicy {
+            bands: vec![
+                RiskBand { above: 8000, algorithms: names(&["SLH-DSA-256s"]) },
+                RiskBand { above: 5000, algorithms: names(&["ML-DSA-65", "ECDSA-secp256k1"]) },
+            ],
+            baseline: names(&["ECDSA-secp256k1"]),
+        };
+
+        assert_eq!(policy.recommend(9000), names(&["SLH-DSA-256s"]));
+        assert_eq!(policy.recommend(8000), names(&["ML-DSA-65", "ECDSA-secp256k1"]));
+        assert_eq!(policy.recommend(6000), names(&["ML-DSA-65", "ECDSA-secp256k1"]));
+        assert_eq!(policy.recommend(5000), names(&["ECDSA-secp256k1"]));
+
+        let mut stack = QvmProtocolStack::new(QvmConfig::default());
+        stack.set_recommendation_policy(policy.clone());
+        stack.assess_and_update();
+        let assessment = stack.last_assessment.as_ref().unwrap();
+        assert_eq!(assessment.recommended_algorithms, policy.recommend(assessment.composite_risk));
+    }
+
+    #[test]
+    fn test_assessment_diff_reports_escalation() {
+        let mut oracle = QvmOracle::new(QuantumProcessor::WillowPink);
+        let mut baseline = oracle.perform_assessment();
+        baseline.shor_assessments[0].threat_level = ThreatLevel::LongTerm;
+
+        let mut worsened = baseline.clone();
+        worsened.composite_risk += 1500;
+        worsened.shor_assessments[0].threat_level = ThreatLevel::Imminent;
+
+        let diff = baseline.diff(&worsened);
+
+        assert_eq!(diff.composite_risk_delta, 1500);
+        assert!(diff.relaxed.is_empty());
+        assert_eq!(diff.escalated.len(), 1);
+        assert_eq!(diff.escalated[0].algorithm, baseline.shor_assessments[0].target_algorithm);
+        assert_eq!(diff.escalated[0].attack, "shor");
+        assert_eq!(diff.escalated[0].from, Some(ThreatLevel::LongTerm));
+        assert_eq!(diff.escalated[0].to, Some(ThreatLevel::Imminent));
+        assert!(baseline.diff(&baseline).escalated.is_empty());
+    }
+
+    #[test]
+    fn test_compare_processors_willow_at_least_as_threatening() {
+        let algos = vec![("RSA-2048".to_string(), 2048)];
+        let processors = [QuantumProcessor::WillowPink, QuantumProcessor::Rainbow];
+
+        let results = compare_processors(&algos, &processors);
+
+        assert_eq!(results.len(), 2);
+        let willow = &results[&QuantumProcessor::WillowPink];
+        let rainbow = &results[&QuantumProcessor::Rainbow];
+        assert_eq!(willow.shor_assessments[0].target_algorithm, "RSA-2048");
+        assert!(willow.composite_risk >= rainbow.composite_risk,
+            "Willow {} < Rainbow {}", willow.composite_risk, rainbow.composite_risk);
+    }
+
+    #[test]
+    fn test_threat_level_severity_boundaries() {
+        let boundaries = [
+            (0.1, ThreatLevel::None, ThreatLevel::Theoretical),
+            (0.3, ThreatLevel::Theoretical, ThreatLevel::LongTerm),
+            (0.5, ThreatLevel::LongTerm, ThreatLevel::MediumTerm),
+            (0.75, ThreatLevel::MediumTerm, ThreatLevel::NearTerm),
+            (1.0, ThreatLevel::NearTerm, ThreatLevel::Imminent),
+        ];
+        for (severity, below, at) in boundaries {
+            assert_eq!(ThreatLevel::from_severity(severity - 0.001), below, "below {}", severity);
+            assert_eq!(ThreatLevel::from_severity(severity), at, "at {}", severity);
+            assert_eq!(ThreatLevel::from_severity(at.as_score() as f64 / 10000.0), at);
+        }
+        assert_eq!(ThreatLevel::from_severity(0.0), ThreatLevel::None);
+        assert_eq!(ThreatLevel::from_severity(-1.0), ThreatLevel::None);
+        assert_eq!(ThreatLevel::from_severity(1.5), ThreatLevel::Imminent);
+    }
+
+    #[test]
+    fn test_oversized_circuit_rejected_without_allocating() {
+        let n = 25;
+        let circuit = QuantumCircuit {
+            id: "too-wide".to_string(),
+            name: "too-wide".to_string(),
+            qubits: (0..n).map(|i| GridQubit::new(0, i as i32)).collect(),
+            gates: vec![
+                (0..n).map(QuantumGate::H).collect(),
+                (0..n).map(|q| QuantumGate::Measure(q, format!("m{}", q))).collect(),
+            ],
+            metadata: HashMap::new(),
+        };
+        let mut sim = QvmSimulator::new(QuantumProcessor::WillowPink);
+
+        for result in [sim.run(&circuit, 10), sim.run_with_kraus(&circuit, 10)] {
+            let error = result.error.expect("oversized circuit should be rejected");
+            assert!(error.contains("25 qubits"), "{}", error);
+            assert!(result.histogram.is_empty());
+            assert!(result.measurements.is_empty());
+            assert!(sim.state_vector().is_none());
+        }
+        assert!(sim.statevector_after(&circuit).is_empty());
+        assert!(sim.prepare_state(n).is_err());
+
+        let bell = sim.run(&build_bell_state_circuit(), 10);
+        assert!(bell.error.is_none());
+        assert_eq!(bell.histogram.values().sum::<usize>(), 10);
+    }
+
+    #[test]
+    fn test_validate_rejects_malformed_circuits() {
+        let with_gates = |gates: Vec<QuantumGate>| QuantumCircuit {
+            gates: vec![gates],
+            ..build_bell_state_circuit()
+        };
+
+        assert_eq!(
+            with_gates(vec![QuantumGate::CZ(1, 1)]).validate(),
+            Err(CircuitError::SelfLoop { gate: "CZ(1, 1)".to_string(), qubit: 1 }),
+        );
+        assert!(matches!(
+            with_gates(vec![QuantumGate::CNOT(0, 7)]).validate(),
+            Err(CircuitError::QubitOutOfRange { qubit: 7, qubits: 2, .. }),
+        ));
+        assert_eq!(
+            with_gates(vec![
+                QuantumGate::Measure(0, "m".to_string()),
+                QuantumGate::Measure(1, "m".to_string()),
+            ]).validate(),
+            Err(CircuitError::DuplicateMeasurementKey("m".to_string())),
+        );
+        assert!(build_bell_state_circuit().validate().is_ok());
+    }
+
+    #[test]
+    fn test_classically_controlled_teleportation() {
+        let controlled = |key: &str, gate: QuantumGate| QuantumGate::ClassicalControl {
+            condition_key: key.to_string(),
+            expected: 1,
+            gate: Box::new(gate),
+        };
+        // Teleport |1⟩ from qubit 0 to qubit 2 with feed-forward corrections
+        let circuit = QuantumCircuit {
+            id: "teleport".to_string(),
+            name: "Teleportation".to_string(),
+            qubits: (0..3).map(|c| GridQubit::new(0, c)).collect(),
+            gates: vec![
+                vec![QuantumGate::X(0), QuantumGate::H(1)],
+                vec![QuantumGate::CNOT(1, 2)],
+                vec![QuantumGate::CNOT(0, 1)],
+                vec![QuantumGate::H(0)],
+                vec![
+                    QuantumGate::Measure(0, "m0".to_string()),
+                    QuantumGate::Measure(1, "m1".to_string()),
+                ],
+                vec![controlled("m1", QuantumGate::X(2))],
+                vec![controlled("m0", QuantumGate::Z(2))],
+                vec![QuantumGate::Measure(2, "out".to_string())],
+            ],
+            metadata: HashMap::new(),
+        };
+        assert!(circuit.validate().is_ok());
+
+        let mut simulator = QvmSimulator::new(QuantumProcessor::WillowPink);
+        let result = simulator.run(&circuit, 400);
+
+        let out = &result.measurements["out"];
+        let ones = out.iter().filter(|&&bit| bit == 1).count();
+        assert!(ones as f64 / out.len() as f64 > 0.9, "teleported |1⟩ read as 1 in {}/{}", ones, out.len());
+        // Both correction branches were exercised
+        assert!(result.measurements["m1"].contains(&0) && result.measurements["m1"].contains(&1));
+
+        let unmeasured = QuantumCircuit {
+            gates: vec![vec![controlled("later", QuantumGate::X(0))]],
+            ..build_bell_state_circuit()
+        };
+        assert_eq!(unmeasured.validate(), Err(CircuitError::UnmeasuredCondition("later".to_string())));
+    }
+
+    #[test]
+    fn test_content_hash_ignores_labels() {
+        let bell = build_bell_state_circuit();
+        let renamed = QuantumCircuit {
+            id: "entangler".to_string(),
+            name: "Renamed".to_string(),
+            metadata: HashMap::new(),
+            ..bell.clone()
+        };
+        assert_eq!(bell.content_hash(), renamed.content_hash());
+        assert_eq!(bell.content_hash().len(), 64);
+
+        let mut altered = bell.clone();
+        altered.gates[0] = vec![QuantumGate::X(0)];
+        assert_ne!(bell.content_hash(), altered.content_hash());
+    }
+
+    #[test]
+    fn test_seeded_runs_hit_result_cache() {
+        let circuit = build_ghz_circuit(3);
+        let mut simulator = QvmSimulator::new(QuantumProcessor::WillowPink);
+
+        simulator.run(&circuit, 200);
+        simulator.run(&circuit, 200);
+        assert_eq!(simulator.cache_hits(), 0, "unseeded runs bypass the cache");
+
+        simulator.set_seed(Some(7));
+        let first = simulator.run(&circuit, 200);
+        let second = simulator.run(&circuit, 200);
+        assert_eq!(simulator.cache_hits(), 1);
+        assert_eq!(first.histogram, second.histogram);
+
+        simulator.run(&circuit, 300);
+        assert_eq!(simulator.cache_hits(), 1, "different repetitions miss");
+
+        // The cached result matches a fresh simulator with the same seed
+        let mut fresh = QvmSimulator::new(QuantumProcessor::WillowPink);
+        fresh.set_seed(Some(7));
+        assert_eq!(fresh.run(&circuit, 200).measurements, first.measurements);
+    }
+
+    #[test]
+    fn test_result_statistics_capture_noise() {
+        let bell = build_bell_state_circuit();
+        let ideal: HashMap<u64, f64> = [(0, 0.5), (3, 0.5)].into_iter().collect();
+        let clean = CircuitResult {
+            circuit_id: bell.id.clone(),
+            repetitions: 1000,
+            measurements: HashMap::new(),
+            histogram: [(0, 500), (3, 500)].into_iter().collect(),
+            execution_time_ms: 0.0,
+            fidelity_estimate: 1.0,
+            noise_applied: false,
+            most_probable_outcome: None,
+            entropy_bits: 0.0,
+            error: None,
+        }.with_statistics();
+
+        assert!((clean.entropy_bits - 1.0).abs() < 1e-12);
+        assert_eq!(clean.most_probable_outcome, Some(0));
+        assert!(clean.tvd_from(&ideal) < 1e-12);
+
+        let mut simulator = QvmSimulator::new(QuantumProcessor::Rainbow);
+        simulator.set_seed(Some(11));
+        let noisy = simulator.run_with_kraus(&bell, 4000);
+
+        assert!(noisy.histogram.keys().any(|outcome| *outcome == 1 || *outcome == 2));
+        assert!(noisy.entropy() > clean.entropy(), "{} <= {}", noisy.entropy(), clean.entropy());
+        assert!(noisy.tvd_from(&ideal) > 0.0);
+        assert!(matches!(noisy.most_probable_outcome, Some(0) | Some(3)));
+    }
+
+    #[test]
+    fn test_iswap_fidelity_uses_fsim_error_norm() {
+        let picker = QubitPicker::new(QuantumProcessor::Rainbow);
+        let qubits = vec![GridQubit::new(3, 3), GridQubit::new(3, 4)];
+        let pair = picker.get_pair_error(qubits[0], qubits[1]).unwrap().clone();
+        assert_ne!(pair.fsim_error_norm, pair.pauli_error);
+
+        let circuit = |gate: fn(usize, usize) -> QuantumGate| QuantumCircuit {
+            id: "native".to_string(),
+            name: "native".to_string(),
+            qubits: qubits.clone(),
+            gates: (0..10).map(|_| vec![gate(0, 1)]).collect(),
+            metadata: HashMap::new(),
+        };
+        let strategy = QubitPickingStrategy::Balanced;
+        let baseline = picker.estimate_fidelity(&qubits, &[]);
+        let iswap = picker.estimate_circuit_fidelity(&qubits, &circuit(QuantumGate::ISWAP), strategy);
+        let cz = picker.estimate_circuit_fidelity(&qubits, &circuit(QuantumGate::CZ), strategy);
+
+        assert!((iswap - baseline * (1.0 - pair.fsim_error_norm).powi(10)).abs() < 1e-12);
+        assert!((cz - baseline * (1.0 - pair.pauli_error).powi(10)).abs() < 1e-12);
+
+        let noise = NoiseModel::from_processor(QuantumProcessor::Rainbow);
+        assert_eq!(noise.two_qubit_error(TwoQubitGateFamily::Cz), QuantumProcessor::Rainbow.two_qubit_error_rate());
+        assert_ne!(noise.two_qubit_error(TwoQubitGateFamily::ISwap), noise.two_qubit_error(TwoQubitGateFamily::Cz));
+    }
+
+    #[test]
+    fn test_qft_gate_counts() {
+        for n in 1..=6 {
+            let circuit = build_qft_circuit(n);
+            let count = |pred: fn(&QuantumGate) -> bool| circuit.gates.iter().flatten().filter(|g| pred(g)).count();
+            let hadamards = count(|g| matches!(g, QuantumGate::H(_)));
+            let cphases = count(|g| matches!(g, QuantumGate::CPhase(..)));
+
+            assert_eq!(hadamards, n);
+            assert_eq!(cphases, n * (n - 1) / 2);
+            // Hadamards plus rotations: the n(n+1)/2 textbook gate count
+            assert_eq!(hadamards + cphases, n * (n + 1) / 2);
+            assert!(circuit.validate_width(n).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_qft_handles_registers_wider_than_64_qubits() {
+        let register: Vec<usize> = (0..70).collect();
+        let smallest = qft_moments(&register, false).iter().flatten()
+            .filter_map(|g| match g {
+                QuantumGate::CPhase(_, _, theta) => Some(*theta),
+                _ => None,
+            })
+            .fold(f64::INFINITY, f64::min);
+        assert!(smallest > 0.0 && (smallest - std::f64::consts::PI / 2f64.powi(69)).abs() < 1e-30);
+    }
+
+    #[test]
+    fn test_inverse_qft_undoes_qft() {
+        let register: Vec<usize> = (0..4).collect();
+        let mut gates = vec![vec![QuantumGate::X(0), QuantumGate::X(2)]];
+        gates.extend(qft_moments(&register, false));
+        gates.extend(qft_moments(&register, true));
+        let circuit = QuantumCircuit {
+            id: "qft_round_trip".to_string(),
+            name: "QFT round trip".to_string(),
+            qubits: (0..4).map(|i| GridQubit::new(i, 0)).collect(),
+            gates,
+            metadata: HashMap::new(),
+        };
+
+        let mut sim = QvmSimulator::new(QuantumProcessor::Rainbow);
+        let probs = sim.ideal_probabilities(&circuit);
+        assert!((probs[0b0101] - 1.0).abs() < 1e-9, "{:?}", probs);
+
+        // CPhase(π) is CZ
+        let cz = QuantumCircuit { gates: vec![vec![QuantumGate::H(0), QuantumGate::H(1)], vec![QuantumGate::CZ(0, 1)]], ..circuit.clone() };
+        let cphase = QuantumCircuit { gates: vec![vec![QuantumGate::H(0), QuantumGate::H(1)], vec![QuantumGate::CPhase(0, 1, std::f64::consts::PI)]], ..circuit };
+        assert!(states_equal(&sim.statevector_after(&cz), &sim.statevector_after(&cphase), 1e-9));
+    }
+
+    #[test]
+    fn test_modular_exponentiation_stub_structure() {
+        let circuit = build_modular_exponentiation_stub(4, 2);
+        assert_eq!(circuit.qubits.len(), 6);
+        assert!(circuit.validate_width(6).is_ok());
+        let measured: Vec<_> = circuit.gates.iter().flatten()
+            .filter_map(|g| match g { QuantumGate::Measure(q, _) => Some(*q), _ => None })
+            .collect();
+        assert_eq!(measured.len(), 4);
+        assert!(measured.iter().all(|q| *q < 4));
+        assert_eq!(circuit.gates.iter().flatten().filter(|g| matches!(g, QuantumGate::CPhase(..))).count(), 6);
+    }
+
+    #[test]
+    fn test_bad_readout_qubit_reports_excess_ones() {
+        let mut sim = QvmSimulator::new(QuantumProcessor::Custom {
+            qubits: 4,
+            connectivity: ConnectivityType::Grid,
+        });
+        assert_eq!(sim.noise_model.readout_errors.len(), 4);
+        // Qubit 1 gets a badly miscalibrated discriminator that often reads |0⟩ as 1
+        sim.noise_model.readout_errors.insert("1".to_string(), (0.05, 0.25));
+
+        let device_average = sim.noise_model.readout_errors.values()
+            .map(|(_, excitation)| excitation)
+            .sum::<f64>() / sim.noise_model.readout_errors.len() as f64;
+
+        let shots = 4000;
+        let mut ones = 0;
+        for _ in 0..shots {
+            sim.prepare_state(2).unwrap();
+            ones += sim.measure_qubit(1) as usize;
+        }
+        let measured_one_rate = ones as f64 / shots as f64;
+
+        assert!(measured_one_rate > device_average, "{} <= {}", measured_one_rate, device_average);
+    }
+
+    #[cfg(not(feature = "qvm-fault-injection"))]
+    #[test]
+    fn test_golden_selftest_passes() {
+        let report = run_golden_selftest();
+        assert_eq!(report.circuits.len(), 3);
+        assert!(report.passed, "{:?}", report);
+    }
+
+    #[cfg(feature = "qvm-fault-injection")]
+    #[test]
+    fn test_golden_selftest_detects_faulty_gate() {
+        let report = run_golden_selftest();
+        assert!(!report.passed);
+        assert!(report.circuits.iter().any(|c| !c.passed));
+    }
+
+    fn state_after(n_qubits: usize, gates: &[QuantumGate]) -> Vec<Complex> {
+        let mut sim = QvmSimulator::new(QuantumProcessor::WillowPink);
+        sim.prepare_state(n_qubits).unwrap();
+        for gate in gates {
+            sim.apply_gate(gate);
+        }
+        sim.state_vector().unwrap().to_vec()
+    }
+
+    /// |⟨a|b⟩| == 1 means the states match up to a global phase
+    fn overlap(a: &[Complex], b: &[Complex]) -> f64 {
+        let inner = a.iter().zip(b).fold(Complex::zero(), |acc, (x, y)| {
+            acc.add(&Complex::new(x.real, -x.imag).mul(y))
+        });
+        inner.norm_squared().sqrt()
+    }
+
+    #[test]
+    fn test_rx_pi_matches_x_up_to_global_phase() {
+        let rx = state_after(1, &[QuantumGate::Rx(0, std::f64::consts::PI)]);
+        let x = state_after(1, &[QuantumGate::X(0)]);
+
+        assert!((overlap(&rx, &x) - 1.0).abs() < 1e-12);
+        assert!(rx[0].norm_squared() < 1e-24);
+    }
+
+    #[test]
+    fn test_t_twice_equals_s() {
+        let tt = state_after(2, &[QuantumGate::H(0), QuantumGate::H(1), QuantumGate::T(0), QuantumGate::T(0)]);
+        let s = state_after(2, &[QuantumGate::H(0), QuantumGate::H(1), QuantumGate::S(0)]);
+
+        for (a, b) in tt.iter().zip(&s) {
+            assert!((a.real - b.real).abs() < 1e-12);
+            assert!((a.imag - b.imag).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_iswap_and_sqrt_iswap() {
+        // iSWAP |01⟩ = i|10⟩ (qubit 0 is the least significant bit)
+        let iswap = state_after(2, &[QuantumGate::X(0), QuantumGate::ISWAP(0, 1)]);
+        assert!((iswap[2].imag - 1.0).abs() < 1e-12);
+        assert!(iswap[1].norm_squared() < 1e-24);
+
+        // Two √iSWAPs compose to iSWAP
+        let sqrt_twice = state_after(2, &[QuantumGate::X(0), QuantumGate::SqrtISWAP(0, 1), QuantumGate::SqrtISWAP(0, 1)]);
+        for (a, b) in sqrt_twice.iter().zip(&iswap) {
+            assert!((a.real - b.real).abs() < 1e-12);
+            assert!((a.imag - b.imag).abs() < 1e-12);
+        }
+    }
 }
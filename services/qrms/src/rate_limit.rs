@@ -0,0 +1,87 @@
+//! Per-client token bucket rate limiting
+//! Guards endpoints that feed the risk engine, such as threat injection
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Instant;
+
+/// Default threat injections allowed per client per minute
+pub const DEFAULT_INJECTIONS_PER_MINUTE: u32 = 10;
+
+/// Buckets tracked before idle (full) ones are pruned
+const MAX_TRACKED_CLIENTS: usize = 4096;
+
+/// Token bucket per client IP: `capacity` calls in a burst, refilled over a minute
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// Client -> (tokens remaining, last refill)
+    buckets: HashMap<IpAddr, (f64, Instant)>,
+}
+
+impl RateLimiter {
+    /// Allow `limit` calls per minute per client (minimum 1)
+    pub fn per_minute(limit: u32) -> Self {
+        let capacity = limit.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: capacity / 60.0,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Take a token for `client`; false when its bucket is empty
+    pub fn check(&mut self, client: IpAddr) -> bool {
+        self.check_at(client, Instant::now())
+    }
+
+    /// `check` at an explicit time
+    pub fn check_at(&mut self, client: IpAddr, now: Instant) -> bool {
+        if self.buckets.len() >= MAX_TRACKED_CLIENTS {
+            self.prune(now);
+        }
+
+        let (tokens, last) = self.buckets.entry(client).or_insert((self.capacity, now));
+        let elapsed = now.saturating_duration_since(*last).as_secs_f64();
+        *tokens = (*tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        *last = now;
+
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forget clients whose buckets would have refilled completely
+    fn prune(&mut self, now: Instant) {
+        let (capacity, rate) = (self.capacity, self.refill_per_sec);
+        self.buckets.retain(|_, (tokens, last)| {
+            *tokens + now.saturating_duration_since(*last).as_secs_f64() * rate < capacity
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_bucket_refills_over_the_window() {
+        let mut limiter = RateLimiter::per_minute(3);
+        let client: IpAddr = [10, 0, 0, 1].into();
+        let other: IpAddr = [10, 0, 0, 2].into();
+        let start = Instant::now();
+
+        assert!((0..3).all(|_| limiter.check_at(client, start)));
+        assert!(!limiter.check_at(client, start));
+        assert!(limiter.check_at(other, start), "clients have separate buckets");
+
+        // One token every 20s at 3/minute
+        assert!(limiter.check_at(client, start + Duration::from_secs(20)));
+        assert!(!limiter.check_at(client, start + Duration::from_secs(21)));
+        assert!((0..3).all(|_| limiter.check_at(client, start + Duration::from_secs(120))));
+    }
+}
@@ -3,12 +3,18 @@
 
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
+use hmac::{Hmac, Mac};
 use hex;
 use chrono::{DateTime, Utc};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::aegis_tee::{DecryptedTransaction, IntelligenceOrdering};
 use crate::apqc::AdaptivePqcLayer;
+use crate::crypto::CryptoError;
+use crate::ordering::{self, OrderingCandidate, OrderingContext};
 
 /// Transaction status
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -28,22 +34,59 @@ pub struct Transaction {
     pub data: String,
     pub timestamp: DateTime<Utc>,
     pub priority_fee: u64,
+    /// Per-sender sequence number; must strictly increase across submissions
+    #[serde(default)]
+    pub nonce: u64,
+    /// Risk score weighed by intelligence ordering
+    #[serde(default)]
+    pub risk_level: u32,
     pub status: TxStatus,
 }
 
 impl Transaction {
-    pub fn new(sender: String, data: String, priority_fee: u64) -> Self {
+    pub fn new(sender: String, data: String, priority_fee: u64, nonce: u64) -> Self {
         Self {
             tx_id: format!("tx_{}", Uuid::new_v4().simple()),
             sender,
             data,
             timestamp: Utc::now(),
             priority_fee,
+            nonce,
+            risk_level: 0,
             status: TxStatus::Pending,
         }
     }
 }
 
+/// Reasons a transaction is refused by the sequencer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitError {
+    /// Nonce equals the sender's last accepted nonce
+    ReplayedNonce { sender: String, nonce: u64 },
+    /// Nonce is below the sender's last accepted nonce
+    NonceTooLow { sender: String, nonce: u64, last_accepted: u64 },
+    /// Mempool is at capacity; retry once a batch drains it
+    MempoolFull { capacity: usize },
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReplayedNonce { sender, nonce } => {
+                write!(f, "nonce {} already used by {}", nonce, sender)
+            }
+            Self::NonceTooLow { sender, nonce, last_accepted } => write!(
+                f,
+                "nonce {} for {} is below last accepted nonce {}",
+                nonce, sender, last_accepted
+            ),
+            Self::MempoolFull { capacity } => write!(f, "mempool full ({} transactions)", capacity),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
 /// TEE attestation data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeeAttestation {
@@ -65,14 +108,118 @@ pub struct Batch {
     pub slh_dsa_sig: String,
     pub attestation: TeeAttestation,
     pub timestamp: DateTime<Utc>,
+    /// Uniform price paid by every included tx (batch auction mode only)
+    #[serde(default)]
+    pub clearing_price: Option<u64>,
+    /// Signature algorithms the batch was actually signed with
+    #[serde(default)]
+    pub signature_algorithms: Vec<String>,
+    /// Beacon seed the transactions were shuffled with (verifiable random mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beacon: Option<String>,
 }
 
 /// Ordering mode for transactions
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderingMode {
-    Fcfs,           // First-come-first-served
-    BatchAuction,   // Periodic batch with uniform price
+    Fcfs,             // First-come-first-served
+    BatchAuction,     // Periodic batch with uniform price
+    VerifiableRandom, // Keyed-hash shuffle under a beacon committed in the batch
+}
+
+/// Hashed into the beacon used before any verifiable-random batch has been signed
+const GENESIS_BEACON: &[u8] = b"QRMS-Beacon-genesis";
+
+/// Domain separation for beacons derived from batch signatures
+const BEACON_DOMAIN: &[u8] = b"QRMS-Beacon-v1";
+
+/// Order transactions by HMAC-SHA256(beacon, tx_id)
+/// Anyone holding the batch and its beacon can recompute the order.
+pub fn beacon_order(txs: &mut [Transaction], beacon: &str) {
+    txs.sort_by_cached_key(|tx| {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(beacon.as_bytes())
+            .expect("HMAC accepts any key size");
+        mac.update(tx.tx_id.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    });
+}
+
+/// Next block's beacon, derived from the ML-DSA signature over this batch
+pub fn next_beacon(ml_dsa_sig: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(BEACON_DOMAIN);
+    hasher.update(ml_dsa_sig.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Reorder selected transactions with an intelligence strategy
+/// Plain transactions carry no asset refs, so only risk, fee and migration tags apply.
+fn intelligence_order(mode: IntelligenceOrdering, txs: Vec<Transaction>) -> Vec<Transaction> {
+    let candidates: Vec<OrderingCandidate> = txs
+        .iter()
+        .map(|tx| {
+            let decrypted = DecryptedTransaction {
+                tx_id: tx.tx_id.clone(),
+                sender: tx.sender.clone(),
+                data: tx.data.clone(),
+                asset_refs: vec![],
+                priority_fee: tx.priority_fee,
+                timestamp: tx.timestamp,
+            };
+            (decrypted, tx.risk_level, vec![])
+        })
+        .collect();
+    let registry = HashMap::new();
+    let ctx = OrderingContext { asset_registry: &registry, migration_in_progress: false };
+
+    let mut by_id: HashMap<String, Transaction> =
+        txs.into_iter().map(|tx| (tx.tx_id.clone(), tx)).collect();
+    ordering::order(mode, candidates, ctx)
+        .into_iter()
+        .filter_map(|tx| by_id.remove(&tx.tx_id))
+        .collect()
+}
+
+/// Common interface over the plain, Aegis-TEE, and Phala sequencers
+/// `tee_key` decrypts the encrypted mempool; sequencers without one ignore it.
+#[async_trait::async_trait]
+pub trait Sequencer: Send {
+    /// Transaction as submitted to the mempool
+    type Tx: Send;
+    /// Transaction as emitted by an ordering pass
+    type Ordered;
+    /// Signed batch
+    type Batch: Send;
+
+    /// Add a transaction to the mempool
+    fn submit(&mut self, tx: Self::Tx) -> Result<(), SubmitError>;
+
+    /// Run one ordering pass over the mempool
+    fn order(&mut self, tee_key: &[u8]) -> Vec<Self::Ordered>;
+
+    /// Order whatever is pending and sign it into a batch
+    /// `Ok(None)` means there was nothing to batch yet.
+    async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer, tee_key: &[u8]) -> Result<Option<Self::Batch>, CryptoError>;
+
+    /// Most recent batches, newest first
+    fn recent_batches(&self, count: usize) -> Vec<Self::Batch>;
+}
+
+/// Upper bound on transactions per batch
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Default mempool capacity before submissions are refused
+pub const DEFAULT_MAX_MEMPOOL: usize = 10_000;
+
+/// Default number of senders whose last nonce is remembered
+pub const DEFAULT_MAX_TRACKED_SENDERS: usize = 100_000;
+
+/// When the last block was produced, on whichever clock stamped it
+#[derive(Debug, Clone, Copy)]
+enum BlockMark {
+    Wall(Instant),
+    Logical(DateTime<Utc>),
 }
 
 /// TEE Sequencer
@@ -80,11 +227,31 @@ pub struct TeeSequencer {
     encrypted_mempool: VecDeque<Transaction>,
     ordered_queue: VecDeque<Transaction>,
     batches: Vec<Batch>,
+    /// Highest accepted nonce per sender, with the stamp of its last acceptance
+    sender_nonces: HashMap<String, (u64, u64)>,
+    /// Senders in acceptance order; entries whose stamp is outdated are skipped
+    nonce_lru: VecDeque<(String, u64)>,
+    nonce_stamp: u64,
+    /// Senders tracked before the least recently active is forgotten
+    /// A forgotten sender's nonce sequence restarts, so keep this well above
+    /// the number of senders active within a replay window.
+    pub max_tracked_senders: usize,
     pub current_block: u64,
     pub batch_size: usize,
+    /// Pending transactions accepted before submissions are refused
+    pub max_mempool: usize,
     pub ordering_mode: OrderingMode,
+    /// Intelligence strategy applied on top of FCFS and batch auction selection
+    pub intelligence_mode: Option<IntelligenceOrdering>,
     pub tee_platform: String,
     pub mrenclave: String,
+    /// Minimum wall-clock spacing between produced blocks
+    pub min_block_interval: Duration,
+    /// Time stamped on batches and attestations; None follows the wall clock
+    pub clock: Option<DateTime<Utc>>,
+    last_block_at: Option<BlockMark>,
+    /// Seed for the next verifiable-random batch
+    beacon: String,
 }
 
 impl TeeSequencer {
@@ -97,19 +264,75 @@ impl TeeSequencer {
             encrypted_mempool: VecDeque::with_capacity(1000),
             ordered_queue: VecDeque::with_capacity(1000),
             batches: Vec::with_capacity(1000),
+            sender_nonces: HashMap::new(),
+            nonce_lru: VecDeque::new(),
+            nonce_stamp: 0,
+            max_tracked_senders: DEFAULT_MAX_TRACKED_SENDERS,
             current_block: 0,
             batch_size: 5,
+            max_mempool: DEFAULT_MAX_MEMPOOL,
             ordering_mode: OrderingMode::Fcfs,
+            intelligence_mode: None,
             tee_platform: "SGX".to_string(),
             mrenclave,
+            min_block_interval: Duration::from_millis(1000),
+            clock: None,
+            last_block_at: None,
+            beacon: hex::encode(Sha256::digest(GENESIS_BEACON)),
         }
     }
 
     /// Submit transaction to encrypted mempool
-    pub fn submit_transaction(&mut self, mut tx: Transaction) -> Transaction {
+    /// Rejects nonces that do not exceed the sender's last accepted nonce,
+    /// and any transaction once the mempool holds `max_mempool` entries
+    pub fn submit_transaction(&mut self, mut tx: Transaction) -> Result<Transaction, SubmitError> {
+        if self.encrypted_mempool.len() >= self.max_mempool {
+            return Err(SubmitError::MempoolFull { capacity: self.max_mempool });
+        }
+        if let Some(&(last_accepted, _)) = self.sender_nonces.get(&tx.sender) {
+            if tx.nonce == last_accepted {
+                return Err(SubmitError::ReplayedNonce { sender: tx.sender, nonce: tx.nonce });
+            }
+            if tx.nonce < last_accepted {
+                return Err(SubmitError::NonceTooLow { sender: tx.sender, nonce: tx.nonce, last_accepted });
+            }
+        }
+        self.record_nonce(&tx.sender, tx.nonce);
+
         tx.status = TxStatus::Pending;
         self.encrypted_mempool.push_back(tx.clone());
-        tx
+        Ok(tx)
+    }
+
+    /// Remember `sender`'s nonce, forgetting the least recently active senders
+    /// beyond `max_tracked_senders`
+    fn record_nonce(&mut self, sender: &str, nonce: u64) {
+        self.nonce_stamp += 1;
+        self.sender_nonces.insert(sender.to_string(), (nonce, self.nonce_stamp));
+        self.nonce_lru.push_back((sender.to_string(), self.nonce_stamp));
+
+        let cap = self.max_tracked_senders.max(1);
+        while self.sender_nonces.len() > cap {
+            let Some((oldest, stamp)) = self.nonce_lru.pop_front() else { break };
+            if self.sender_nonces.get(&oldest).is_some_and(|&(_, current)| current == stamp) {
+                self.sender_nonces.remove(&oldest);
+            }
+        }
+        // Drop outdated entries left behind by senders that submitted again
+        if self.nonce_lru.len() > 2 * cap {
+            let live = &self.sender_nonces;
+            self.nonce_lru.retain(|(sender, stamp)| live.get(sender).is_some_and(|&(_, current)| current == *stamp));
+        }
+    }
+
+    /// Number of senders whose last nonce is remembered
+    pub fn tracked_senders(&self) -> usize {
+        self.sender_nonces.len()
+    }
+
+    /// Beacon the next verifiable-random batch will be ordered with
+    pub fn beacon(&self) -> &str {
+        &self.beacon
     }
 
     /// Get mempool size
@@ -132,29 +355,44 @@ impl TeeSequencer {
         self.batches.iter().rev().take(count).cloned().collect()
     }
 
+    /// Stored batch with the given ID
+    pub fn get_batch(&self, batch_id: &str) -> Option<&Batch> {
+        self.batches.iter().rev().find(|b| b.batch_id == batch_id)
+    }
+
     /// Decrypt and order transactions (simulate TEE operation)
     pub fn decrypt_and_order(&mut self) -> Vec<Transaction> {
         if self.encrypted_mempool.is_empty() {
             return vec![];
         }
 
-        // Take up to batch_size transactions
-        let mut to_order = Vec::with_capacity(self.batch_size);
-        for _ in 0..self.batch_size {
-            if let Some(tx) = self.encrypted_mempool.pop_front() {
-                to_order.push(tx);
-            } else {
-                break;
-            }
-        }
-
-        // Sort by timestamp (FCFS) or by priority fee (auction)
-        match self.ordering_mode {
-            OrderingMode::Fcfs => {
-                to_order.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        let mut to_order = match self.ordering_mode {
+            // Verifiable random shuffles at batch creation, once the batch's beacon is fixed
+            OrderingMode::Fcfs | OrderingMode::VerifiableRandom => {
+                // Take up to batch_size transactions in arrival order
+                let take = self.batch_size.min(self.encrypted_mempool.len());
+                let mut txs: Vec<Transaction> = self.encrypted_mempool.drain(..take).collect();
+                txs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+                txs
             }
             OrderingMode::BatchAuction => {
-                to_order.sort_by(|a, b| b.priority_fee.cmp(&a.priority_fee));
+                // All pending txs bid; the highest batch_size fees win, ties by arrival
+                let mut bids: Vec<Transaction> = self.encrypted_mempool.drain(..).collect();
+                bids.sort_by(|a, b| {
+                    b.priority_fee.cmp(&a.priority_fee).then(a.timestamp.cmp(&b.timestamp))
+                });
+                // Outbid txs return to the mempool in arrival order for the next round
+                let mut losers = bids.split_off(self.batch_size.min(bids.len()));
+                losers.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+                self.encrypted_mempool.extend(losers);
+                bids
+            }
+        };
+
+        // Verifiable random order is fixed by the beacon, so intelligence only reorders the others
+        if let Some(mode) = self.intelligence_mode {
+            if !matches!(self.ordering_mode, OrderingMode::VerifiableRandom) {
+                to_order = intelligence_order(mode, to_order);
             }
         }
 
@@ -167,10 +405,23 @@ impl TeeSequencer {
         to_order
     }
 
+    /// Check whether the minimum block interval has elapsed since the last batch
+    /// Measured on `clock` when the last block was stamped by it, otherwise on wall time.
+    pub fn can_produce_block(&self) -> bool {
+        let elapsed = match self.last_block_at {
+            None => return true,
+            Some(BlockMark::Wall(at)) => at.elapsed(),
+            Some(BlockMark::Logical(at)) => (self.now() - at).to_std().unwrap_or_default(),
+        };
+        elapsed >= self.min_block_interval
+    }
+
     /// Create and sign a batch
-    pub async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer) -> Option<Batch> {
-        if self.ordered_queue.is_empty() {
-            return None;
+    /// Returns `Ok(None)` while rate limited; ordered transactions stay buffered for the next block.
+    /// A signing failure also leaves the transactions queued.
+    pub async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer) -> Result<Option<Batch>, CryptoError> {
+        if self.ordered_queue.is_empty() || !self.can_produce_block() {
+            return Ok(None);
         }
 
         // Take transactions for batch
@@ -185,9 +436,21 @@ impl TeeSequencer {
         }
 
         if txs.is_empty() {
-            return None;
+            return Ok(None);
         }
 
+        // Winners pay the lowest included bid
+        let clearing_price = match self.ordering_mode {
+            OrderingMode::Fcfs | OrderingMode::VerifiableRandom => None,
+            OrderingMode::BatchAuction => txs.iter().map(|tx| tx.priority_fee).min(),
+        };
+
+        // Shuffle under the current beacon before signing so the order is committed
+        let beacon = matches!(self.ordering_mode, OrderingMode::VerifiableRandom).then(|| {
+            beacon_order(&mut txs, &self.beacon);
+            self.beacon.clone()
+        });
+
         // Create batch data
         let batch_data = serde_json::to_vec(&txs).unwrap_or_default();
         
@@ -196,7 +459,26 @@ impl TeeSequencer {
         let batch_id = hex::encode(&hasher.finalize()[..8]);
 
         // Sign with dual PQC (real implementation)
-        let signatures = apqc.sign_dual(&batch_data).await;
+        let signatures = match apqc.sign_dual(&batch_data).await {
+            Ok(signatures) => signatures,
+            Err(e) => {
+                for mut tx in txs.into_iter().rev() {
+                    tx.status = TxStatus::Ordered;
+                    self.ordered_queue.push_front(tx);
+                }
+                return Err(e);
+            }
+        };
+
+        let mut signature_algorithms = vec![
+            signatures.ml_dsa.algorithm.clone(),
+            signatures.slh_dsa.algorithm.clone(),
+        ];
+        signature_algorithms.extend(signatures.falcon.as_ref().map(|sig| sig.algorithm.clone()));
+
+        if beacon.is_some() {
+            self.beacon = next_beacon(&signatures.ml_dsa.signature);
+        }
 
         // Generate TEE attestation
         let attestation = self.generate_attestation(&batch_id);
@@ -207,19 +489,32 @@ impl TeeSequencer {
             ml_dsa_sig: signatures.ml_dsa.signature,
             slh_dsa_sig: signatures.slh_dsa.signature,
             attestation,
-            timestamp: Utc::now(),
+            timestamp: self.now(),
+            clearing_price,
+            signature_algorithms,
+            beacon,
         };
 
         self.batches.push(batch.clone());
         self.current_block += 1;
+        self.last_block_at = Some(match self.clock {
+            Some(now) => BlockMark::Logical(now),
+            None => BlockMark::Wall(Instant::now()),
+        });
 
-        Some(batch)
+        Ok(Some(batch))
+    }
+
+    /// Current sequencer time: `clock` if set, otherwise the wall clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.unwrap_or_else(Utc::now)
     }
 
     /// Generate mock TEE attestation
     fn generate_attestation(&self, batch_id: &str) -> TeeAttestation {
+        let now = self.now();
         let mut hasher = Sha256::new();
-        hasher.update(format!("{}", Utc::now().timestamp_nanos_opt().unwrap_or(0)).as_bytes());
+        hasher.update(format!("{}", now.timestamp_nanos_opt().unwrap_or(0)).as_bytes());
         let nonce = hex::encode(&hasher.finalize()[..8]);
 
         let mut report_hasher = Sha256::new();
@@ -238,7 +533,7 @@ impl TeeSequencer {
             mrsigner,
             report_data,
             nonce,
-            timestamp: Utc::now(),
+            timestamp: now,
             pqc_signed: true,
         }
     }
@@ -249,3 +544,328 @@ impl Default for TeeSequencer {
         Self::new()
     }
 }
+
+#[async_trait::async_trait]
+impl Sequencer for TeeSequencer {
+    type Tx = Transaction;
+    type Ordered = Transaction;
+    type Batch = Batch;
+
+    fn submit(&mut self, tx: Transaction) -> Result<(), SubmitError> {
+        self.submit_transaction(tx).map(|_| ())
+    }
+
+    fn order(&mut self, _tee_key: &[u8]) -> Vec<Transaction> {
+        self.decrypt_and_order()
+    }
+
+    async fn create_batch(&mut self, apqc: &mut AdaptivePqcLayer, _tee_key: &[u8]) -> Result<Option<Batch>, CryptoError> {
+        self.decrypt_and_order();
+        TeeSequencer::create_batch(self, apqc).await
+    }
+
+    fn recent_batches(&self, count: usize) -> Vec<Batch> {
+        self.get_recent_batches(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submit_and_order(sequencer: &mut TeeSequencer, count: usize, nonce: u64) {
+        for i in 0..count {
+            sequencer
+                .submit_transaction(Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, nonce))
+                .unwrap();
+        }
+        sequencer.decrypt_and_order();
+    }
+
+    #[tokio::test]
+    async fn test_block_production_respects_min_interval() {
+        let mut sequencer = TeeSequencer::new();
+        let mut apqc = AdaptivePqcLayer::new();
+        sequencer.min_block_interval = Duration::from_millis(50);
+        sequencer.batch_size = 2;
+        submit_and_order(&mut sequencer, 2, 0);
+
+        let first = sequencer.create_batch(&mut apqc).await.unwrap().expect("first block");
+        let first_at = Instant::now();
+
+        // Rate limited: transactions stay buffered in the ordered queue
+        submit_and_order(&mut sequencer, 2, 1);
+        assert!(sequencer.create_batch(&mut apqc).await.unwrap().is_none());
+        assert_eq!(sequencer.ordered_queue_size(), 2);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let second = sequencer.create_batch(&mut apqc).await.unwrap().expect("second block");
+
+        assert!(first_at.elapsed() >= sequencer.min_block_interval);
+        assert!(second.timestamp - first.timestamp >= chrono::Duration::milliseconds(50));
+        assert_eq!(sequencer.batch_count(), 2);
+        assert_eq!(sequencer.ordered_queue_size(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_logical_clock_paces_blocks() {
+        let mut sequencer = TeeSequencer::new();
+        let mut apqc = AdaptivePqcLayer::new();
+        let start = DateTime::<Utc>::UNIX_EPOCH;
+        sequencer.clock = Some(start);
+        sequencer.batch_size = 2;
+        submit_and_order(&mut sequencer, 2, 0);
+        sequencer.create_batch(&mut apqc).await.unwrap().expect("first block");
+
+        // Wall time passing does not matter; only the logical clock does
+        submit_and_order(&mut sequencer, 2, 1);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        sequencer.clock = Some(start + chrono::Duration::milliseconds(500));
+        assert!(sequencer.create_batch(&mut apqc).await.unwrap().is_none());
+
+        sequencer.clock = Some(start + chrono::Duration::from_std(sequencer.min_block_interval).unwrap());
+        assert!(sequencer.create_batch(&mut apqc).await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_accepts_increasing_nonces() {
+        let mut sequencer = TeeSequencer::new();
+        for nonce in [0, 1, 5] {
+            let tx = Transaction::new("0xaa".to_string(), "transfer(1)".to_string(), 1, nonce);
+            assert!(sequencer.submit_transaction(tx).is_ok());
+        }
+        assert_eq!(sequencer.mempool_size(), 3);
+    }
+
+    #[test]
+    fn test_rejects_replayed_nonce() {
+        let mut sequencer = TeeSequencer::new();
+        let tx = Transaction::new("0xaa".to_string(), "transfer(1)".to_string(), 1, 3);
+        sequencer.submit_transaction(tx.clone()).unwrap();
+
+        assert_eq!(
+            sequencer.submit_transaction(tx).unwrap_err(),
+            SubmitError::ReplayedNonce { sender: "0xaa".to_string(), nonce: 3 }
+        );
+        assert_eq!(sequencer.mempool_size(), 1);
+    }
+
+    #[test]
+    fn test_rejects_lower_nonce() {
+        let mut sequencer = TeeSequencer::new();
+        sequencer.submit_transaction(Transaction::new("0xaa".to_string(), "transfer(1)".to_string(), 1, 4)).unwrap();
+
+        let stale = Transaction::new("0xaa".to_string(), "transfer(2)".to_string(), 1, 2);
+        assert_eq!(
+            sequencer.submit_transaction(stale).unwrap_err(),
+            SubmitError::NonceTooLow { sender: "0xaa".to_string(), nonce: 2, last_accepted: 4 }
+        );
+
+        // Other senders keep their own nonce sequence
+        let other = Transaction::new("0xbb".to_string(), "transfer(3)".to_string(), 1, 2);
+        assert!(sequencer.submit_transaction(other).is_ok());
+    }
+
+    #[test]
+    fn test_sender_nonce_map_is_capped() {
+        let mut sequencer = TeeSequencer::new();
+        sequencer.max_tracked_senders = 3;
+        let tx = |sender: &str, nonce| Transaction::new(sender.to_string(), "transfer(1)".to_string(), 1, nonce);
+
+        for sender in ["0x01", "0x02", "0x03"] {
+            sequencer.submit_transaction(tx(sender, 5)).unwrap();
+        }
+        // Refreshing 0x01 makes 0x02 the least recently active
+        sequencer.submit_transaction(tx("0x01", 6)).unwrap();
+        for sender in ["0x04", "0x05"] {
+            sequencer.submit_transaction(tx(sender, 5)).unwrap();
+        }
+
+        assert_eq!(sequencer.tracked_senders(), 3);
+        assert!(sequencer.nonce_lru.len() <= 6);
+        assert!(sequencer.submit_transaction(tx("0x02", 5)).is_ok(), "forgotten sender restarts");
+        assert!(matches!(sequencer.submit_transaction(tx("0x05", 5)), Err(SubmitError::ReplayedNonce { .. })));
+    }
+
+    #[test]
+    fn test_full_mempool_rejects_submissions() {
+        let mut sequencer = TeeSequencer::new();
+        sequencer.max_mempool = 4;
+        for nonce in 0..4 {
+            let tx = Transaction::new("0xaa".to_string(), "transfer(1)".to_string(), 1, nonce);
+            sequencer.submit_transaction(tx).unwrap();
+        }
+
+        let overflow = Transaction::new("0xaa".to_string(), "transfer(2)".to_string(), 1, 4);
+        assert_eq!(
+            sequencer.submit_transaction(overflow.clone()).unwrap_err(),
+            SubmitError::MempoolFull { capacity: 4 }
+        );
+        assert_eq!(sequencer.mempool_size(), 4);
+
+        // A rejected submission does not consume the sender's nonce
+        sequencer.decrypt_and_order();
+        assert!(sequencer.submit_transaction(overflow).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_verifiable_random_order_follows_beacon() {
+        let txs: Vec<Transaction> = (0..8)
+            .map(|i| Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, 0))
+            .collect();
+        let ids = |txs: &[Transaction]| txs.iter().map(|tx| tx.tx_id.clone()).collect::<Vec<_>>();
+
+        let (mut a, mut b, mut c) = (txs.clone(), txs.clone(), txs.clone());
+        beacon_order(&mut a, "beacon-1");
+        beacon_order(&mut b, "beacon-1");
+        beacon_order(&mut c, "beacon-2");
+        assert_eq!(ids(&a), ids(&b));
+        assert_ne!(ids(&a), ids(&c));
+
+        // The batch commits the beacon it was shuffled with, then advances it
+        let mut sequencer = TeeSequencer::new();
+        let mut apqc = AdaptivePqcLayer::new();
+        sequencer.ordering_mode = OrderingMode::VerifiableRandom;
+        sequencer.batch_size = 8;
+        for tx in txs {
+            sequencer.submit_transaction(tx).unwrap();
+        }
+        let initial = sequencer.beacon().to_string();
+        sequencer.decrypt_and_order();
+        let batch = sequencer.create_batch(&mut apqc).await.unwrap().unwrap();
+
+        assert_eq!(batch.beacon.as_deref(), Some(initial.as_str()));
+        let mut replayed = batch.transactions.clone();
+        beacon_order(&mut replayed, &initial);
+        assert_eq!(ids(&replayed), ids(&batch.transactions));
+        assert_eq!(sequencer.beacon(), next_beacon(&batch.ml_dsa_sig));
+    }
+
+    #[tokio::test]
+    async fn test_batch_auction_clears_at_lowest_winning_fee() {
+        let mut sequencer = TeeSequencer::new();
+        let mut apqc = AdaptivePqcLayer::new();
+        sequencer.ordering_mode = OrderingMode::BatchAuction;
+        sequencer.batch_size = 3;
+
+        for (i, fee) in [5, 40, 12, 70, 12].into_iter().enumerate() {
+            let tx = Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), fee, 0);
+            sequencer.submit_transaction(tx).unwrap();
+        }
+
+        let ordered = sequencer.decrypt_and_order();
+        let fees: Vec<u64> = ordered.iter().map(|tx| tx.priority_fee).collect();
+        assert_eq!(fees, vec![70, 40, 12]);
+        // Equal bids are broken by arrival time
+        assert_eq!(ordered[2].sender, "0x02");
+        assert_eq!(sequencer.mempool_size(), 2);
+
+        let batch = sequencer.create_batch(&mut apqc).await.unwrap().expect("batch");
+        assert_eq!(batch.clearing_price, Some(12));
+        assert_eq!(batch.transactions.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_signing_failure_surfaces_error_and_keeps_queue() {
+        let mut sequencer = TeeSequencer::new();
+        let mut apqc = AdaptivePqcLayer::new();
+        for i in 0..3 {
+            let tx = Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, 0);
+            sequencer.submit_transaction(tx).unwrap();
+        }
+        sequencer.decrypt_and_order();
+
+        apqc.active_signatures.retain(|alg| *alg != crate::apqc::SignatureAlgorithm::SlhDsa256s);
+        assert_eq!(
+            sequencer.create_batch(&mut apqc).await.unwrap_err(),
+            CryptoError::AlgorithmInactive("SLH-DSA-256s")
+        );
+        assert_eq!(sequencer.ordered_queue_size(), 3);
+        assert_eq!(sequencer.batch_count(), 0);
+
+        apqc.active_signatures.push(crate::apqc::SignatureAlgorithm::SlhDsa256s);
+        let batch = sequencer.create_batch(&mut apqc).await.unwrap().expect("batch");
+        assert_eq!(batch.transactions.len(), 3);
+        assert!(batch.transactions.iter().all(|tx| tx.status == TxStatus::Committed));
+    }
+
+    /// Drive a sequencer through submit → order → batch using only the trait
+    async fn exercise<S: Sequencer + ?Sized>(
+        sequencer: &mut S,
+        make_tx: impl Fn(usize) -> S::Tx,
+        tee_key: &[u8],
+    ) {
+        let mut apqc = AdaptivePqcLayer::new();
+        for i in 0..2 {
+            sequencer.submit(make_tx(i)).unwrap();
+        }
+        assert_eq!(sequencer.order(tee_key).len(), 2);
+        assert!(sequencer.order(tee_key).is_empty());
+
+        for i in 2..4 {
+            sequencer.submit(make_tx(i)).unwrap();
+        }
+        assert!(sequencer.create_batch(&mut apqc, tee_key).await.unwrap().is_some());
+        assert_eq!(sequencer.recent_batches(10).len(), 1);
+        assert!(sequencer.create_batch(&mut apqc, tee_key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sequencer_trait_uniform_across_implementations() {
+        use crate::aegis_tee::{
+            AegisTeeSequencer, DecryptedTransaction, EncryptedTransaction, QuantumResistantBatch as AegisBatch,
+        };
+        use crate::crypto::{encrypt_transaction, ThresholdCipher};
+        use crate::phala_tee::{PhalaTeeSequencer, QuantumResistantBatch as PhalaBatch};
+
+        let encrypted = |tx_id: String, data: Vec<u8>| EncryptedTransaction {
+            tx_id,
+            encrypted_data: data,
+            asset_refs: vec![],
+            priority_fee: 1,
+            timestamp: Utc::now(),
+            risk_level: 10,
+            requires_migration: false,
+        };
+
+        let plain: &mut dyn Sequencer<Tx = Transaction, Ordered = Transaction, Batch = Batch> =
+            &mut TeeSequencer::new();
+        exercise(
+            plain,
+            |i| Transaction::new(format!("0x{:02x}", i), "transfer(1)".to_string(), 1, 0),
+            &[],
+        )
+        .await;
+
+        let cipher = ThresholdCipher::generate();
+        let public_key = cipher.public_key_bytes();
+        let aegis: &mut dyn Sequencer<Tx = EncryptedTransaction, Ordered = DecryptedTransaction, Batch = AegisBatch> =
+            &mut AegisTeeSequencer::default();
+        exercise(
+            aegis,
+            |i| encrypted(format!("tx_{}", i), encrypt_transaction(b"transfer(1)", &public_key).unwrap()),
+            &cipher.secret_key_bytes(),
+        )
+        .await;
+
+        let phala: &mut dyn Sequencer<Tx = EncryptedTransaction, Ordered = DecryptedTransaction, Batch = PhalaBatch> =
+            &mut PhalaTeeSequencer::default();
+        exercise(
+            phala,
+            |i| encrypted(format!("tx_{}", i), b"transfer(1)".to_vec()),
+            &[],
+        )
+        .await;
+
+        // A full mempool is refused through the trait, not silently accepted
+        let mut bounded = PhalaTeeSequencer::default();
+        bounded.set_max_mempool(1);
+        let phala: &mut dyn Sequencer<Tx = EncryptedTransaction, Ordered = DecryptedTransaction, Batch = PhalaBatch> =
+            &mut bounded;
+        phala.submit(encrypted("tx_a".to_string(), b"transfer(1)".to_vec())).unwrap();
+        assert_eq!(
+            phala.submit(encrypted("tx_b".to_string(), b"transfer(1)".to_vec())),
+            Err(SubmitError::MempoolFull { capacity: 1 })
+        );
+    }
+}
@@ -1,15 +1,32 @@
 //! Application State
 //! Shared state and simulation loop
 
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, broadcast};
+use tracing::Instrument;
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::qrm::{QuantumResistanceMonitor, RiskRecommendation, ThreatIndicator, RiskAssessment, ThreatCategory, QuantumEra};
-use crate::apqc::AdaptivePqcLayer;
+use crate::qrm::{
+    QuantumResistanceMonitor, RiskRecommendation, ThreatIndicator, RiskAssessment, ThreatCategory, QuantumEra,
+    ThreatFeed, SimulatedFeed,
+};
+use crate::apqc::{AdaptivePqcLayer, CombinerMode, SignatureAlgorithm};
 use crate::sequencer::{TeeSequencer, Transaction, Batch};
 use crate::chain::{ChainState, Block};
+use crate::aegis_tee::{AegisTeeSequencer, IntelligenceOrdering};
+use crate::qvm::{QvmProtocolStack, QvmConfig, GroverThreatAssessment, ShorThreatAssessment};
+use crate::hndl::HndlEstimator;
+use crate::binary_event::{
+    AlgorithmSetFrame, AttestationFrame, BatchFrame, BinaryEvent, BlockFrame, GroverThreatFrame, ShorThreatFrame,
+    TransactionFrame,
+};
+use crate::event_signature::append_event_signature;
+use crate::rate_limit::{RateLimiter, DEFAULT_INJECTIONS_PER_MINUTE};
 
 /// Events broadcast to WebSocket clients
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,36 +61,420 @@ pub enum Event {
     SimulationStarted,
     #[serde(rename = "simulation_stopped")]
     SimulationStopped,
+    #[serde(rename = "qvm_assessment")]
+    QvmAssessment {
+        grover_threats: Vec<GroverThreatAssessment>,
+        shor_threats: Vec<ShorThreatAssessment>,
+        composite_risk: u32,
+    },
+}
+
+impl Event {
+    /// Wire name of the event, as sent in the `type` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::QrmUpdate { .. } => "qrm_update",
+            Self::TxSubmitted(_) => "tx_submitted",
+            Self::TxsOrdered { .. } => "txs_ordered",
+            Self::BatchCreated { .. } => "batch_created",
+            Self::RotationScheduled { .. } => "rotation_scheduled",
+            Self::RotationExecuted { .. } => "rotation_executed",
+            Self::SimulationStarted => "simulation_started",
+            Self::SimulationStopped => "simulation_stopped",
+            Self::QvmAssessment { .. } => "qvm_assessment",
+        }
+    }
+
+    /// Bincode frame for binary clients; None for events that always go as text
+    pub fn to_binary(&self) -> Option<Vec<u8>> {
+        let binary = match self {
+            Self::BatchCreated { batch, block } => BinaryEvent::BatchCreated {
+                batch: batch_frame(batch),
+                block: block_frame(block),
+            },
+            Self::QvmAssessment { grover_threats, shor_threats, composite_risk } => BinaryEvent::QvmAssessment {
+                grover_threats: grover_threats.iter().map(grover_frame).collect(),
+                shor_threats: shor_threats.iter().map(shor_frame).collect(),
+                composite_risk: *composite_risk,
+            },
+            _ => return None,
+        };
+        binary.to_bincode().ok()
+    }
+}
+
+/// Name a unit enum variant serializes to, as JSON clients see it
+fn serde_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn batch_frame(batch: &Batch) -> BatchFrame {
+    BatchFrame {
+        batch_id: batch.batch_id.clone(),
+        transactions: batch.transactions.iter().map(|tx| TransactionFrame {
+            tx_id: tx.tx_id.clone(),
+            sender: tx.sender.clone(),
+            data: tx.data.clone(),
+            timestamp: tx.timestamp,
+            priority_fee: tx.priority_fee,
+            nonce: tx.nonce,
+            risk_level: tx.risk_level,
+            status: serde_name(&tx.status),
+        }).collect(),
+        ml_dsa_sig: batch.ml_dsa_sig.clone(),
+        slh_dsa_sig: batch.slh_dsa_sig.clone(),
+        attestation: AttestationFrame {
+            platform: batch.attestation.platform.clone(),
+            mrenclave: batch.attestation.mrenclave.clone(),
+            mrsigner: batch.attestation.mrsigner.clone(),
+            report_data: batch.attestation.report_data.clone(),
+            nonce: batch.attestation.nonce.clone(),
+            timestamp: batch.attestation.timestamp,
+            pqc_signed: batch.attestation.pqc_signed,
+        },
+        timestamp: batch.timestamp,
+        clearing_price: batch.clearing_price,
+        signature_algorithms: batch.signature_algorithms.clone(),
+        beacon: batch.beacon.clone(),
+    }
+}
+
+fn block_frame(block: &Block) -> BlockFrame {
+    BlockFrame {
+        height: block.height,
+        batch_id: block.batch_id.clone(),
+        tx_count: block.tx_count,
+        timestamp: block.timestamp,
+        attestation_valid: block.attestation_valid,
+        risk_score: block.risk_score,
+        algorithms: AlgorithmSetFrame {
+            signatures: block.algorithms.signatures.clone(),
+            kems: block.algorithms.kems.clone(),
+        },
+        downgraded: block.downgraded,
+    }
+}
+
+fn grover_frame(threat: &GroverThreatAssessment) -> GroverThreatFrame {
+    GroverThreatFrame {
+        target_algorithm: threat.target_algorithm.clone(),
+        classical_bits: threat.classical_bits,
+        quantum_speedup: threat.quantum_speedup,
+        estimated_iterations: threat.estimated_iterations,
+        required_logical_qubits: threat.required_logical_qubits,
+        required_physical_qubits: threat.required_physical_qubits,
+        estimated_time_years: threat.estimated_time_years,
+        threat_level: serde_name(&threat.threat_level),
+        noise_adjusted: threat.noise_adjusted,
+    }
+}
+
+fn shor_frame(threat: &ShorThreatAssessment) -> ShorThreatFrame {
+    ShorThreatFrame {
+        target_algorithm: threat.target_algorithm.clone(),
+        key_bits: threat.key_bits,
+        required_logical_qubits: threat.required_logical_qubits,
+        required_t_gates: threat.required_t_gates,
+        required_physical_qubits: threat.required_physical_qubits,
+        error_correction_overhead: threat.error_correction_overhead,
+        estimated_time_hours: threat.estimated_time_hours,
+        threat_level: serde_name(&threat.threat_level),
+        note: threat.note.clone(),
+    }
+}
+
+/// An event encoded once and shared by every WebSocket client
+#[derive(Debug)]
+pub struct EventFrame {
+    pub event: Event,
+    /// JSON text, with an ML-DSA signature appended when events are signed
+    pub text: String,
+    /// Bincode frame for binary clients; never built while events are signed
+    pub binary: Option<Vec<u8>>,
+}
+
+impl EventFrame {
+    /// Serialize `event`, signing it when the state signs events
+    pub async fn encode(state: &AppState, event: Event) -> Option<Self> {
+        let mut text = serde_json::to_string(&event).ok()?;
+        if state.sign_events {
+            match state.apqc.lock().await.sign_mldsa(text.as_bytes()).await {
+                Ok(signature) => text = append_event_signature(&text, &signature),
+                Err(e) => tracing::warn!("Failed to sign event: {}", e),
+            }
+        }
+        let binary = if state.sign_events { None } else { event.to_binary() };
+        Some(Self { event, text, binary })
+    }
+}
+
+/// Encode each broadcast event once and republish it to WebSocket clients
+/// Subscribes before returning, so no event broadcast afterwards is missed.
+pub fn spawn_event_encoder(state: Arc<AppState>) -> tokio::task::JoinHandle<()> {
+    let mut rx = state.subscribe();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if let Some(frame) = EventFrame::encode(&state, event).await {
+                        let _ = state.frame_tx.send(Arc::new(frame));
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Most transactions the simulation may generate in one tick
+pub const MAX_SIMULATED_TXS_PER_TICK: usize = 1_000;
+
+/// Pace of the background simulation, re-read every tick
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SimulationConfig {
+    pub tick_ms: u64,
+    pub min_txs: usize,
+    pub max_txs: usize,
+    /// Chance per tick that the threat feed emits an indicator
+    pub threat_probability: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: 2000,
+            min_txs: 1,
+            max_txs: 3,
+            threat_probability: 1.0,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Reject configurations the loop cannot run
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tick_ms == 0 {
+            return Err("tick_ms must be at least 1".to_string());
+        }
+        if self.min_txs > self.max_txs {
+            return Err(format!("min_txs ({}) exceeds max_txs ({})", self.min_txs, self.max_txs));
+        }
+        if self.max_txs > MAX_SIMULATED_TXS_PER_TICK {
+            return Err(format!(
+                "max_txs ({}) exceeds the limit of {}", self.max_txs, MAX_SIMULATED_TXS_PER_TICK,
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.threat_probability) {
+            return Err(format!("threat_probability must be within [0, 1], got {}", self.threat_probability));
+        }
+        Ok(())
+    }
+}
+
+/// Stages of one simulation tick, each run in its own tracing span
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationPhase {
+    ThreatFeed,
+    TxGeneration,
+    Ordering,
+    BatchCreation,
+    Rotation,
+}
+
+impl SimulationPhase {
+    pub const ALL: [SimulationPhase; 5] = [
+        Self::ThreatFeed,
+        Self::TxGeneration,
+        Self::Ordering,
+        Self::BatchCreation,
+        Self::Rotation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ThreatFeed => "threat_feed",
+            Self::TxGeneration => "tx_generation",
+            Self::Ordering => "ordering",
+            Self::BatchCreation => "batch_creation",
+            Self::Rotation => "rotation",
+        }
+    }
+}
+
+/// Upper bounds (ms) of the phase duration buckets; one overflow bucket follows
+pub const PHASE_BUCKETS_MS: [f64; 6] = [1.0, 5.0, 25.0, 100.0, 500.0, 2500.0];
+
+/// Duration histogram for one simulation phase
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseStats {
+    pub phase: SimulationPhase,
+    pub count: u64,
+    pub total_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+    /// Samples per `PHASE_BUCKETS_MS` bound, then the overflow bucket
+    pub buckets: Vec<u64>,
+}
+
+impl PhaseStats {
+    fn new(phase: SimulationPhase) -> Self {
+        Self {
+            phase,
+            count: 0,
+            total_ms: 0.0,
+            max_ms: 0.0,
+            last_ms: 0.0,
+            buckets: vec![0; PHASE_BUCKETS_MS.len() + 1],
+        }
+    }
+
+    fn record(&mut self, elapsed_ms: f64) {
+        let bucket = PHASE_BUCKETS_MS.iter()
+            .position(|&bound| elapsed_ms <= bound)
+            .unwrap_or(PHASE_BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total_ms += elapsed_ms;
+        self.max_ms = self.max_ms.max(elapsed_ms);
+        self.last_ms = elapsed_ms;
+    }
+}
+
+/// In-memory phase duration histograms for the simulation loop
+#[derive(Debug, Clone)]
+pub struct PhaseTimings {
+    phases: Vec<PhaseStats>,
+}
+
+impl PhaseTimings {
+    pub fn new() -> Self {
+        Self { phases: SimulationPhase::ALL.iter().map(|&p| PhaseStats::new(p)).collect() }
+    }
+
+    pub fn record(&mut self, phase: SimulationPhase, elapsed: Duration) {
+        if let Some(stats) = self.phases.iter_mut().find(|s| s.phase == phase) {
+            stats.record(elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<PhaseStats> {
+        self.phases.clone()
+    }
+}
+
+impl Default for PhaseTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run one simulation phase in a tracing span and record its duration
+pub async fn timed_phase<T>(state: &AppState, phase: SimulationPhase, work: impl Future<Output = T>) -> T {
+    let span = tracing::info_span!("simulation_phase", phase = phase.name());
+    let started = Instant::now();
+    let output = work.instrument(span.clone()).await;
+    let elapsed = started.elapsed();
+
+    span.in_scope(|| tracing::debug!(elapsed_ms = elapsed.as_secs_f64() * 1000.0, "phase complete"));
+    state.phase_timings.lock().await.record(phase, elapsed);
+    output
+}
+
+/// Startup milestones gating the readiness probe
+/// APQC key pairs are generated by `AppState::new`, so they always exist here.
+#[derive(Debug, Default)]
+pub struct Readiness {
+    /// The background simulation task has started polling
+    pub simulation_started: AtomicBool,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.simulation_started.load(Ordering::Acquire)
+    }
 }
 
 /// Shared application state
 pub struct AppState {
     pub qrm: Mutex<QuantumResistanceMonitor>,
+    /// Where the simulation loop pulls threat indicators from
+    pub threat_feed: Mutex<Box<dyn ThreatFeed>>,
     pub apqc: Mutex<AdaptivePqcLayer>,
     pub sequencer: Mutex<TeeSequencer>,
+    pub aegis_tee: Mutex<AegisTeeSequencer>,
     pub chain: Mutex<ChainState>,
+    pub qvm: Mutex<QvmProtocolStack>,
+    pub hndl: Mutex<HndlEstimator>,
     pub simulation_running: Mutex<bool>,
+    pub simulation_config: Mutex<SimulationConfig>,
+    /// Duration histograms of the simulation loop's phases
+    pub phase_timings: Mutex<PhaseTimings>,
+    /// Per-client limit on manual threat injection
+    pub inject_limiter: Mutex<RateLimiter>,
+    /// Randomness for the simulation loop; fixed by `seed_simulation`
+    pub sim_rng: Mutex<StdRng>,
+    pub readiness: Readiness,
+    /// Where QRM state is flushed by the simulation loop, if persistence is enabled
+    pub state_file: Option<PathBuf>,
+    /// Append an ML-DSA signature to every WebSocket event
+    pub sign_events: bool,
     pub event_tx: broadcast::Sender<Event>,
+    /// Events as sent to WebSocket clients, fed by `spawn_event_encoder`
+    pub frame_tx: broadcast::Sender<Arc<EventFrame>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
         let (event_tx, _) = broadcast::channel(1000);
+        let (frame_tx, _) = broadcast::channel(1000);
         
         Self {
             qrm: Mutex::new(QuantumResistanceMonitor::new()),
+            threat_feed: Mutex::new(Box::new(SimulatedFeed::default())),
             apqc: Mutex::new(AdaptivePqcLayer::new()),
             sequencer: Mutex::new(TeeSequencer::new()),
+            aegis_tee: Mutex::new(AegisTeeSequencer::default()),
             chain: Mutex::new(ChainState::new()),
+            qvm: Mutex::new(QvmProtocolStack::new(QvmConfig::default())),
+            hndl: Mutex::new(HndlEstimator::with_default_assets()),
             simulation_running: Mutex::new(false),
+            simulation_config: Mutex::new(SimulationConfig::default()),
+            phase_timings: Mutex::new(PhaseTimings::new()),
+            inject_limiter: Mutex::new(RateLimiter::per_minute(DEFAULT_INJECTIONS_PER_MINUTE)),
+            sim_rng: Mutex::new(StdRng::from_entropy()),
+            readiness: Readiness::default(),
+            state_file: None,
+            sign_events: false,
             event_tx,
+            frame_tx,
         }
     }
 
+    /// Make the simulation reproducible: the loop, the simulated threat feed
+    /// and the QVM simulator all draw from generators derived from `seed`, and
+    /// batches are stamped by a logical clock starting at the Unix epoch
+    pub fn seed_simulation(&mut self, seed: u64) {
+        let rng = self.sim_rng.get_mut();
+        *rng = StdRng::seed_from_u64(seed);
+        let (feed_seed, qvm_seed) = (rng.gen(), rng.gen());
+        *self.threat_feed.get_mut() = Box::new(SimulatedFeed::seeded(feed_seed));
+        self.qvm.get_mut().oracle.simulator_mut().set_seed(Some(qvm_seed));
+        self.sequencer.get_mut().clock = Some(chrono::DateTime::<chrono::Utc>::UNIX_EPOCH);
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<Event> {
         self.event_tx.subscribe()
     }
 
+    pub fn subscribe_frames(&self) -> broadcast::Receiver<Arc<EventFrame>> {
+        self.frame_tx.subscribe()
+    }
+
     pub fn broadcast(&self, event: Event) {
         let _ = self.event_tx.send(event);
     }
@@ -86,6 +487,7 @@ pub struct StatusResponse {
     pub apqc: ApqcStatus,
     pub sequencer: SequencerStatus,
     pub chain: ChainStatus,
+    pub phases: Vec<PhaseStats>,
 }
 
 #[derive(Debug, Serialize)]
@@ -108,15 +510,18 @@ pub struct ApqcStatus {
     pub kems: Vec<String>,
     pub rotation_pending: bool,
     pub rotation_block: Option<u64>,
+    pub combiner_mode: CombinerMode,
 }
 
 #[derive(Debug, Serialize)]
 pub struct SequencerStatus {
     pub mempool_size: usize,
+    pub mempool_capacity: usize,
     pub ordered_queue: usize,
     pub batch_count: usize,
     pub tee_platform: String,
     pub mrenclave: String,
+    pub intelligence_mode: IntelligenceOrdering,
 }
 
 #[derive(Debug, Serialize)]
@@ -126,9 +531,107 @@ pub struct ChainStatus {
     pub risk_score: u32,
 }
 
+/// Build a status snapshot
+/// Acquires locks one at a time and releases before the next to avoid deadlocks
+pub async fn build_status(state: &AppState) -> StatusResponse {
+    let (risk, indicator_count, threshold_scheduled, threshold_emergency) = {
+        let qrm = state.qrm.lock().await;
+        let risk = qrm.current_risk();
+        (risk, qrm.indicator_count(), qrm.threshold_scheduled, qrm.threshold_emergency)
+    };
+
+    let apqc_status = {
+        let apqc = state.apqc.lock().await;
+        ApqcStatus {
+            signatures: apqc.active_signatures.iter().map(|s| s.name().to_string()).collect(),
+            kems: apqc.active_kems.iter().map(|k| k.name().to_string()).collect(),
+            rotation_pending: apqc.rotation_pending,
+            rotation_block: apqc.rotation_block,
+            combiner_mode: apqc.combiner_mode,
+        }
+    };
+
+    let intelligence_mode = state.aegis_tee.lock().await.intelligence_mode();
+
+    let sequencer_status = {
+        let sequencer = state.sequencer.lock().await;
+        SequencerStatus {
+            mempool_size: sequencer.mempool_size(),
+            mempool_capacity: sequencer.max_mempool,
+            ordered_queue: sequencer.ordered_queue_size(),
+            batch_count: sequencer.batch_count(),
+            tee_platform: sequencer.tee_platform.clone(),
+            mrenclave: sequencer.mrenclave.clone(),
+            intelligence_mode,
+        }
+    };
+
+    let chain_status = {
+        let chain = state.chain.lock().await;
+        ChainStatus {
+            height: chain.current_height,
+            algorithm_set: chain.algorithm_set.clone(),
+            risk_score: chain.risk_score,
+        }
+    };
+
+    let phases = state.phase_timings.lock().await.snapshot();
+
+    StatusResponse {
+        qrm: QrmStatus {
+            risk_score: risk.score,
+            recommendation: risk.recommendation,
+            indicator_count,
+            thresholds: Thresholds {
+                scheduled: threshold_scheduled,
+                emergency: threshold_emergency,
+            },
+        },
+        apqc: apqc_status,
+        sequencer: sequencer_status,
+        chain: chain_status,
+        phases,
+    }
+}
+
+/// Pull the next indicator from the threat feed into the monitor
+pub async fn poll_threat_feed(state: &AppState) -> Option<ThreatIndicator> {
+    let mut feed = state.threat_feed.lock().await;
+    let mut qrm = state.qrm.lock().await;
+    let indicator = feed.next_indicator(&qrm).await?;
+    qrm.add_indicator(indicator.clone());
+    Some(indicator)
+}
+
+/// Persist QRM state to `state_file`, if persistence is enabled
+pub async fn flush_qrm_state(state: &AppState) {
+    if let Some(path) = &state.state_file {
+        let qrm = state.qrm.lock().await;
+        if let Err(e) = qrm.save_to_file(path) {
+            tracing::warn!("Failed to save QRM state to {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Wait for `signal`, then stop the simulation, flush QRM state and tell
+/// WebSocket clients the session is over. Pass to `with_graceful_shutdown`.
+pub async fn shutdown_on(state: Arc<AppState>, signal: impl std::future::Future<Output = ()>) {
+    signal.await;
+    tracing::info!("Shutting down");
+
+    *state.simulation_running.lock().await = false;
+    flush_qrm_state(&state).await;
+    state.broadcast(Event::SimulationStopped);
+}
+
+/// Simulation iterations between QRM state flushes (~30s at the 2s tick)
+const STATE_FLUSH_INTERVAL: u64 = 15;
+
 /// Run the simulation loop
 pub async fn run_simulation(state: Arc<AppState>) {
     let mut _tx_counter: u64 = 0;
+    let mut iteration: u64 = 0;
+    state.readiness.simulation_started.store(true, Ordering::Release);
     
     loop {
         // Check if simulation should run
@@ -140,108 +643,207 @@ pub async fn run_simulation(state: Arc<AppState>) {
             }
         }
 
-        // 1. Simulate QRM threat feed
-        let (indicator, risk) = {
-            let mut qrm = state.qrm.lock().await;
-            let indicator = qrm.simulate_threat_feed();
-            let risk = qrm.calculate_risk();
-            (indicator, risk)
-        };
-
-        state.broadcast(Event::QrmUpdate {
-            indicator,
-            risk: risk.clone(),
-        });
+        let config = *state.simulation_config.lock().await;
+        advance_sim_clock(&state, &config).await;
 
-        // 2. Generate random transactions
-        let tx_count = {
-            let mut rng = rand::thread_rng();
-            rng.gen_range(1..=3)
-        };
-        
-        for _ in 0..tx_count {
-            let (sender, data, fee) = {
-                let mut rng = rand::thread_rng();
-                (
-                    format!("0x{:016x}", rng.gen::<u64>()),
-                    format!("transfer({})", rng.gen_range(1..1000)),
-                    rng.gen_range(1..100),
-                )
+        // 1. Pull from the QRM threat feed
+        let risk = timed_phase(&state, SimulationPhase::ThreatFeed, async {
+            let indicator = roll_threat_feed(&state, &config).await;
+            let risk = {
+                let mut qrm = state.qrm.lock().await;
+                qrm.evict_expired();
+                qrm.calculate_risk()
             };
-            
-            let tx = Transaction::new(sender, data, fee);
-            
-            {
-                let mut sequencer = state.sequencer.lock().await;
-                let submitted = sequencer.submit_transaction(tx);
-                state.broadcast(Event::TxSubmitted(submitted));
+
+            if let Some(indicator) = indicator {
+                state.broadcast(Event::QrmUpdate {
+                    indicator,
+                    risk: risk.clone(),
+                });
             }
-            
-            _tx_counter += 1;
+            risk
+        }).await;
+
+        iteration += 1;
+        if iteration.is_multiple_of(STATE_FLUSH_INTERVAL) {
+            flush_qrm_state(&state).await;
         }
 
+        // 2. Generate random transactions
+        _tx_counter += timed_phase(&state, SimulationPhase::TxGeneration, async {
+            generate_transactions(&state, &config).await.len() as u64
+        }).await;
+
         // 3. Process transactions through sequencer
-        let ordered = {
-            let mut sequencer = state.sequencer.lock().await;
-            sequencer.decrypt_and_order()
-        };
-        
-        if !ordered.is_empty() {
-            state.broadcast(Event::TxsOrdered {
-                count: ordered.len(),
-                txs: ordered,
-            });
-        }
+        timed_phase(&state, SimulationPhase::Ordering, async {
+            let ordered = {
+                let mut sequencer = state.sequencer.lock().await;
+                sequencer.decrypt_and_order()
+            };
+            
+            if !ordered.is_empty() {
+                state.broadcast(Event::TxsOrdered {
+                    count: ordered.len(),
+                    txs: ordered,
+                });
+            }
+        }).await;
 
         // 4. Create batch if enough transactions
-        let should_create_batch = {
-            let sequencer = state.sequencer.lock().await;
-            sequencer.ordered_queue_size() >= sequencer.batch_size
-        };
+        timed_phase(&state, SimulationPhase::BatchCreation, async {
+            let should_create_batch = {
+                let sequencer = state.sequencer.lock().await;
+                sequencer.ordered_queue_size() >= sequencer.batch_size
+            };
+
+            if !should_create_batch {
+                return;
+            }
 
-        if should_create_batch {
             let batch_result = {
                 let mut sequencer = state.sequencer.lock().await;
                 let mut apqc = state.apqc.lock().await;
                 sequencer.create_batch(&mut apqc).await
             };
 
-            if let Some(batch) = batch_result {
-                let block = {
-                    let mut chain = state.chain.lock().await;
-                    chain.commit_batch(&batch, &risk)
-                };
+            let batch = match batch_result {
+                Ok(Some(batch)) => batch,
+                Ok(None) => return,
+                Err(e) => {
+                    tracing::warn!("Failed to sign batch: {}", e);
+                    return;
+                }
+            };
+
+            let (block, downgrade) = {
+                let mut chain = state.chain.lock().await;
+                let downgrade = chain.detect_downgrade(&batch);
+                (chain.commit_batch(&batch, &risk), downgrade)
+            };
 
-                state.broadcast(Event::BatchCreated { batch, block });
+            if let Some(indicator) = downgrade {
+                let risk = {
+                    let mut qrm = state.qrm.lock().await;
+                    qrm.add_indicator(indicator.clone());
+                    qrm.calculate_risk()
+                };
+                state.broadcast(Event::QrmUpdate { indicator, risk });
             }
-        }
+
+            state.broadcast(Event::BatchCreated { batch, block });
+        }).await;
 
         // 5. Check for rotation
-        let current_block = {
-            let chain = state.chain.lock().await;
-            chain.current_height
-        };
+        timed_phase(&state, SimulationPhase::Rotation, async {
+            let current_block = {
+                let chain = state.chain.lock().await;
+                chain.current_height
+            };
 
-        if risk.recommendation == RiskRecommendation::EmergencyRotation {
-            let mut apqc = state.apqc.lock().await;
-            apqc.execute_rotation().await;
-            state.broadcast(Event::RotationExecuted {
-                rotation_type: "emergency".to_string(),
-            });
-        } else if risk.recommendation == RiskRecommendation::ScheduleRotation {
-            let mut apqc = state.apqc.lock().await;
-            if !apqc.rotation_pending {
-                let effective_block = current_block + 10;
-                apqc.schedule_rotation(effective_block);
-                state.broadcast(Event::RotationScheduled { effective_block });
+            let event = {
+                let mut apqc = state.apqc.lock().await;
+                let recommendation = apqc.compromise_recommendation(risk.recommendation);
+                apply_rotation_policy(&mut apqc, recommendation, current_block).await
+            };
+            if let Some(event) = event {
+                state.broadcast(event);
             }
-        }
+        }).await;
 
         // Sleep between iterations
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(config.tick_ms.max(1))).await;
     }
 }
 
+/// Move a seeded run's logical clock forward by one tick
+pub async fn advance_sim_clock(state: &AppState, config: &SimulationConfig) {
+    if let Some(clock) = state.sequencer.lock().await.clock.as_mut() {
+        *clock += chrono::Duration::milliseconds(config.tick_ms as i64);
+    }
+}
+
+/// Pull from the threat feed with the configured per-tick probability
+pub async fn roll_threat_feed(state: &AppState, config: &SimulationConfig) -> Option<ThreatIndicator> {
+    let emit = state.sim_rng.lock().await.gen_bool(config.threat_probability.clamp(0.0, 1.0));
+    if emit { poll_threat_feed(state).await } else { None }
+}
+
+/// Submit a random batch of transfers, broadcasting and returning the accepted ones
+pub async fn generate_transactions(state: &AppState, config: &SimulationConfig) -> Vec<Transaction> {
+    let tx_count = state.sim_rng.lock().await
+        .gen_range(config.min_txs..=config.max_txs.max(config.min_txs));
+
+    let mut submitted = Vec::with_capacity(tx_count);
+    for _ in 0..tx_count {
+        let (tx_id, sender, data, fee, risk_level) = {
+            let mut rng = state.sim_rng.lock().await;
+            (
+                uuid::Builder::from_random_bytes(rng.gen()).into_uuid(),
+                format!("0x{:016x}", rng.gen::<u64>()),
+                format!("transfer({})", rng.gen_range(1..1000)),
+                rng.gen_range(1..100),
+                rng.gen_range(0..100),
+            )
+        };
+
+        let mut tx = Transaction::new(sender, data, fee, 0);
+        tx.tx_id = format!("tx_{}", tx_id.simple());
+        tx.risk_level = risk_level;
+
+        let mut sequencer = state.sequencer.lock().await;
+        tx.timestamp = sequencer.now();
+        if let Ok(tx) = sequencer.submit_transaction(tx) {
+            state.broadcast(Event::TxSubmitted(tx.clone()));
+            submitted.push(tx);
+        }
+    }
+    submitted
+}
+
+/// Rotate or schedule a rotation as the risk recommendation demands
+/// Nothing is started while the APQC rotation cooldown is running.
+pub async fn apply_rotation_policy(
+    apqc: &mut AdaptivePqcLayer,
+    recommendation: RiskRecommendation,
+    current_block: u64,
+) -> Option<Event> {
+    if !apqc.rotation_allowed(current_block) {
+        return None;
+    }
+
+    match recommendation {
+        RiskRecommendation::EmergencyRotation => {
+            apqc.execute_rotation_at(current_block).await;
+            Some(Event::RotationExecuted { rotation_type: "emergency".to_string() })
+        }
+        RiskRecommendation::ScheduleRotation if !apqc.rotation_pending => {
+            let effective_block = current_block + 10;
+            apqc.schedule_rotation(effective_block);
+            Some(Event::RotationScheduled { effective_block })
+        }
+        _ => None,
+    }
+}
+
+/// Mark a signature algorithm compromised and feed the disclosure to the QRM
+/// The returned assessment carries the compromise-adjusted recommendation.
+pub async fn disclose_compromise(
+    state: &AppState,
+    algorithm: SignatureAlgorithm,
+    description: &str,
+) -> RiskAssessment {
+    let (indicator, mut risk) = {
+        let indicator = state.apqc.lock().await.mark_compromised(algorithm, description);
+        let mut qrm = state.qrm.lock().await;
+        qrm.add_indicator(indicator.clone());
+        (indicator, qrm.calculate_risk())
+    };
+    risk.recommendation = state.apqc.lock().await.compromise_recommendation(risk.recommendation);
+
+    state.broadcast(Event::QrmUpdate { indicator, risk: risk.clone() });
+    risk
+}
+
 /// Inject high severity threats across multiple categories
 pub async fn inject_high_threats(state: &AppState) {
     let mut qrm = state.qrm.lock().await;
@@ -266,6 +868,8 @@ pub async fn inject_high_threats(state: &AppState) {
             description: desc.to_string(),
             era_relevance: QuantumEra::Nisq,  // Imminent threat
             references: vec!["EMERGENCY-2026-001".to_string()],
+            tags: vec![],
+            expires_at: None,
         };
         qrm.add_indicator(indicator);
     }
@@ -277,3 +881,272 @@ pub async fn inject_high_threats(state: &AppState) {
         state.broadcast(Event::QrmUpdate { indicator, risk });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_status_reflects_intelligence_mode() {
+        let state = AppState::new();
+        assert_eq!(build_status(&state).await.sequencer.intelligence_mode, IntelligenceOrdering::Hybrid);
+
+        state.aegis_tee.lock().await.set_intelligence_mode(IntelligenceOrdering::RiskAware);
+        let status = build_status(&state).await;
+        assert_eq!(status.sequencer.intelligence_mode, IntelligenceOrdering::RiskAware);
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["sequencer"]["intelligence_mode"], "risk_aware");
+    }
+
+    #[tokio::test]
+    async fn test_event_signed_once_for_all_clients() {
+        let mut state = AppState::new();
+        state.sign_events = true;
+        let state = Arc::new(state);
+        let (mut first, mut second) = (state.subscribe_frames(), state.subscribe_frames());
+        spawn_event_encoder(state.clone());
+
+        state.broadcast(Event::RotationScheduled { effective_block: 7 });
+        let (a, b) = (first.recv().await.unwrap(), second.recv().await.unwrap());
+        assert!(Arc::ptr_eq(&a, &b));
+
+        let (mldsa_pk, _, _) = state.apqc.lock().await.get_public_keys().await;
+        let public_key = crate::crypto::MldsaKeyPair::from_public_key(&mldsa_pk).unwrap().public_key;
+        assert!(crate::event_signature::verify_event_signature(&a.text, &public_key));
+    }
+
+    #[tokio::test]
+    async fn test_rotation_cooldown_suppresses_repeat_crossings() {
+        let mut apqc = AdaptivePqcLayer::new();
+        apqc.rotation_cooldown_blocks = 20;
+
+        let first = apply_rotation_policy(&mut apqc, RiskRecommendation::EmergencyRotation, 100).await;
+        let second = apply_rotation_policy(&mut apqc, RiskRecommendation::EmergencyRotation, 105).await;
+        assert!(matches!(first, Some(Event::RotationExecuted { .. })));
+        assert!(second.is_none());
+        assert_eq!(apqc.rotation_count, 1);
+        assert_eq!(apqc.last_rotation_block, Some(100));
+
+        // Scheduling is refused during the cooldown too
+        assert!(apply_rotation_policy(&mut apqc, RiskRecommendation::ScheduleRotation, 119).await.is_none());
+        assert!(!apqc.rotation_pending);
+
+        let after = apply_rotation_policy(&mut apqc, RiskRecommendation::EmergencyRotation, 120).await;
+        assert!(after.is_some());
+        assert_eq!(apqc.rotation_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_compromise_disclosure_raises_signature_risk() {
+        let state = AppState::new();
+        let signature_score = |risk: &RiskAssessment| risk.category_breakdown.iter()
+            .find(|c| c.category == ThreatCategory::DigitalSignatures)
+            .map_or(0, |c| c.score);
+        let before = signature_score(&state.qrm.lock().await.calculate_risk());
+
+        let risk = disclose_compromise(&state, SignatureAlgorithm::MlDsa87, "lattice reduction break").await;
+
+        assert!(signature_score(&risk) > before, "{} <= {}", signature_score(&risk), before);
+        assert!(matches!(
+            risk.recommendation,
+            RiskRecommendation::ScheduleRotation | RiskRecommendation::EmergencyRotation
+        ));
+        let mut apqc = state.apqc.lock().await;
+        assert!(apqc.is_compromised(SignatureAlgorithm::MlDsa87));
+        assert!(!apqc.adaptive_combiner.is_trusted(SignatureAlgorithm::MlDsa87));
+        assert!(apply_rotation_policy(&mut apqc, risk.recommendation, 0).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_seeded_runs_generate_identical_sequences() {
+        /// Fees, threat categories, and each batch's id, timestamp and transactions
+        async fn run(seed: u64) -> (Vec<u64>, Vec<ThreatCategory>, Vec<(String, String, String)>) {
+            let mut state = AppState::new();
+            state.seed_simulation(seed);
+            let config = SimulationConfig { threat_probability: 0.5, ..SimulationConfig::default() };
+
+            let (mut fees, mut categories, mut batches) = (Vec::new(), Vec::new(), Vec::new());
+            for _ in 0..10 {
+                advance_sim_clock(&state, &config).await;
+                if let Some(indicator) = roll_threat_feed(&state, &config).await {
+                    categories.push(indicator.category);
+                }
+                fees.extend(generate_transactions(&state, &config).await.iter().map(|tx| tx.priority_fee));
+
+                let mut sequencer = state.sequencer.lock().await;
+                sequencer.decrypt_and_order();
+                let mut apqc = state.apqc.lock().await;
+                if let Some(batch) = sequencer.create_batch(&mut apqc).await.unwrap() {
+                    batches.push((
+                        batch.batch_id,
+                        batch.timestamp.to_rfc3339(),
+                        serde_json::to_string(&batch.transactions).unwrap(),
+                    ));
+                }
+            }
+            (fees, categories, batches)
+        }
+
+        let (fees, categories, batches) = run(42).await;
+        assert!(!fees.is_empty() && !categories.is_empty() && !batches.is_empty());
+        assert_eq!(run(42).await, (fees.clone(), categories, batches));
+        assert_ne!(run(43).await.0, fees);
+    }
+
+    #[tokio::test]
+    async fn test_slow_phase_records_elevated_duration() {
+        let state = AppState::new();
+        timed_phase(&state, SimulationPhase::Ordering, async {}).await;
+        timed_phase(&state, SimulationPhase::BatchCreation, tokio::time::sleep(Duration::from_millis(30))).await;
+
+        let status = build_status(&state).await;
+        let stats = |phase: SimulationPhase| status.phases.iter().find(|s| s.phase == phase).unwrap();
+        let (fast, slow) = (stats(SimulationPhase::Ordering), stats(SimulationPhase::BatchCreation));
+
+        assert_eq!(slow.count, 1);
+        assert!(slow.max_ms >= 30.0, "{:?}", slow);
+        assert!(slow.max_ms > fast.max_ms);
+        // Nothing at or below the 25 ms bound
+        assert_eq!(&slow.buckets[..3], &[0, 0, 0]);
+        assert_eq!(stats(SimulationPhase::Rotation).count, 0);
+    }
+
+    /// Run the simulation for `window` and count the batches it creates
+    async fn batches_created(config: SimulationConfig, window: std::time::Duration) -> usize {
+        let state = Arc::new(AppState::new());
+        *state.simulation_config.lock().await = config;
+        state.sequencer.lock().await.min_block_interval = std::time::Duration::from_millis(20);
+        *state.simulation_running.lock().await = true;
+        let mut events = state.subscribe();
+
+        let sim = tokio::spawn(run_simulation(state.clone()));
+        tokio::time::sleep(window).await;
+        sim.abort();
+
+        let mut batches = 0;
+        while let Ok(event) = events.try_recv() {
+            if matches!(event, Event::BatchCreated { .. }) {
+                batches += 1;
+            }
+        }
+        batches
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fast_simulation_config_creates_more_batches() {
+        let window = std::time::Duration::from_millis(1500);
+        let fast = SimulationConfig { tick_ms: 5, min_txs: 20, max_txs: 40, threat_probability: 0.5 };
+        assert!(fast.validate().is_ok());
+
+        let default_batches = batches_created(SimulationConfig::default(), window).await;
+        let fast_batches = batches_created(fast, window).await;
+
+        assert!(fast_batches > default_batches, "fast {} vs default {}", fast_batches, default_batches);
+    }
+
+    #[test]
+    fn test_simulation_config_caps_max_txs() {
+        let at_cap = SimulationConfig { max_txs: MAX_SIMULATED_TXS_PER_TICK, ..Default::default() };
+        assert!(at_cap.validate().is_ok());
+
+        let over_cap = SimulationConfig { max_txs: MAX_SIMULATED_TXS_PER_TICK + 1, ..Default::default() };
+        assert!(over_cap.validate().unwrap_err().contains("max_txs"));
+    }
+
+    /// Feed that replays a fixed script, then goes quiet
+    struct ScriptedFeed(std::collections::VecDeque<ThreatIndicator>);
+
+    #[async_trait::async_trait]
+    impl ThreatFeed for ScriptedFeed {
+        async fn next_indicator(&mut self, _scoring: &QuantumResistanceMonitor) -> Option<ThreatIndicator> {
+            self.0.pop_front()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weight_override_shapes_simulated_feed_severity() {
+        async fn mean_severity(weights: Option<std::collections::HashMap<ThreatCategory, f64>>) -> f64 {
+            let mut state = AppState::new();
+            state.seed_simulation(7);
+            if let Some(weights) = weights {
+                state.qrm.get_mut().set_weights(weights).unwrap();
+            }
+            let mut total = 0.0;
+            for _ in 0..300 {
+                total += poll_threat_feed(&state).await.unwrap().severity;
+            }
+            total / 300.0
+        }
+
+        // Equal weights give every category the full era multiplier
+        let categories = ThreatCategory::all();
+        let uniform = categories.iter().map(|c| (*c, 1.0 / categories.len() as f64)).collect();
+
+        let default_mean = mean_severity(None).await;
+        let uniform_mean = mean_severity(Some(uniform)).await;
+        assert!(uniform_mean > default_mean + 0.05, "{} vs {}", uniform_mean, default_mean);
+    }
+
+    #[tokio::test]
+    async fn test_scripted_feed_reaches_monitor_in_order() {
+        let state = AppState::new();
+        let script: Vec<ThreatIndicator> = ["first", "second", "third"].iter()
+            .map(|name| ThreatIndicator {
+                category: ThreatCategory::DecryptionHndl,
+                sub_category: name.to_string(),
+                severity: 0.7,
+                confidence: 0.9,
+                source: "Scripted".to_string(),
+                timestamp: chrono::Utc::now(),
+                description: format!("{} scripted indicator", name),
+                era_relevance: QuantumEra::Nisq,
+                references: vec![],
+                tags: vec![],
+                expires_at: None,
+            })
+            .collect();
+        *state.threat_feed.lock().await = Box::new(ScriptedFeed(script.into()));
+
+        let mut pulled = Vec::new();
+        while let Some(indicator) = poll_threat_feed(&state).await {
+            pulled.push(indicator.sub_category);
+        }
+
+        assert_eq!(pulled, ["first", "second", "third"]);
+        let stored: Vec<String> = state.qrm.lock().await.get_indicators().into_iter()
+            .map(|i| i.sub_category)
+            .collect();
+        assert_eq!(stored, ["first", "second", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown_flushes_state() {
+        let path = std::env::temp_dir().join(format!("qrms-shutdown-{}.json", uuid::Uuid::new_v4()));
+        let mut app_state = AppState::new();
+        app_state.state_file = Some(path.clone());
+        let state = Arc::new(app_state);
+        state.qrm.lock().await.simulate_threat_feed(&mut rand::thread_rng());
+        *state.simulation_running.lock().await = true;
+        let mut events = state.subscribe();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let app = axum::Router::new()
+            .route("/api/status", axum::routing::get(crate::handlers::get_status))
+            .with_state(state.clone());
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let shutdown = shutdown_on(state.clone(), async { stop_rx.await.ok(); });
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).with_graceful_shutdown(shutdown).await
+        });
+
+        stop_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+
+        assert!(!*state.simulation_running.lock().await);
+        let restored = QuantumResistanceMonitor::load_from_file(&path).unwrap();
+        assert_eq!(restored.indicator_count(), 1);
+        assert!(matches!(events.recv().await.unwrap(), Event::SimulationStopped));
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,225 @@
+//! Scoring Tuning Hot-Reload
+//! Watches a JSON tuning file (`QRMS_TUNING_FILE`) and applies category weights,
+//! rotation thresholds and the signature combiner mode without a restart.
+//!
+//! Example file:
+//! ```json
+//! {
+//!   "weights": { "digital_signatures": 0.20, "hash_reversal": 0.02, "side_channel": 0.0 },
+//!   "threshold_scheduled": 5000,
+//!   "threshold_emergency": 8500,
+//!   "combiner_mode": "and"
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::apqc::CombinerMode;
+use crate::qrm::{QuantumResistanceMonitor, ThreatCategory};
+use crate::state::AppState;
+
+/// Tunable scoring parameters; omitted fields keep their current values
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TuningProfile {
+    #[serde(default)]
+    pub weights: Option<HashMap<ThreatCategory, f64>>,
+    #[serde(default)]
+    pub threshold_scheduled: Option<u32>,
+    #[serde(default)]
+    pub threshold_emergency: Option<u32>,
+    #[serde(default)]
+    pub combiner_mode: Option<CombinerMode>,
+}
+
+impl TuningProfile {
+    /// Read and parse a tuning file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Check the profile against a monitor without modifying it
+    fn validate(&self, qrm: &QuantumResistanceMonitor) -> Result<(), String> {
+        let scheduled = self.threshold_scheduled.unwrap_or(qrm.threshold_scheduled);
+        let emergency = self.threshold_emergency.unwrap_or(qrm.threshold_emergency);
+        if emergency > 10000 {
+            return Err(format!("Emergency threshold {} exceeds 10000", emergency));
+        }
+        if scheduled >= emergency {
+            return Err(format!(
+                "Scheduled threshold {} must be below emergency threshold {}",
+                scheduled, emergency
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Validate and apply a profile; on error nothing is changed
+pub async fn apply_profile(state: &AppState, profile: &TuningProfile) -> Result<(), String> {
+    // Hold both locks so scoring never observes a partially applied profile
+    let mut qrm = state.qrm.lock().await;
+    let mut apqc = state.apqc.lock().await;
+
+    profile.validate(&qrm)?;
+    if let Some(weights) = &profile.weights {
+        qrm.set_weights(weights.clone())?;
+    }
+    if let Some(scheduled) = profile.threshold_scheduled {
+        qrm.threshold_scheduled = scheduled;
+    }
+    if let Some(emergency) = profile.threshold_emergency {
+        qrm.threshold_emergency = emergency;
+    }
+    if let Some(mode) = profile.combiner_mode {
+        apqc.combiner_mode = mode;
+    }
+
+    tracing::info!(
+        "Applied tuning profile: weights={:?} scheduled={} emergency={} combiner={:?}",
+        ThreatCategory::all().iter().map(|c| (*c, qrm.weight(*c))).collect::<Vec<_>>(),
+        qrm.threshold_scheduled,
+        qrm.threshold_emergency,
+        apqc.combiner_mode,
+    );
+
+    Ok(())
+}
+
+/// Load the tuning file and apply it, keeping current values on any error
+pub async fn reload(state: &AppState, path: &Path) {
+    let result = match TuningProfile::load(path) {
+        Ok(profile) => apply_profile(state, &profile).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        tracing::warn!("Tuning reload rejected, keeping previous values: {}", e);
+    }
+}
+
+/// Apply the tuning file now and re-apply it whenever it changes
+///
+/// The returned watcher must be kept alive for reloads to continue.
+pub async fn watch(state: Arc<AppState>, path: PathBuf) -> notify::Result<RecommendedWatcher> {
+    reload(&state, &path).await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let file_name = path.file_name().map(|n| n.to_os_string());
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            let relevant = (event.kind.is_modify() || event.kind.is_create())
+                && event.paths.iter().any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+            if relevant {
+                let _ = tx.blocking_send(());
+            }
+        }
+    })?;
+
+    // Watch the directory so editors that replace the file are still picked up
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        while rx.recv().await.is_some() {
+            // Coalesce bursts of events from a single write
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            while rx.try_recv().is_ok() {}
+            reload(&state, &path).await;
+        }
+    });
+
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::qrm::{QuantumEra, ThreatIndicator};
+
+    fn signature_indicator() -> ThreatIndicator {
+        ThreatIndicator {
+            category: ThreatCategory::DigitalSignatures,
+            sub_category: "ECDSA/secp256k1".to_string(),
+            severity: 0.8,
+            confidence: 0.9,
+            source: "Test".to_string(),
+            timestamp: chrono::Utc::now(),
+            description: "Test indicator".to_string(),
+            era_relevance: QuantumEra::Nisq,
+            references: vec![],
+            tags: vec![],
+            expires_at: None,
+        }
+    }
+
+    fn temp_tuning_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("qrms-tuning-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("tuning.json")
+    }
+
+    #[tokio::test]
+    async fn test_file_change_updates_weights() {
+        let state = Arc::new(AppState::new());
+        state.qrm.lock().await.add_indicator(signature_indicator());
+        let default_score = state.qrm.lock().await.calculate_risk().score;
+
+        let path = temp_tuning_file("reload");
+        std::fs::write(&path, "{}").unwrap();
+        let _watcher = watch(state.clone(), path.clone()).await.unwrap();
+
+        // Put all weight on digital signatures
+        let weights: serde_json::Map<String, serde_json::Value> = ThreatCategory::all().iter()
+            .map(|c| {
+                let w = if *c == ThreatCategory::DigitalSignatures { 1.0 } else { 0.0 };
+                (serde_json::to_value(c).unwrap().as_str().unwrap().to_string(), w.into())
+            })
+            .collect();
+        std::fs::write(&path, serde_json::json!({ "weights": weights, "combiner_mode": "or" }).to_string()).unwrap();
+
+        let mut applied = false;
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            if state.qrm.lock().await.weight(ThreatCategory::DigitalSignatures) == 1.0 {
+                applied = true;
+                break;
+            }
+        }
+        assert!(applied, "tuning file change was not applied");
+
+        let tuned_score = state.qrm.lock().await.calculate_risk().score;
+        assert!(tuned_score > default_score);
+        assert_eq!(tuned_score, 8000);
+        assert_eq!(state.apqc.lock().await.combiner_mode, CombinerMode::Or);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_profile_keeps_previous_values() {
+        let state = AppState::new();
+        let path = temp_tuning_file("invalid");
+
+        std::fs::write(&path, r#"{ "threshold_scheduled": 9500, "threshold_emergency": 9000 }"#).unwrap();
+        reload(&state, &path).await;
+        std::fs::write(&path, r#"{ "weights": { "digital_signatures": 0.9 } }"#).unwrap();
+        reload(&state, &path).await;
+        std::fs::write(&path, "not json").unwrap();
+        reload(&state, &path).await;
+
+        let qrm = state.qrm.lock().await;
+        assert_eq!(qrm.threshold_scheduled, 6000);
+        assert_eq!(qrm.threshold_emergency, 9000);
+        assert_eq!(qrm.weight(ThreatCategory::DigitalSignatures), ThreatCategory::DigitalSignatures.weight());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}